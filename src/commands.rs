@@ -0,0 +1,302 @@
+use crate::{
+    environment::{AngleMode, Environment},
+    error::{CLMathError, CommandError, CommandErrorType},
+    tokenizer::Span,
+};
+
+/// Prefix that marks an input as a command changing the session instead of a math expression
+pub const COMMAND_PREFIX: char = ':';
+
+/// The largest number of significant digits of decimal numbers. Powers with fractional exponents already take
+/// about half a second with 300 digits in release builds and the time grows faster than quadratically.
+const MAX_PRECISION: u64 = 300;
+
+/// The largest recursion limit. The stack grows on the heap as needed, but a nested call with a deeply nested body
+/// takes about 80 KiB in debug builds, so deeper recursion would take too much memory.
+const MAX_RECURSION_LIMIT: usize = 1000;
+
+pub fn is_command(input: &str) -> bool {
+    input.starts_with(COMMAND_PREFIX)
+}
+
+/// Runs a session command, e.g. `:angle deg`, and returns a message describing the new state
+pub fn execute_command(input: &str, env: &mut Environment) -> Result<String, CLMathError> {
+    let prefix_len = input.len() - input.trim_start_matches(COMMAND_PREFIX).len();
+    let words = split_words(&input[prefix_len..], prefix_len);
+
+    let command = words.first().map_or("", |(word, _)| word);
+    let args = words
+        .iter()
+        .skip(1)
+        .map(|(word, _)| *word)
+        .collect::<Vec<_>>();
+
+    // Unknown commands are shown at the command, invalid arguments at all arguments
+    let command_span = Span::new(0, words.first().map_or(prefix_len, |(_, span)| span.end));
+    let args_span = match (words.get(1), words.last()) {
+        (Some((_, first)), Some((_, last))) => first.to(*last),
+        _ => Span::new(command_span.end, command_span.end),
+    };
+
+    match command {
+        "angle" => angle(&args, env),
+        "recursion" => recursion(&args, env),
+        "exact" => exact(&args, env),
+        "complex" => complex(&args, env),
+        "precision" => precision(&args, env),
+        _ => Err(create_error(
+            CommandErrorType::UnknownCommand,
+            command,
+            None,
+        )),
+    }
+    .map_err(|err| {
+        let span = match err.error_type {
+            CommandErrorType::UnknownCommand => command_span,
+            CommandErrorType::InvalidArgument => args_span,
+        };
+        CLMathError::Command(err.at(span))
+    })
+}
+
+/// Splits the input into its words with their spans, which start at `offset`
+fn split_words(input: &str, offset: usize) -> Vec<(&str, Span)> {
+    let mut words = Vec::new();
+    let mut word_start = None;
+
+    for (char_idx, (byte_idx, c)) in input.char_indices().enumerate() {
+        match (c.is_whitespace(), word_start) {
+            (false, None) => word_start = Some((byte_idx, char_idx)),
+            (true, Some((byte_start, char_start))) => {
+                words.push((
+                    &input[byte_start..byte_idx],
+                    Span::new(offset + char_start, offset + char_idx),
+                ));
+                word_start = None;
+            }
+            _ => (),
+        }
+    }
+
+    if let Some((byte_start, char_start)) = word_start {
+        words.push((
+            &input[byte_start..],
+            Span::new(offset + char_start, offset + input.chars().count()),
+        ));
+    }
+
+    words
+}
+
+fn angle(args: &[&str], env: &mut Environment) -> Result<String, CommandError> {
+    match args {
+        [] => (),
+        ["rad" | "radians"] => env.angle_mode = AngleMode::Radians,
+        ["deg" | "degrees"] => env.angle_mode = AngleMode::Degrees,
+        ["grad" | "gradians"] => env.angle_mode = AngleMode::Gradians,
+        _ => {
+            return Err(create_error(
+                CommandErrorType::InvalidArgument,
+                "angle",
+                Some("rad, deg or grad"),
+            ))
+        }
+    }
+
+    Ok(format!("Angle mode: {}", env.angle_mode))
+}
+
+fn recursion(args: &[&str], env: &mut Environment) -> Result<String, CommandError> {
+    match args {
+        [] => (),
+        [limit] => match limit.parse() {
+            Ok(limit) if (1..=MAX_RECURSION_LIMIT).contains(&limit) => env.recursion_limit = limit,
+            _ => {
+                return Err(create_error(
+                    CommandErrorType::InvalidArgument,
+                    "recursion",
+                    Some(&format!("a positive integer up to {}", MAX_RECURSION_LIMIT)),
+                ))
+            }
+        },
+        _ => {
+            return Err(create_error(
+                CommandErrorType::InvalidArgument,
+                "recursion",
+                Some(&format!("a positive integer up to {}", MAX_RECURSION_LIMIT)),
+            ))
+        }
+    }
+
+    Ok(format!("Recursion limit: {}", env.recursion_limit))
+}
+
+fn exact(args: &[&str], env: &mut Environment) -> Result<String, CommandError> {
+    match args {
+        [] => (),
+        ["on"] => env.exact_mode = true,
+        ["off"] => env.exact_mode = false,
+        _ => {
+            return Err(create_error(
+                CommandErrorType::InvalidArgument,
+                "exact",
+                Some("on or off"),
+            ))
+        }
+    }
+
+    Ok(format!(
+        "Exact mode: {}",
+        if env.exact_mode { "on" } else { "off" }
+    ))
+}
+
+fn complex(args: &[&str], env: &mut Environment) -> Result<String, CommandError> {
+    match args {
+        [] => (),
+        ["on"] => env.complex_mode = true,
+        ["off"] => env.complex_mode = false,
+        _ => {
+            return Err(create_error(
+                CommandErrorType::InvalidArgument,
+                "complex",
+                Some("on or off"),
+            ))
+        }
+    }
+
+    Ok(format!(
+        "Complex mode: {}",
+        if env.complex_mode { "on" } else { "off" }
+    ))
+}
+
+fn precision(args: &[&str], env: &mut Environment) -> Result<String, CommandError> {
+    match args {
+        [] => (),
+        ["off"] => env.precision = None,
+        [digits] => match digits.parse() {
+            Ok(digits) if (1..=MAX_PRECISION).contains(&digits) => env.precision = Some(digits),
+            _ => {
+                return Err(create_error(
+                    CommandErrorType::InvalidArgument,
+                    "precision",
+                    Some(&format!(
+                        "a number of digits from 1 to {} or off",
+                        MAX_PRECISION
+                    )),
+                ))
+            }
+        },
+        _ => {
+            return Err(create_error(
+                CommandErrorType::InvalidArgument,
+                "precision",
+                Some(&format!(
+                    "a number of digits from 1 to {} or off",
+                    MAX_PRECISION
+                )),
+            ))
+        }
+    }
+
+    Ok(match env.precision {
+        Some(digits) => format!("Precision: {} digits", digits),
+        None => "Precision: off (64bit floating point numbers)".to_string(),
+    })
+}
+
+fn create_error(
+    error_type: CommandErrorType,
+    command: &str,
+    expected: Option<&str>,
+) -> CommandError {
+    match error_type {
+        CommandErrorType::UnknownCommand => CommandError::new(
+            format!("'{}{}' is not a known command.", COMMAND_PREFIX, command),
+            error_type,
+        ),
+        CommandErrorType::InvalidArgument => CommandError::new(
+            format!(
+                "Invalid argument for the command '{}{}'. Expected {}.",
+                COMMAND_PREFIX,
+                command,
+                expected.unwrap()
+            ),
+            error_type,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_angle() {
+        let mut env = Environment::new();
+        assert_eq!(
+            execute_command(":angle deg", &mut env).unwrap(),
+            "Angle mode: degrees"
+        );
+        assert_eq!(env.angle_mode, AngleMode::Degrees);
+        assert!(execute_command(":angle turns", &mut env).is_err());
+        assert!(execute_command(":nothing", &mut env).is_err());
+    }
+
+    #[test]
+    fn test_error_spans() {
+        let mut env = Environment::new();
+        let span = |input| match execute_command(input, &mut Environment::new()) {
+            Err(CLMathError::Command(err)) => err.span,
+            _ => None,
+        };
+
+        assert_eq!(span(":nothing"), Some(Span::new(0, 8)));
+        assert_eq!(span(":angle  turns"), Some(Span::new(8, 13)));
+        assert_eq!(span(":precision 1 2"), Some(Span::new(11, 14)));
+        assert_eq!(span(":exact on off"), Some(Span::new(7, 13)));
+        assert!(execute_command(":angle rad", &mut env).is_ok());
+    }
+
+    #[test]
+    fn test_recursion() {
+        let mut env = Environment::new();
+        assert_eq!(
+            execute_command(":recursion 100", &mut env).unwrap(),
+            "Recursion limit: 100"
+        );
+        assert_eq!(env.recursion_limit, 100);
+        assert!(execute_command(":recursion 0", &mut env).is_err());
+        assert!(execute_command(":recursion 100000", &mut env).is_err());
+        assert_eq!(env.recursion_limit, 100);
+    }
+
+    #[test]
+    fn test_exact() {
+        let mut env = Environment::new();
+        assert_eq!(
+            execute_command(":exact on", &mut env).unwrap(),
+            "Exact mode: on"
+        );
+        assert!(env.exact_mode);
+        assert!(execute_command(":exact yes", &mut env).is_err());
+    }
+
+    #[test]
+    fn test_precision() {
+        let mut env = Environment::new();
+        assert_eq!(
+            execute_command(":precision 50", &mut env).unwrap(),
+            "Precision: 50 digits"
+        );
+        assert_eq!(env.precision, Some(50));
+        assert!(execute_command(":precision 0", &mut env).is_err());
+        assert!(execute_command(":precision 301", &mut env).is_err());
+        assert_eq!(
+            execute_command(":precision off", &mut env).unwrap(),
+            "Precision: off (64bit floating point numbers)"
+        );
+        assert_eq!(env.precision, None);
+    }
+}
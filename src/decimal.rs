@@ -0,0 +1,1244 @@
+use std::{num::NonZeroU64, str::FromStr};
+
+use bigdecimal::{BigDecimal, Context, RoundingMode};
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+use crate::{
+    environment::AngleMode,
+    error::{FunctionError, FunctionErrorType},
+    functions::{create_error, Constant, FunctionReturnType},
+};
+
+/// The number of additional digits with which a function is calculated before its result is rounded to the precision
+const GUARD_DIGITS: u64 = 10;
+
+/// The maximum number of additional digits. Results that are still ambiguous are so close to the middle of two
+/// numbers of the precision that rounding to either of them is accurate to far more digits than requested.
+const MAX_GUARD_DIGITS: u64 = 160;
+
+/// The largest order of magnitude a result may have, larger results take too long to calculate
+const MAX_ORDER_OF_MAGNITUDE: f64 = 1e12;
+
+/// Decimal numbers with a larger order of magnitude are displayed in scientific notation, e.g. `1e-120`
+const MAX_PLAIN_ORDER_OF_MAGNITUDE: i64 = 100;
+
+/// Rounds a decimal number to the provided number of significant digits
+fn round(num: &BigDecimal, precision: u64) -> BigDecimal {
+    num.with_precision_round(
+        NonZeroU64::new(precision).expect("The precision should be at least one digit."),
+        RoundingMode::HalfEven,
+    )
+}
+
+/// Calculates the function with more and more guard digits until two attempts round to the same result.
+/// A rounded approximation is only wrong if the exact result is closer to the middle of two numbers
+/// of the precision than the error of the approximation, which the more accurate attempt detects.
+fn round_correctly(precision: u64, function: impl Fn(u64) -> BigDecimal) -> BigDecimal {
+    let mut guard_digits = GUARD_DIGITS;
+    let mut result = round(&function(precision + guard_digits), precision);
+
+    loop {
+        guard_digits *= 2;
+        let next = round(&function(precision + guard_digits), precision);
+
+        if next == result || guard_digits >= MAX_GUARD_DIGITS {
+            return next;
+        }
+
+        result = next;
+    }
+}
+
+fn ten_to_the(exponent: u64) -> BigInt {
+    BigInt::from(10).pow(exponent as u32)
+}
+
+/// Returns 10^-digits, terms of a series that are smaller do not change the first digits of the result
+fn epsilon(digits: u64) -> BigDecimal {
+    BigDecimal::new(BigInt::one(), digits as i64)
+}
+
+/// Divides two decimal numbers with at least the provided number of significant digits, the result is truncated
+fn divide(a: &BigDecimal, b: &BigDecimal, digits: u64) -> BigDecimal {
+    let (a_digits, a_scale) = a.as_bigint_and_exponent();
+    let (b_digits, b_scale) = b.as_bigint_and_exponent();
+
+    // The dividend is shifted to the left until the integer quotient has enough digits
+    let shift = (digits as i64 + b.digits() as i64 - a.digits() as i64 + 1).max(0) as u64;
+
+    BigDecimal::new(
+        a_digits * ten_to_the(shift) / b_digits,
+        a_scale - b_scale + shift as i64,
+    )
+}
+
+fn square_root_series(num: &BigDecimal, digits: u64) -> BigDecimal {
+    num.sqrt_with_context(&Context::new(
+        NonZeroU64::new(digits).expect("The precision should be at least one digit."),
+        RoundingMode::HalfEven,
+    ))
+    .expect("The square root should only be calculated for numbers that are not negative.")
+}
+
+/// Raises the number to an integer power by repeated squaring
+fn integer_power(base: &BigDecimal, exponent: i64, digits: u64) -> BigDecimal {
+    let mut result = BigDecimal::one();
+    let mut square = base.clone();
+    let mut remaining = exponent.unsigned_abs();
+
+    while remaining > 0 {
+        if remaining % 2 == 1 {
+            result = round(&(result * &square), digits);
+        }
+
+        square = round(&square.square(), digits);
+        remaining /= 2;
+    }
+
+    if exponent < 0 {
+        divide(&BigDecimal::one(), &result, digits)
+    } else {
+        result
+    }
+}
+
+fn exponential_series(num: &BigDecimal, digits: u64) -> BigDecimal {
+    // e^x = (e^(x / 2^k))^(2^k), the number is halved until the series converges quickly
+    let mut reduced = num.clone();
+    let mut halvings = 0;
+
+    while reduced.abs() > epsilon(2) {
+        reduced = reduced.half();
+        halvings += 1;
+    }
+
+    // Every squaring doubles the relative error
+    let digits = digits + halvings / 3 + 2;
+    let threshold = epsilon(digits + 2);
+
+    let mut sum = BigDecimal::one();
+    let mut term = BigDecimal::one();
+    let mut n = 1;
+
+    loop {
+        term = divide(&(term * &reduced), &BigDecimal::from(n), digits);
+
+        if term.abs() < threshold {
+            break;
+        }
+
+        sum += &term;
+        n += 1;
+    }
+
+    for _ in 0..halvings {
+        sum = round(&sum.square(), digits);
+    }
+
+    sum
+}
+
+/// Calculates e^x - 1 without losing the digits of small numbers to the subtraction
+fn exponential_minus_one_series(num: &BigDecimal, digits: u64) -> BigDecimal {
+    if num.abs() > BigDecimal::new(5.into(), 1) {
+        return exponential_series(num, digits + 2) - BigDecimal::one();
+    }
+
+    let mut sum = num.clone();
+    let mut term = num.clone();
+    let mut n = 2;
+
+    loop {
+        term = divide(&(term * num), &BigDecimal::from(n), digits);
+
+        if term.is_zero() || term.abs() < sum.abs() * epsilon(digits + 2) {
+            return sum;
+        }
+
+        sum += &term;
+        n += 1;
+    }
+}
+
+/// Sums z + z^3/3 + z^5/5 + ..., which is the inverse hyperbolic tangent of z or,
+/// with alternating signs, the arctangent of z. It converges quickly for small z.
+fn odd_power_series(z: &BigDecimal, digits: u64, alternating: bool) -> BigDecimal {
+    if z.is_zero() {
+        return BigDecimal::zero();
+    }
+
+    let square = round(&z.square(), digits);
+    let threshold = z.abs() * epsilon(digits + 2);
+
+    let mut power = z.clone();
+    let mut sum = z.clone();
+    let mut n = 1;
+
+    loop {
+        power = round(&(power * &square), digits);
+        if alternating {
+            power = -power;
+        }
+        n += 2;
+
+        let term = divide(&power, &BigDecimal::from(n), digits);
+        if term.abs() < threshold {
+            return sum;
+        }
+
+        sum += term;
+    }
+}
+
+fn ln2(digits: u64) -> BigDecimal {
+    // ln(2) = 2 atanh(1/3)
+    odd_power_series(
+        &divide(&BigDecimal::one(), &BigDecimal::from(3), digits),
+        digits,
+        false,
+    )
+    .double()
+}
+
+fn pi(digits: u64) -> BigDecimal {
+    // Machin's formula: pi = 16 atan(1/5) - 4 atan(1/239)
+    let digits = digits + 3;
+    let atan_fifth = odd_power_series(
+        &divide(&BigDecimal::one(), &BigDecimal::from(5), digits),
+        digits,
+        true,
+    );
+    let atan_239th = odd_power_series(
+        &divide(&BigDecimal::one(), &BigDecimal::from(239), digits),
+        digits,
+        true,
+    );
+
+    atan_fifth * BigDecimal::from(16) - atan_239th * BigDecimal::from(4)
+}
+
+fn logarithm_series(num: &BigDecimal, digits: u64) -> BigDecimal {
+    // x = y * 2^k with y close to 1, so ln(x) = k ln(2) + ln(y)
+    let magnitude = num.order_of_magnitude();
+    let (num_digits, scale) = num.as_bigint_and_exponent();
+    let leading = BigDecimal::new(num_digits.clone(), scale + magnitude)
+        .to_f64()
+        .expect("The leading digits of a decimal number should fit into a floating point number.");
+    let k = (magnitude as f64 * std::f64::consts::LOG2_10 + leading.log2()).round() as i64;
+
+    let digits = digits + k.unsigned_abs().to_string().len() as u64 + 2;
+
+    // Dividing by a power of two is exact for decimal numbers: x / 2^k = x * 5^k / 10^k
+    let reduced = if k >= 0 {
+        BigDecimal::new(num_digits * BigInt::from(5).pow(k as u32), scale + k)
+    } else {
+        BigDecimal::new(num_digits * BigInt::from(2).pow(-k as u32), scale)
+    };
+    let reduced = round(&reduced, digits + 5);
+
+    // ln(y) = 2 atanh((y - 1) / (y + 1))
+    let z = divide(
+        &(&reduced - BigDecimal::one()),
+        &(&reduced + BigDecimal::one()),
+        digits,
+    );
+
+    ln2(digits) * BigDecimal::from(k) + odd_power_series(&z, digits, false).double()
+}
+
+fn arctangent_series(num: &BigDecimal, digits: u64) -> BigDecimal {
+    if num.is_zero() {
+        return BigDecimal::zero();
+    }
+
+    // atan(x) = sign(x) * pi/2 - atan(1/x)
+    if num.abs() > BigDecimal::one() {
+        let half_pi = pi(digits).half();
+        let inverse = arctangent_series(&divide(&BigDecimal::one(), num, digits), digits);
+
+        return if num.is_negative() {
+            -half_pi - inverse
+        } else {
+            half_pi - inverse
+        };
+    }
+
+    // atan(x) = 2 atan(x / (1 + sqrt(1 + x^2))) halves the number until the series converges quickly
+    let mut reduced = num.clone();
+    let mut doublings = 0;
+
+    while reduced.abs() > epsilon(1) {
+        let root = square_root_series(&(BigDecimal::one() + reduced.square()), digits);
+        reduced = divide(&reduced, &(BigDecimal::one() + root), digits);
+        doublings += 1;
+    }
+
+    odd_power_series(&reduced, digits, true) * BigDecimal::from(BigInt::from(2).pow(doublings))
+}
+
+/// Calculates the sine or the cosine of an angle in radians
+fn trigonometric_series(angle: &BigDecimal, digits: u64, cosine: bool) -> BigDecimal {
+    // sin(x - n pi) = (-1)^n sin(x), which reduces the angle to [-pi/2, pi/2]
+    let digits = digits + angle.order_of_magnitude().max(0) as u64 + 2;
+    let pi = pi(digits);
+    let half_turns = divide(angle, &pi, digits)
+        .round(0)
+        .as_bigint_and_exponent()
+        .0;
+    let reduced = angle - &pi * BigDecimal::from(half_turns.clone());
+
+    let square = round(&reduced.square(), digits);
+    let threshold = epsilon(digits + 2);
+
+    let (mut term, mut n) = if cosine {
+        (BigDecimal::one(), 0)
+    } else {
+        (reduced, 1)
+    };
+    let mut sum = term.clone();
+
+    loop {
+        term = -divide(
+            &(term * &square),
+            &BigDecimal::from((n + 1) * (n + 2)),
+            digits,
+        );
+        n += 2;
+
+        if term.abs() < threshold {
+            break;
+        }
+
+        sum += &term;
+    }
+
+    if half_turns.is_odd() {
+        -sum
+    } else {
+        sum
+    }
+}
+
+fn get_full_turn(angle_mode: &AngleMode, digits: u64) -> BigDecimal {
+    match angle_mode {
+        AngleMode::Radians => pi(digits).double(),
+        AngleMode::Degrees => BigDecimal::from(360),
+        AngleMode::Gradians => BigDecimal::from(400),
+    }
+}
+
+/// Converts an angle in the unit of the angle mode to radians. Full turns are removed exactly first.
+fn convert_to_radians(angle: &BigDecimal, angle_mode: &AngleMode, digits: u64) -> BigDecimal {
+    match angle_mode {
+        AngleMode::Radians => angle.clone(),
+        _ => {
+            let full_turn = get_full_turn(angle_mode, digits);
+            divide(
+                &((angle % &full_turn) * pi(digits).double()),
+                &full_turn,
+                digits,
+            )
+        }
+    }
+}
+
+fn convert_from_radians(angle: &BigDecimal, angle_mode: &AngleMode, digits: u64) -> BigDecimal {
+    match angle_mode {
+        AngleMode::Radians => angle.clone(),
+        _ => divide(
+            &(angle * get_full_turn(angle_mode, digits)),
+            &pi(digits).double(),
+            digits,
+        ),
+    }
+}
+
+/// Returns the number of quarter turns modulo 4 if the angle is an exact multiple of a quarter turn
+fn get_quarter_turns(angle: &BigDecimal, angle_mode: &AngleMode) -> Option<u8> {
+    if *angle_mode == AngleMode::Radians {
+        return None;
+    }
+
+    let quarter_turn = get_full_turn(angle_mode, 1).half().half();
+    if !(angle % &quarter_turn).is_zero() {
+        return None;
+    }
+
+    let quarter_turns = divide(angle, &quarter_turn, angle.digits() + 2)
+        .round(0)
+        .as_bigint_and_exponent()
+        .0;
+
+    quarter_turns.mod_floor(&BigInt::from(4)).to_u8()
+}
+
+/// Returns an error if the number is not in the domain of logarithms, i.e. if it is not positive
+fn check_logarithm_domain(num: &BigDecimal, operation_name: &str) -> Result<(), FunctionError> {
+    if num.is_zero() {
+        Err(create_error(
+            FunctionErrorType::LogarithmOfZero,
+            0.0,
+            None,
+            Some(operation_name),
+        ))
+    } else if num.is_negative() {
+        Err(create_error(
+            FunctionErrorType::LogarithmOfNegative,
+            to_f64(num),
+            None,
+            Some(operation_name),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns an error if the result of the operation would have an order of magnitude that is too large to calculate
+fn check_order_of_magnitude(
+    order_of_magnitude: f64,
+    num: &BigDecimal,
+    operation_name: &str,
+) -> Result<(), FunctionError> {
+    if order_of_magnitude.abs() > MAX_ORDER_OF_MAGNITUDE || order_of_magnitude.is_nan() {
+        return Err(FunctionError::new(
+            format!(
+                "The result of the {} of {} has too many digits to be calculated as a decimal number.",
+                operation_name,
+                format(num)
+            ),
+            FunctionErrorType::OverflowInf,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Formats a decimal number without trailing zeros, very small and very large numbers are displayed
+/// in scientific notation like 64bit floating point numbers in error messages, e.g. `2.5e-120`
+pub fn format(num: &BigDecimal) -> String {
+    let (digits, scale) = num.normalized().as_bigint_and_exponent();
+    let sign = if digits.is_negative() { "-" } else { "" };
+    let digits = digits.abs().to_string();
+    let order_of_magnitude = digits.len() as i64 - 1 - scale;
+
+    if digits == "0" {
+        "0".to_string()
+    } else if order_of_magnitude.abs() > MAX_PLAIN_ORDER_OF_MAGNITUDE {
+        let (first, rest) = digits.split_at(1);
+        let fraction = if rest.is_empty() {
+            String::new()
+        } else {
+            format!(".{rest}")
+        };
+        format!("{sign}{first}{fraction}e{order_of_magnitude}")
+    } else if scale <= 0 {
+        format!("{sign}{digits}{}", "0".repeat(-scale as usize))
+    } else if scale as usize >= digits.len() {
+        format!(
+            "{sign}0.{}{digits}",
+            "0".repeat(scale as usize - digits.len())
+        )
+    } else {
+        let (integer, fraction) = digits.split_at(digits.len() - scale as usize);
+        format!("{sign}{integer}.{fraction}")
+    }
+}
+
+/// Converts a decimal number to the nearest 64bit floating point number, e.g. for error messages
+pub fn to_f64(num: &BigDecimal) -> f64 {
+    num.to_f64().unwrap_or(f64::NAN)
+}
+
+/// Converts a 64bit floating point number to the decimal number with the shortest representation
+/// that has the same floating point value, e.g. 0.1 instead of 0.1000000000000000055511151231257827
+pub fn from_f64(num: f64) -> Option<BigDecimal> {
+    if !num.is_finite() {
+        return None;
+    }
+
+    BigDecimal::from_str(&num.to_string()).ok()
+}
+
+/// Converts a fraction to the nearest decimal number with the provided number of significant digits
+pub fn from_rational(num: &BigRational, precision: u64) -> BigDecimal {
+    round_correctly(precision, |digits| {
+        divide(
+            &BigDecimal::from(num.numer().clone()),
+            &BigDecimal::from(num.denom().clone()),
+            digits,
+        )
+    })
+}
+
+/// Converts a decimal number to the fraction with exactly the same value
+pub fn to_rational(num: &BigDecimal) -> BigRational {
+    let (digits, scale) = num.as_bigint_and_exponent();
+
+    if scale >= 0 {
+        BigRational::new(digits, ten_to_the(scale as u64))
+    } else {
+        BigRational::from(digits * ten_to_the(scale.unsigned_abs()))
+    }
+}
+
+/// Returns the value of the constant with the provided number of significant digits
+pub fn constant(constant: &Constant, precision: u64) -> FunctionReturnType {
+    let value = match constant {
+        Constant::Pi => round_correctly(precision, pi),
+        Constant::Tau => round_correctly(precision, |digits| pi(digits).double()),
+        Constant::E => round_correctly(precision, |digits| {
+            exponential_series(&BigDecimal::one(), digits)
+        }),
+        Constant::Phi => round_correctly(precision, |digits| {
+            (BigDecimal::one() + square_root_series(&BigDecimal::from(5), digits)).half()
+        }),
+        // Physical constants are defined with less digits than any precision needs
+        constant => match from_f64(constant.get_value()) {
+            Some(value) => round(&value, precision),
+            None => return FunctionReturnType::F64(constant.get_value()),
+        },
+    };
+
+    FunctionReturnType::Decimal(value)
+}
+
+/// Returns true if the first of the two provided decimal numbers is smaller than the second else false
+pub fn less_than(a: &BigDecimal, b: &BigDecimal) -> FunctionReturnType {
+    FunctionReturnType::Bool(a < b)
+}
+
+/// Returns true if the first of the two provided decimal numbers is greater than the second else false
+pub fn greater_than(a: &BigDecimal, b: &BigDecimal) -> FunctionReturnType {
+    FunctionReturnType::Bool(a > b)
+}
+
+/// Returns the absolute value of the provided decimal number
+pub fn absolute(num: &BigDecimal) -> FunctionReturnType {
+    FunctionReturnType::Decimal(num.abs())
+}
+
+/// Returns the negated value of the provided decimal number
+pub fn negation(num: &BigDecimal) -> FunctionReturnType {
+    FunctionReturnType::Decimal(-num)
+}
+
+/// Returns the minimum of the two provided decimal numbers
+pub fn minimum(a: &BigDecimal, b: &BigDecimal) -> FunctionReturnType {
+    FunctionReturnType::Decimal(a.min(b).clone())
+}
+
+/// Returns the maximum of the two provided decimal numbers
+pub fn maximum(a: &BigDecimal, b: &BigDecimal) -> FunctionReturnType {
+    FunctionReturnType::Decimal(a.max(b).clone())
+}
+
+/// Calculates the sum of two decimal numbers rounded to the precision
+pub fn addition(a: &BigDecimal, b: &BigDecimal, precision: u64) -> FunctionReturnType {
+    FunctionReturnType::Decimal(round(&(a + b), precision))
+}
+
+/// Calculates the difference of two decimal numbers rounded to the precision
+pub fn subtraction(a: &BigDecimal, b: &BigDecimal, precision: u64) -> FunctionReturnType {
+    FunctionReturnType::Decimal(round(&(a - b), precision))
+}
+
+/// Calculates the product of two decimal numbers rounded to the precision
+pub fn multiplication(a: &BigDecimal, b: &BigDecimal, precision: u64) -> FunctionReturnType {
+    FunctionReturnType::Decimal(round(&(a * b), precision))
+}
+
+/// Calculates the quotient of two decimal numbers rounded to the precision
+pub fn division(
+    a: &BigDecimal,
+    b: &BigDecimal,
+    precision: u64,
+) -> Result<FunctionReturnType, FunctionError> {
+    if b.is_zero() {
+        return Err(create_error(
+            FunctionErrorType::DivisionByZero,
+            to_f64(a),
+            Some(0.0),
+            None,
+        ));
+    }
+
+    Ok(FunctionReturnType::Decimal(round_correctly(
+        precision,
+        |digits| divide(a, b, digits),
+    )))
+}
+
+/// Calculates the modulus of the division of two decimal numbers. The result has the sign of the dividend.
+pub fn modulo(
+    a: &BigDecimal,
+    n: &BigDecimal,
+    precision: u64,
+) -> Result<FunctionReturnType, FunctionError> {
+    if n.is_zero() {
+        return Err(create_error(
+            FunctionErrorType::DivisionByZero,
+            to_f64(a),
+            Some(0.0),
+            None,
+        ));
+    }
+
+    Ok(FunctionReturnType::Decimal(round(&(a % n), precision)))
+}
+
+/// Calculates the euclidean modulus of the division of two decimal numbers. The result is never negative.
+pub fn modulo_euclid(
+    a: &BigDecimal,
+    n: &BigDecimal,
+    precision: u64,
+) -> Result<FunctionReturnType, FunctionError> {
+    if n.is_zero() {
+        return Err(create_error(
+            FunctionErrorType::DivisionByZero,
+            to_f64(a),
+            Some(0.0),
+            None,
+        ));
+    }
+
+    let n = n.abs();
+    let remainder = a % &n;
+    let remainder = if remainder.is_negative() {
+        remainder + n
+    } else {
+        remainder
+    };
+
+    Ok(FunctionReturnType::Decimal(round(&remainder, precision)))
+}
+
+/// Raises the first decimal number to the power of the second
+pub fn exponentiation(
+    base: &BigDecimal,
+    exponent: &BigDecimal,
+    precision: u64,
+) -> Result<FunctionReturnType, FunctionError> {
+    let order_of_magnitude = to_f64(exponent) * (base.order_of_magnitude() as f64 + 1.0).max(1.0);
+    check_order_of_magnitude(order_of_magnitude, base, "exponentiation")?;
+
+    if base.is_zero() {
+        return match exponent.sign() {
+            num_bigint::Sign::Plus => Ok(FunctionReturnType::Decimal(BigDecimal::zero())),
+            num_bigint::Sign::NoSign => Ok(FunctionReturnType::Decimal(BigDecimal::one())),
+            num_bigint::Sign::Minus => Err(create_error(
+                FunctionErrorType::DivisionByZero,
+                0.0,
+                Some(to_f64(exponent)),
+                Some("exponentiation"),
+            )),
+        };
+    }
+
+    if exponent.is_integer() {
+        let exponent = exponent
+            .to_i64()
+            .expect("The exponent should be small enough after checking the order of magnitude.");
+
+        return Ok(FunctionReturnType::Decimal(round_correctly(
+            precision,
+            |digits| integer_power(base, exponent, digits),
+        )));
+    }
+
+    if base.is_negative() {
+        return Err(create_error(
+            FunctionErrorType::DomainError,
+            to_f64(base),
+            Some(to_f64(exponent)),
+            Some("exponentiation"),
+        ));
+    }
+
+    // b^x = e^(x ln(b)), the logarithm needs additional digits for the integer digits of the product
+    let extra_digits = order_of_magnitude.abs().log10().max(0.0) as u64 + 2;
+
+    Ok(FunctionReturnType::Decimal(round_correctly(
+        precision,
+        |digits| {
+            exponential_series(
+                &(exponent * logarithm_series(base, digits + extra_digits)),
+                digits,
+            )
+        },
+    )))
+}
+
+/// Calculates the square root of a decimal number
+pub fn square_root(num: &BigDecimal, precision: u64) -> Result<FunctionReturnType, FunctionError> {
+    if num.is_negative() {
+        return Err(create_error(
+            FunctionErrorType::DomainError,
+            to_f64(num),
+            Some(2.0),
+            Some("root"),
+        ));
+    }
+
+    Ok(FunctionReturnType::Decimal(round_correctly(
+        precision,
+        |digits| square_root_series(num, digits),
+    )))
+}
+
+/// Calculates the cube root of a decimal number
+pub fn cube_root(num: &BigDecimal, precision: u64) -> Result<FunctionReturnType, FunctionError> {
+    root(num, &BigDecimal::from(3), precision)
+}
+
+/// Calculates the n-th root of a decimal number.
+/// Odd roots of negative numbers are negative, even roots of negative numbers have no real result.
+pub fn root(
+    num: &BigDecimal,
+    n: &BigDecimal,
+    precision: u64,
+) -> Result<FunctionReturnType, FunctionError> {
+    if n.is_zero() || (num.is_negative() && !(n.is_integer() && n % BigDecimal::from(2) != 0)) {
+        return Err(create_error(
+            FunctionErrorType::DomainError,
+            to_f64(num),
+            Some(to_f64(n)),
+            Some("root"),
+        ));
+    }
+
+    if num.is_zero() {
+        return Ok(FunctionReturnType::Decimal(BigDecimal::zero()));
+    }
+
+    let order_of_magnitude = num.order_of_magnitude() as f64 / to_f64(n);
+    check_order_of_magnitude(order_of_magnitude, num, "root")?;
+    let extra_digits = order_of_magnitude.abs().log10().max(0.0) as u64 + 2;
+
+    // The n-th root of x is e^(ln(|x|) / n) with the sign of x
+    let result = round_correctly(precision, |digits| {
+        exponential_series(
+            &divide(
+                &logarithm_series(&num.abs(), digits + extra_digits),
+                n,
+                digits + extra_digits,
+            ),
+            digits,
+        )
+    });
+
+    Ok(FunctionReturnType::Decimal(if num.is_negative() {
+        -result
+    } else {
+        result
+    }))
+}
+
+/// Calculates the sine of an angle given in the unit of the angle mode
+pub fn sine(angle: &BigDecimal, precision: u64, angle_mode: &AngleMode) -> FunctionReturnType {
+    FunctionReturnType::Decimal(match get_quarter_turns(angle, angle_mode) {
+        Some(0 | 2) => BigDecimal::zero(),
+        Some(1) => BigDecimal::one(),
+        Some(3) => -BigDecimal::one(),
+        _ => round_correctly(precision, |digits| {
+            trigonometric_series(
+                &convert_to_radians(angle, angle_mode, digits),
+                digits,
+                false,
+            )
+        }),
+    })
+}
+
+/// Calculates the cosine of an angle given in the unit of the angle mode
+pub fn cosine(angle: &BigDecimal, precision: u64, angle_mode: &AngleMode) -> FunctionReturnType {
+    FunctionReturnType::Decimal(match get_quarter_turns(angle, angle_mode) {
+        Some(0) => BigDecimal::one(),
+        Some(1 | 3) => BigDecimal::zero(),
+        Some(2) => -BigDecimal::one(),
+        _ => round_correctly(precision, |digits| {
+            trigonometric_series(&convert_to_radians(angle, angle_mode, digits), digits, true)
+        }),
+    })
+}
+
+/// Calculates the tangent of an angle given in the unit of the angle mode
+pub fn tangent(
+    angle: &BigDecimal,
+    precision: u64,
+    angle_mode: &AngleMode,
+) -> Result<FunctionReturnType, FunctionError> {
+    match get_quarter_turns(angle, angle_mode) {
+        Some(0 | 2) => Ok(FunctionReturnType::Decimal(BigDecimal::zero())),
+        Some(_) => Err(create_error(
+            FunctionErrorType::DomainError,
+            to_f64(angle),
+            None,
+            Some("tangent"),
+        )),
+        None => Ok(FunctionReturnType::Decimal(round_correctly(
+            precision,
+            |digits| {
+                let radians = convert_to_radians(angle, angle_mode, digits);
+                divide(
+                    &trigonometric_series(&radians, digits, false),
+                    &trigonometric_series(&radians, digits, true),
+                    digits,
+                )
+            },
+        ))),
+    }
+}
+
+/// Calculates the arcsine of a number in the range [-1, 1] and returns it in the unit of the angle mode
+pub fn arcsine(
+    num: &BigDecimal,
+    precision: u64,
+    angle_mode: &AngleMode,
+) -> Result<FunctionReturnType, FunctionError> {
+    if num.abs() > BigDecimal::one() {
+        return Err(create_error(
+            FunctionErrorType::DomainError,
+            to_f64(num),
+            None,
+            Some("arcsine"),
+        ));
+    }
+
+    // asin(x) = atan(x / sqrt(1 - x^2)), the arcsine of 1 and -1 is a quarter turn
+    Ok(FunctionReturnType::Decimal(round_correctly(
+        precision,
+        |digits| {
+            let radians = if num.abs().is_one() {
+                pi(digits).half() * BigDecimal::from(num.sign() as i8 - 1)
+            } else {
+                let root = square_root_series(&(BigDecimal::one() - num.square()), digits);
+                arctangent_series(&divide(num, &root, digits), digits)
+            };
+
+            convert_from_radians(&radians, angle_mode, digits)
+        },
+    )))
+}
+
+/// Calculates the arccosine of a number in the range [-1, 1] and returns it in the unit of the angle mode
+pub fn arccosine(
+    num: &BigDecimal,
+    precision: u64,
+    angle_mode: &AngleMode,
+) -> Result<FunctionReturnType, FunctionError> {
+    if num.abs() > BigDecimal::one() {
+        return Err(create_error(
+            FunctionErrorType::DomainError,
+            to_f64(num),
+            None,
+            Some("arccosine"),
+        ));
+    }
+
+    // acos(x) = 2 atan(sqrt((1 - x) / (1 + x))), which is accurate for numbers close to 1, the arccosine of -1 is a half turn
+    Ok(FunctionReturnType::Decimal(round_correctly(
+        precision,
+        |digits| {
+            let radians = if (num + BigDecimal::one()).is_zero() {
+                pi(digits)
+            } else {
+                let quotient = divide(
+                    &(BigDecimal::one() - num),
+                    &(BigDecimal::one() + num),
+                    digits,
+                );
+                arctangent_series(&square_root_series(&quotient, digits), digits).double()
+            };
+
+            convert_from_radians(&radians, angle_mode, digits)
+        },
+    )))
+}
+
+/// Calculates the arctangent of a number and returns it in the unit of the angle mode
+pub fn arctangent(num: &BigDecimal, precision: u64, angle_mode: &AngleMode) -> FunctionReturnType {
+    FunctionReturnType::Decimal(round_correctly(precision, |digits| {
+        convert_from_radians(&arctangent_series(num, digits), angle_mode, digits)
+    }))
+}
+
+/// Calculates the angle of the point (x, y) to the positive x axis and returns it in the unit of the angle mode
+pub fn arctangent2(
+    y: &BigDecimal,
+    x: &BigDecimal,
+    precision: u64,
+    angle_mode: &AngleMode,
+) -> FunctionReturnType {
+    FunctionReturnType::Decimal(round_correctly(precision, |digits| {
+        let radians = if x.is_zero() {
+            pi(digits).half() * BigDecimal::from(y.sign() as i8 - 1)
+        } else {
+            let angle = arctangent_series(&divide(y, x, digits), digits);
+
+            match (x.is_negative(), y.is_negative()) {
+                (false, _) => angle,
+                (true, false) => angle + pi(digits),
+                (true, true) => angle - pi(digits),
+            }
+        };
+
+        convert_from_radians(&radians, angle_mode, digits)
+    }))
+}
+
+/// Calculates the hyperbolic sine of a decimal number
+pub fn hyperbolic_sine(
+    num: &BigDecimal,
+    precision: u64,
+) -> Result<FunctionReturnType, FunctionError> {
+    check_order_of_magnitude(
+        to_f64(num) / std::f64::consts::LN_10,
+        num,
+        "hyperbolic sine",
+    )?;
+
+    // sinh(x) = (expm1(x) - expm1(-x)) / 2, which is accurate for numbers close to 0
+    Ok(FunctionReturnType::Decimal(round_correctly(
+        precision,
+        |digits| {
+            (exponential_minus_one_series(num, digits)
+                - exponential_minus_one_series(&-num, digits))
+            .half()
+        },
+    )))
+}
+
+/// Calculates the hyperbolic cosine of a decimal number
+pub fn hyperbolic_cosine(
+    num: &BigDecimal,
+    precision: u64,
+) -> Result<FunctionReturnType, FunctionError> {
+    check_order_of_magnitude(
+        to_f64(num) / std::f64::consts::LN_10,
+        num,
+        "hyperbolic cosine",
+    )?;
+
+    Ok(FunctionReturnType::Decimal(round_correctly(
+        precision,
+        |digits| (exponential_series(num, digits) + exponential_series(&-num, digits)).half(),
+    )))
+}
+
+/// Calculates the hyperbolic tangent of a decimal number
+pub fn hyperbolic_tangent(num: &BigDecimal, precision: u64) -> FunctionReturnType {
+    FunctionReturnType::Decimal(round_correctly(precision, |digits| {
+        // The distance of the result to 1 or -1 is below the precision
+        if num.abs() > digits {
+            return BigDecimal::from(num.sign() as i8 - 1);
+        }
+
+        // tanh(x) = expm1(2x) / (expm1(2x) + 2)
+        let exponential = exponential_minus_one_series(&num.double(), digits);
+        divide(&exponential, &(&exponential + BigDecimal::from(2)), digits)
+    }))
+}
+
+/// Calculates the inverse hyperbolic sine of a decimal number
+pub fn area_hyperbolic_sine(num: &BigDecimal, precision: u64) -> FunctionReturnType {
+    // asinh(x) = sign(x) ln(1 + |x| + x^2 / (1 + sqrt(1 + x^2))), which is accurate for numbers close to 0
+    FunctionReturnType::Decimal(round_correctly(precision, |digits| {
+        let square = num.square();
+        let root = square_root_series(&(BigDecimal::one() + &square), digits);
+        let result = logarithm_series(
+            &(BigDecimal::one() + num.abs() + divide(&square, &(BigDecimal::one() + root), digits)),
+            digits,
+        );
+
+        if num.is_negative() {
+            -result
+        } else {
+            result
+        }
+    }))
+}
+
+/// Calculates the inverse hyperbolic cosine of a decimal number that is at least 1
+pub fn area_hyperbolic_cosine(
+    num: &BigDecimal,
+    precision: u64,
+) -> Result<FunctionReturnType, FunctionError> {
+    if *num < BigDecimal::one() {
+        return Err(create_error(
+            FunctionErrorType::DomainError,
+            to_f64(num),
+            None,
+            Some("inverse hyperbolic cosine"),
+        ));
+    }
+
+    // acosh(x) = ln(1 + (x - 1) + sqrt((x - 1)(x + 1))), which is accurate for numbers close to 1
+    Ok(FunctionReturnType::Decimal(round_correctly(
+        precision,
+        |digits| {
+            let distance = num - BigDecimal::one();
+            let root = square_root_series(&(&distance * (num + BigDecimal::one())), digits);
+            logarithm_series(&(BigDecimal::one() + distance + root), digits)
+        },
+    )))
+}
+
+/// Calculates the inverse hyperbolic tangent of a decimal number in the range (-1, 1)
+pub fn area_hyperbolic_tangent(
+    num: &BigDecimal,
+    precision: u64,
+) -> Result<FunctionReturnType, FunctionError> {
+    if num.abs() >= BigDecimal::one() {
+        return Err(create_error(
+            FunctionErrorType::DomainError,
+            to_f64(num),
+            None,
+            Some("inverse hyperbolic tangent"),
+        ));
+    }
+
+    // atanh(x) = ln((1 + x) / (1 - x)) / 2
+    Ok(FunctionReturnType::Decimal(round_correctly(
+        precision,
+        |digits| {
+            logarithm_series(
+                &divide(
+                    &(BigDecimal::one() + num),
+                    &(BigDecimal::one() - num),
+                    digits,
+                ),
+                digits,
+            )
+            .half()
+        },
+    )))
+}
+
+/// Calculates the natural logarithm of a positive decimal number
+pub fn natural_logarithm(
+    num: &BigDecimal,
+    precision: u64,
+) -> Result<FunctionReturnType, FunctionError> {
+    check_logarithm_domain(num, "natural logarithm")?;
+
+    Ok(FunctionReturnType::Decimal(round_correctly(
+        precision,
+        |digits| logarithm_series(num, digits),
+    )))
+}
+
+/// Calculates the logarithm to base 10 of a positive decimal number
+pub fn decimal_logarithm(
+    num: &BigDecimal,
+    precision: u64,
+) -> Result<FunctionReturnType, FunctionError> {
+    logarithm(num, &BigDecimal::from(10), precision)
+}
+
+/// Calculates the logarithm to base 2 of a positive decimal number
+pub fn binary_logarithm(
+    num: &BigDecimal,
+    precision: u64,
+) -> Result<FunctionReturnType, FunctionError> {
+    logarithm(num, &BigDecimal::from(2), precision)
+}
+
+/// Calculates the logarithm of a positive decimal number to a positive base that is not 1
+pub fn logarithm(
+    num: &BigDecimal,
+    base: &BigDecimal,
+    precision: u64,
+) -> Result<FunctionReturnType, FunctionError> {
+    check_logarithm_domain(num, "logarithm")?;
+
+    if !base.is_positive() || base.is_one() {
+        return Err(create_error(
+            FunctionErrorType::InvalidLogarithmBase,
+            to_f64(num),
+            Some(to_f64(base)),
+            None,
+        ));
+    }
+
+    Ok(FunctionReturnType::Decimal(round_correctly(
+        precision,
+        |digits| {
+            divide(
+                &logarithm_series(num, digits),
+                &logarithm_series(base, digits),
+                digits,
+            )
+        },
+    )))
+}
+
+/// Calculates e raised to the power of a decimal number
+pub fn exponential(num: &BigDecimal, precision: u64) -> Result<FunctionReturnType, FunctionError> {
+    check_order_of_magnitude(to_f64(num) / std::f64::consts::LN_10, num, "exponential")?;
+
+    Ok(FunctionReturnType::Decimal(round_correctly(
+        precision,
+        |digits| exponential_series(num, digits),
+    )))
+}
+
+/// Calculates e raised to the power of a decimal number minus one, which is accurate for numbers close to 0
+pub fn exponential_minus_one(
+    num: &BigDecimal,
+    precision: u64,
+) -> Result<FunctionReturnType, FunctionError> {
+    check_order_of_magnitude(
+        to_f64(num) / std::f64::consts::LN_10,
+        num,
+        "exponential minus one",
+    )?;
+
+    Ok(FunctionReturnType::Decimal(round_correctly(
+        precision,
+        |digits| exponential_minus_one_series(num, digits),
+    )))
+}
+
+/// Calculates the natural logarithm of a decimal number plus one. The sum is exact, so it is accurate for numbers close to 0.
+pub fn natural_logarithm_one_plus(
+    num: &BigDecimal,
+    precision: u64,
+) -> Result<FunctionReturnType, FunctionError> {
+    // The domain of the sum is checked, but the error reports the number that was passed
+    natural_logarithm(&(num + BigDecimal::one()), precision)
+        .map_err(|err| create_error(err.error_type, to_f64(num), None, Some("Ln1p")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decimal(num: &str) -> BigDecimal {
+        BigDecimal::from_str(num).unwrap()
+    }
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(
+            constant(&Constant::Pi, 50).to_string(),
+            "3.1415926535897932384626433832795028841971693993751"
+        );
+        assert_eq!(
+            constant(&Constant::E, 30).to_string(),
+            "2.71828182845904523536028747135"
+        );
+        assert_eq!(
+            constant(&Constant::Phi, 20).to_string(),
+            "1.6180339887498948482"
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(format(&decimal("0")), "0");
+        assert_eq!(format(&decimal("-12.5000")), "-12.5");
+        assert_eq!(
+            format(&decimal("1e-60")),
+            "0.000000000000000000000000000000000000000000000000000000000001"
+        );
+        assert_eq!(
+            format(&decimal("1e60")),
+            "1000000000000000000000000000000000000000000000000000000000000"
+        );
+        assert_eq!(format(&decimal("1e-120")), "1e-120");
+        assert_eq!(format(&decimal("-2.80666e43400")), "-2.80666e43400");
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(
+            division(&decimal("1"), &decimal("3"), 20)
+                .unwrap()
+                .to_string(),
+            "0.33333333333333333333"
+        );
+        assert_eq!(
+            division(&decimal("2"), &decimal("3"), 5)
+                .unwrap()
+                .to_string(),
+            "0.66667"
+        );
+        assert_eq!(
+            addition(&decimal("0.1"), &decimal("0.2"), 50).to_string(),
+            "0.3"
+        );
+        assert_eq!(
+            modulo_euclid(&decimal("-7.5"), &decimal("2"), 10)
+                .unwrap()
+                .to_string(),
+            "0.5"
+        );
+        assert!(division(&decimal("1"), &BigDecimal::zero(), 10).is_err());
+    }
+
+    #[test]
+    fn test_roots_and_powers() {
+        assert_eq!(
+            square_root(&decimal("2"), 40).unwrap().to_string(),
+            "1.41421356237309504880168872420969807857"
+        );
+        assert_eq!(cube_root(&decimal("-27"), 20).unwrap().to_string(), "-3");
+        assert_eq!(
+            exponentiation(&decimal("2"), &decimal("0.5"), 30)
+                .unwrap()
+                .to_string(),
+            "1.41421356237309504880168872421"
+        );
+        assert_eq!(
+            exponentiation(&decimal("1.5"), &decimal("-3"), 10)
+                .unwrap()
+                .to_string(),
+            "0.2962962963"
+        );
+        assert!(square_root(&decimal("-1"), 10).is_err());
+    }
+
+    #[test]
+    fn test_transcendental_functions() {
+        assert_eq!(
+            natural_logarithm(&decimal("2"), 40).unwrap().to_string(),
+            "0.6931471805599453094172321214581765680755"
+        );
+        assert_eq!(
+            decimal_logarithm(&decimal("1000"), 30).unwrap().to_string(),
+            "3"
+        );
+        assert_eq!(
+            exponential(&decimal("-1"), 25).unwrap().to_string(),
+            "0.3678794411714423215955238"
+        );
+        assert_eq!(
+            natural_logarithm_one_plus(&decimal("1e-30"), 10)
+                .unwrap()
+                .to_string(),
+            "0.000000000000000000000000000001"
+        );
+        assert_eq!(
+            sine(&decimal("1"), 30, &AngleMode::Radians).to_string(),
+            "0.84147098480789650665250232163"
+        );
+        assert_eq!(
+            cosine(&decimal("540"), 30, &AngleMode::Degrees).to_string(),
+            "-1"
+        );
+        assert_eq!(
+            arctangent(&decimal("1"), 30, &AngleMode::Degrees).to_string(),
+            "45"
+        );
+        assert_eq!(
+            arcsine(&decimal("0.5"), 20, &AngleMode::Radians)
+                .unwrap()
+                .to_string(),
+            "0.52359877559829887308"
+        );
+        assert!(natural_logarithm(&decimal("-1"), 10).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+use std::{collections::HashMap, f64::consts::TAU, fmt::Display, rc::Rc};
+
+use crate::{functions::FunctionReturnType, parser::Expression};
+
+/// The default maximum number of nested calls of user-defined functions
+pub const DEFAULT_RECURSION_LIMIT: usize = 256;
+
+/// Holds the state of a session that persists between the evaluation of multiple inputs
+#[derive(Debug)]
+pub struct Environment {
+    variables: HashMap<String, FunctionReturnType>,
+    functions: HashMap<String, Closure>,
+    scopes: Vec<HashMap<String, FunctionReturnType>>,
+    /// The number of scopes that belong to function calls, other scopes bind variables, e.g. of `Sum` or `Let`
+    call_depth: usize,
+    pub angle_mode: AngleMode,
+    pub recursion_limit: usize,
+    /// Decimal literals and divisions of integers result in fractions instead of floating point numbers
+    pub exact_mode: bool,
+    /// Square roots, logarithms and powers of negative numbers result in complex numbers instead of errors
+    pub complex_mode: bool,
+    /// The number of significant digits of decimal numbers, 64bit floating point numbers are used if there is none
+    pub precision: Option<u64>,
+    /// Constants are evaluated to symbolic expressions instead of numbers, e.g. `Pi` in `Pi*x`
+    pub symbolic_constants: bool,
+}
+
+/// A function defined by the user, either named, e.g. `f(x, y) = x^2 + y`, or anonymous, e.g. `x -> x * 2`
+#[derive(Debug, Clone)]
+pub struct Closure {
+    pub params: Vec<String>,
+    pub body: Rc<Expression>,
+    /// The variables of the scope in which an anonymous function was created
+    pub captured: HashMap<String, FunctionReturnType>,
+}
+
+impl Closure {
+    pub fn new(
+        params: Vec<String>,
+        body: Rc<Expression>,
+        captured: HashMap<String, FunctionReturnType>,
+    ) -> Self {
+        Self {
+            params,
+            body,
+            captured,
+        }
+    }
+}
+
+impl Display for Closure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.params.as_slice() {
+            [param] => write!(f, "{} -> {}", param, self.body),
+            params => write!(f, "({}) -> {}", params.join(", "), self.body),
+        }
+    }
+}
+
+/// The unit in which trigonometric functions expect and return angles
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum AngleMode {
+    #[default]
+    Radians,
+    Degrees,
+    Gradians,
+}
+
+impl AngleMode {
+    /// Returns the size of a full turn in this unit
+    pub fn get_full_turn(&self) -> f64 {
+        match self {
+            AngleMode::Radians => TAU,
+            AngleMode::Degrees => 360.0,
+            AngleMode::Gradians => 400.0,
+        }
+    }
+
+    /// Converts an angle in this unit to radians
+    pub fn convert_to_radians(&self, angle: f64) -> f64 {
+        match self {
+            AngleMode::Radians => angle,
+            _ => angle / self.get_full_turn() * TAU,
+        }
+    }
+
+    /// Converts an angle in radians to this unit
+    pub fn convert_from_radians(&self, angle: f64) -> f64 {
+        match self {
+            AngleMode::Radians => angle,
+            _ => angle / TAU * self.get_full_turn(),
+        }
+    }
+}
+
+impl Display for AngleMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            AngleMode::Radians => "radians",
+            AngleMode::Degrees => "degrees",
+            AngleMode::Gradians => "gradians",
+        };
+
+        write!(f, "{s}")
+    }
+}
+
+impl Default for Environment {
+    fn default() -> Self {
+        Self {
+            variables: HashMap::new(),
+            functions: HashMap::new(),
+            scopes: Vec::new(),
+            call_depth: 0,
+            angle_mode: AngleMode::default(),
+            recursion_limit: DEFAULT_RECURSION_LIMIT,
+            exact_mode: false,
+            complex_mode: false,
+            precision: None,
+            symbolic_constants: false,
+        }
+    }
+}
+
+impl Environment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the value bound to the variable with the provided name if there is one.
+    /// Variables of the innermost scope shadow the variables of the session.
+    pub fn get_variable(&self, name: &str) -> Option<&FunctionReturnType> {
+        self.scopes
+            .last()
+            .and_then(|scope| scope.get(name))
+            .or_else(|| self.variables.get(name))
+    }
+
+    /// Binds the value to the variable with the provided name in the innermost scope, replacing any previous value
+    pub fn set_variable(&mut self, name: String, value: FunctionReturnType) {
+        match self.scopes.last_mut() {
+            Some(scope) => scope.insert(name, value),
+            None => self.variables.insert(name, value),
+        };
+    }
+
+    /// Returns the names of the variables of the session
+    pub fn get_variable_names(&self) -> impl Iterator<Item = &String> {
+        self.variables.keys()
+    }
+
+    /// Returns the names of all user-defined functions
+    pub fn get_function_names(&self) -> impl Iterator<Item = &String> {
+        self.functions.keys()
+    }
+
+    /// Returns the user-defined function with the provided name if there is one
+    pub fn get_function(&self, name: &str) -> Option<&Closure> {
+        self.functions.get(name)
+    }
+
+    /// Returns true if a user-defined function or a variable holding an anonymous function has the provided name
+    pub fn is_callable(&self, name: &str) -> bool {
+        self.functions.contains_key(name)
+            || matches!(
+                self.variables.get(name),
+                Some(FunctionReturnType::Lambda(_))
+            )
+    }
+
+    /// Returns the names and parameter counts of all user-defined functions and of all variables holding anonymous functions
+    pub fn get_callables(&self) -> impl Iterator<Item = (&String, usize)> {
+        self.functions
+            .iter()
+            .map(|(name, function)| (name, function.params.len()))
+            .chain(
+                self.variables
+                    .iter()
+                    .filter_map(|(name, value)| match value {
+                        FunctionReturnType::Lambda(closure) => Some((name, closure.params.len())),
+                        _ => None,
+                    }),
+            )
+    }
+
+    /// Defines the function with the provided name, replacing any previous definition
+    pub fn set_function(&mut self, name: String, function: Closure) {
+        self.functions.insert(name, function);
+    }
+
+    /// Returns a copy of the variables of the innermost scope, which anonymous functions capture
+    pub fn capture_scope(&self) -> HashMap<String, FunctionReturnType> {
+        self.scopes.last().cloned().unwrap_or_default()
+    }
+
+    /// Opens a new innermost scope, e.g. for the variable of a sum
+    pub fn push_scope(&mut self, scope: HashMap<String, FunctionReturnType>) {
+        self.scopes.push(scope);
+    }
+
+    /// Closes the innermost scope and drops its variables
+    pub fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// Opens a new innermost scope for the parameters of a function call
+    pub fn push_call_scope(&mut self, scope: HashMap<String, FunctionReturnType>) {
+        self.call_depth += 1;
+        self.push_scope(scope);
+    }
+
+    /// Closes the scope of the innermost function call
+    pub fn pop_call_scope(&mut self) {
+        self.call_depth -= 1;
+        self.pop_scope();
+    }
+
+    /// Returns the depth of nested function calls, scopes of bound variables are not counted
+    pub fn get_call_depth(&self) -> usize {
+        self.call_depth
+    }
+}
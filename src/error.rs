@@ -1,65 +1,239 @@
-use std::fmt::Debug;
-
-pub fn handle_errors(errors: Vec<CLMathError>) {
-    errors.into_iter().for_each(handle_error);
-}
-
-pub fn handle_error(error: CLMathError) {
-    match error {
-        CLMathError::Function(error) => print_function_error(error),
-        CLMathError::Tokenizer(error) => print_tokenizer_error(error),
-        CLMathError::Parser(error) => print_parser_error(error),
-        CLMathError::Io(error) => print_io_error(error),
-    }
-}
-
-fn print_parser_error(error: ParserError) {
-    match error {
-        ParserError::ExpectedClosingBracket => println!(
-            "An error occured while trying to evaluate the input: ExpectedClosingBracket\nA closing bracket was expected but not found.",
-        ),
-        ParserError::ExpectedMathExpression => println!(
-            "An error occured while trying to evaluate the input: ExpectedMathExpression\nA math expression was expected but not found.",
-        ),
-        ParserError::ExpressionEmpty => println!(
-            "An error occured while trying to evaluate the input: ExpressionEmpty\nThe expression was empty.",
-        ),
-        ParserError::ExpectedOpeningBracket => println!(
-            "An error occured while trying to evaluate the input: ExpectedOpeningBracket\nAn opening bracket was expected but not found.",
-        ),
-        ParserError::ExpectedComma => println!(
-            "An error occured while trying to evaluate the input: ExpectedComma\nA comma was expected but not found.",
-        ),
-        ParserError::NoLhsExpressionProvided => println!(
-            "An error occured while trying to evaluate the input: NoLhsExpressionProvided\nNo left hand side expression for an elementary function was found.",
-        ),
-        ParserError::ExpectedElementaryFunction => println!(
-            "An error occured while trying to evaluate the input: ExpectedElementaryFunction\nAn elementary function was expected but not found.",
-        )
+use crate::tokenizer::Span;
+
+/// The errors of an input. The tokenizer and the parser report all invalid parts of the input at once,
+/// the executor stops at its first error.
+#[derive(Debug)]
+pub struct Error {
+    errors: Vec<CLMathError>,
+    input: Option<String>,
+}
+
+impl Error {
+    /// Returns every error of the input, there is at least one
+    pub fn errors(&self) -> &[CLMathError] {
+        &self.errors
+    }
+
+    /// Sets the input the errors occurred in, so that their spans are shown under it
+    pub fn with_input(mut self, input: &str) -> Self {
+        self.input = Some(input.to_string());
+        self
+    }
+}
+
+impl From<CLMathError> for Error {
+    fn from(error: CLMathError) -> Self {
+        Self {
+            errors: vec![error],
+            input: None,
+        }
+    }
+}
+
+impl From<Vec<CLMathError>> for Error {
+    fn from(errors: Vec<CLMathError>) -> Self {
+        Self {
+            errors,
+            input: None,
+        }
+    }
+}
+
+/// Echoes the input and underlines the span, e.g.
+/// ```text
+///     1 + Foo(2)
+///         ^~~
+/// ```
+fn underline(input: &str, span: Span) -> String {
+    let width = span.end.saturating_sub(span.start).max(1);
+    format!(
+        "    {}\n    {}^{}",
+        input,
+        " ".repeat(span.start),
+        "~".repeat(width - 1)
+    )
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, error) in self.errors.iter().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", error)?;
+
+            if let (Some(input), Some(span)) = (&self.input, error.span()) {
+                write!(f, "\n{}", underline(input, span))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for CLMathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CLMathError::Function(error) => error.fmt(f),
+            CLMathError::Tokenizer(error) => error.fmt(f),
+            CLMathError::Parser(error) => error.fmt(f),
+            CLMathError::Io(error) => error.fmt(f),
+            CLMathError::Command(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for CLMathError {}
+
+impl std::fmt::Display for ParserError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.error_type.fmt(f)?;
+
+        match self.expected.as_slice() {
+            [] => Ok(()),
+            [kind] => write!(f, "\nExpected {}.", kind),
+            [kinds @ .., last] => {
+                let kinds = kinds
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "\nExpected {} or {}.", kinds, last)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenKind::Operand => write!(f, "a number, constant, variable or function"),
+            TokenKind::Operator => write!(f, "an operator"),
+            TokenKind::Variable => write!(f, "a variable"),
+            TokenKind::OpeningBracket => write!(f, "'('"),
+            TokenKind::ClosingBracket => write!(f, "')'"),
+            TokenKind::ClosingSquareBracket => write!(f, "']'"),
+            TokenKind::Comma => write!(f, "','"),
+            TokenKind::Equals => write!(f, "'='"),
+            TokenKind::Arrow => write!(f, "'->'"),
+            TokenKind::End => write!(f, "the end of the input"),
+        }
     }
 }
 
-fn print_io_error(error: IoError) {
-    match error {
-        IoError::InvalidUTF8(error) => println!(
-            "An error occured while trying to read the input: InvalidUTF8\n{}",
-            error
-        ),
+impl std::fmt::Display for ParserErrorType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParserErrorType::ExpectedClosingBracket => write!(f,
+                "An error occured while trying to evaluate the input: ExpectedClosingBracket\nA closing bracket was expected but not found.",
+            ),
+            ParserErrorType::ExpectedClosingSquareBracket => write!(f,
+                "An error occured while trying to evaluate the input: ExpectedClosingSquareBracket\nA closing square bracket ']' at the end of a list was expected but not found.",
+            ),
+            ParserErrorType::ExpectedMathExpression => write!(f,
+                "An error occured while trying to evaluate the input: ExpectedMathExpression\nA math expression was expected but not found.",
+            ),
+            ParserErrorType::ExpressionEmpty => write!(f,
+                "An error occured while trying to evaluate the input: ExpressionEmpty\nThe expression was empty.",
+            ),
+            ParserErrorType::ExpectedOpeningBracket => write!(f,
+                "An error occured while trying to evaluate the input: ExpectedOpeningBracket\nAn opening bracket was expected but not found.",
+            ),
+            ParserErrorType::ExpectedComma => write!(f,
+                "An error occured while trying to evaluate the input: ExpectedComma\nA comma was expected but not found.",
+            ),
+            ParserErrorType::ExpectedEquals => write!(f,
+                "An error occured while trying to evaluate the input: ExpectedEquals\nAn equals sign was expected but not found.",
+            ),
+            ParserErrorType::ExpectedVariable => write!(f,
+                "An error occured while trying to evaluate the input: ExpectedVariable\nA variable name was expected but not found.",
+            ),
+            ParserErrorType::InvalidNumberOfParams { function, expected, found } => write!(f,
+                "An error occured while trying to evaluate the input: InvalidNumberOfParams\nThe function '{}' takes {} parameter(s) but {} were provided.",
+                function, expected, found
+            ),
+            ParserErrorType::ExpectedArrow => write!(f,
+                "An error occured while trying to evaluate the input: ExpectedArrow\nAn arrow '->' between the parameters and the body of a function was expected but not found.",
+            ),
+            ParserErrorType::TooFewParams { function, minimum, found } => write!(f,
+                "An error occured while trying to evaluate the input: TooFewParams\nThe function '{}' takes at least {} parameter(s) but {} were provided.",
+                function, minimum, found
+            ),
+            ParserErrorType::DuplicateParameter { name } => write!(f,
+                "An error occured while trying to evaluate the input: DuplicateParameter\nThe parameter '{}' is used more than once, the parameters of a function need different names.",
+                name
+            ),
+            ParserErrorType::NoLhsExpressionProvided => write!(f,
+                "An error occured while trying to evaluate the input: NoLhsExpressionProvided\nNo left hand side expression for an elementary function was found.",
+            ),
+            ParserErrorType::ExpectedElementaryFunction => write!(f,
+                "An error occured while trying to evaluate the input: ExpectedElementaryFunction\nAn elementary function was expected but not found.",
+            )
+        }
     }
 }
 
-fn print_tokenizer_error(error: TokenizerError) {
-    println!(
-        "An error occurred while trying evaluate the input: {:?}, Start: {}, End: {}\n{}",
-        error.error_type, error.token_start_idx, error.curr_idx, error.error
-    );
+impl std::fmt::Display for IoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IoError::InvalidUTF8(error) => write!(
+                f,
+                "An error occured while trying to read the input: InvalidUTF8\n{}",
+                error
+            ),
+            IoError::CannotReadInput(error) => write!(
+                f,
+                "An error occured while trying to read the input: CannotReadInput\n{}",
+                error
+            ),
+            IoError::CannotReadFile { path, error } => write!(
+                f,
+                "An error occured while trying to read the input: CannotReadFile\nThe file '{}' could not be read: {}",
+                path, error
+            ),
+            IoError::LineEditor(error) => write!(
+                f,
+                "An error occured while trying to read the input: LineEditor\n{}",
+                error
+            ),
+            IoError::CannotWriteFile { path, error } => write!(
+                f,
+                "An error occured while trying to write the output: CannotWriteFile\nThe file '{}' could not be written: {}",
+                path, error
+            ),
+        }
+    }
 }
 
-fn print_function_error(error: FunctionError) {
-    println!(
-        "An error occurred while trying run the input: {:?}\n{}",
-        error.error_type, error.error,
-    );
+impl std::fmt::Display for TokenizerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "An error occurred while trying evaluate the input: {:?}\n{}",
+            self.error_type, self.error
+        )
+    }
+}
+
+impl std::fmt::Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "An error occurred while trying to run the command: {:?}\n{}",
+            self.error_type, self.error,
+        )
+    }
+}
+
+impl std::fmt::Display for FunctionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "An error occurred while trying run the input: {:?}\n{}",
+            self.error_type, self.error,
+        )
+    }
 }
 
 #[derive(Debug)]
@@ -68,11 +242,36 @@ pub enum CLMathError {
     Tokenizer(TokenizerError),
     Parser(ParserError),
     Function(FunctionError),
+    Command(CommandError),
+}
+
+impl CLMathError {
+    /// Returns the part of the input the error occurred at, if it is known
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            CLMathError::Tokenizer(error) => Some(error.span),
+            CLMathError::Parser(error) => Some(error.span),
+            CLMathError::Function(error) => error.span,
+            CLMathError::Command(error) => error.span,
+            CLMathError::Io(_) => None,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub enum IoError {
     InvalidUTF8(String),
+    /// Reading the next input failed for another reason than invalid UTF-8, e.g. because the file is a directory
+    CannotReadInput(String),
+    CannotReadFile {
+        path: String,
+        error: String,
+    },
+    CannotWriteFile {
+        path: String,
+        error: String,
+    },
+    LineEditor(String),
 }
 
 #[derive(Debug)]
@@ -88,35 +287,84 @@ pub enum TokenizerErrorType {
 pub struct TokenizerError {
     pub error: String,
     pub error_type: TokenizerErrorType,
-    pub token_start_idx: usize,
-    pub curr_idx: usize,
+    pub span: Span,
 }
 
 impl TokenizerError {
-    pub fn new(
-        error: String,
-        error_type: TokenizerErrorType,
-        token_start_idx: usize,
-        curr_idx: usize,
-    ) -> Self {
+    pub fn new(error: String, error_type: TokenizerErrorType, span: Span) -> Self {
         Self {
             error,
             error_type,
-            token_start_idx,
-            curr_idx,
+            span,
         }
     }
 }
 
 #[derive(Debug)]
-pub enum ParserError {
+pub struct ParserError {
+    pub error_type: ParserErrorType,
+    pub span: Span,
+    /// The kinds of tokens that would have been valid at the span
+    pub expected: Vec<TokenKind>,
+}
+
+impl ParserError {
+    pub fn new(error_type: ParserErrorType, span: Span) -> Self {
+        Self {
+            error_type,
+            span,
+            expected: Vec::new(),
+        }
+    }
+
+    pub fn expecting(mut self, expected: &[TokenKind]) -> Self {
+        self.expected = expected.to_vec();
+        self
+    }
+}
+
+/// The kinds of tokens the parser can expect at a position of the input
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TokenKind {
+    Operand,
+    Operator,
+    Variable,
+    OpeningBracket,
+    ClosingBracket,
+    ClosingSquareBracket,
+    Comma,
+    Equals,
+    Arrow,
+    End,
+}
+
+#[derive(Debug)]
+pub enum ParserErrorType {
     ExpectedMathExpression,
     ExpressionEmpty,
     ExpectedElementaryFunction,
     ExpectedOpeningBracket,
     ExpectedClosingBracket,
+    ExpectedClosingSquareBracket,
     ExpectedComma,
+    ExpectedEquals,
+    ExpectedVariable,
+    ExpectedArrow,
     NoLhsExpressionProvided,
+    InvalidNumberOfParams {
+        function: String,
+        expected: usize,
+        found: usize,
+    },
+    TooFewParams {
+        function: String,
+        minimum: usize,
+        found: usize,
+    },
+    /// A function has two parameters with the same name, e.g. `f(x, x) = x`
+    DuplicateParameter {
+        name: String,
+    },
 }
 
 #[derive(Debug)]
@@ -126,46 +374,69 @@ pub enum FunctionErrorType {
     OverflowInf,
     UnderflowInf,
     InvalidParameterType,
+    DomainError,
+    LogarithmOfZero,
+    LogarithmOfNegative,
+    InvalidLogarithmBase,
+    RecursionLimitExceeded,
+    InvalidNumberOfParams,
+    DimensionMismatch,
+    SingularMatrix,
+    NoConvergence,
+    InfinitelyManySolutions,
+    IndeterminateForm,
 }
 
 #[derive(Debug)]
 pub struct FunctionError {
     pub error: String,
     pub error_type: FunctionErrorType,
+    /// The innermost expression the error occurred in
+    pub span: Option<Span>,
 }
 
 impl FunctionError {
     pub fn new(error: String, error_type: FunctionErrorType) -> Self {
-        Self { error, error_type }
+        Self {
+            error,
+            error_type,
+            span: None,
+        }
     }
-}
 
-impl std::fmt::Display for CLMathError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+    /// Sets the span of the error, if it does not have one yet
+    pub fn at(mut self, span: Span) -> Self {
+        self.span.get_or_insert(span);
+        self
     }
 }
 
-impl std::fmt::Display for IoError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
-    }
+#[derive(Debug)]
+pub enum CommandErrorType {
+    UnknownCommand,
+    InvalidArgument,
 }
 
-impl std::fmt::Display for TokenizerError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
-    }
+#[derive(Debug)]
+pub struct CommandError {
+    pub error: String,
+    pub error_type: CommandErrorType,
+    /// The words of the command the error occurred at
+    pub span: Option<Span>,
 }
 
-impl std::fmt::Display for ParserError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+impl CommandError {
+    pub fn new(error: String, error_type: CommandErrorType) -> Self {
+        Self {
+            error,
+            error_type,
+            span: None,
+        }
     }
-}
 
-impl std::fmt::Display for FunctionError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+    /// Sets the span of the error, if it does not have one yet
+    pub fn at(mut self, span: Span) -> Self {
+        self.span.get_or_insert(span);
+        self
     }
 }
@@ -1,92 +1,791 @@
+use std::{collections::HashMap, rc::Rc};
+
+use bigdecimal::BigDecimal;
+use num_bigint::BigInt;
+use num_complex::Complex64;
+use num_rational::BigRational;
+use num_traits::{One, Signed, Zero};
+
 use crate::{
-    error::{CLMathError, FunctionError},
+    decimal,
+    environment::{AngleMode, Closure, Environment},
+    error::{CLMathError, FunctionError, FunctionErrorType},
     functions::{
-        absolute, addition, division, factorial, greater_than, less_than, maximum, minimum, modulo,
-        modulo_euclid, multiplication, subtraction, ElementaryFunc, FunctionReturnType,
-        HigherOrderFunc,
+        absolute, addition, arccosine, arcsine, arctangent, arctangent2, area_hyperbolic_cosine,
+        area_hyperbolic_sine, area_hyperbolic_tangent, argument, binary_logarithm,
+        complex_absolute, complex_addition, complex_division, complex_exponential,
+        complex_exponentiation, complex_multiplication, complex_natural_logarithm,
+        complex_negation, complex_square_root, complex_subtraction, conjugate, cosine,
+        create_complex_error, cube_root, decimal_logarithm, division, exponential,
+        exponential_minus_one, exponentiation, factorial, from_polar, greater_than,
+        hyperbolic_cosine, hyperbolic_sine, hyperbolic_tangent, imaginary_part, integer_absolute,
+        integer_addition, integer_division, integer_exponentiation, integer_greater_than,
+        integer_less_than, integer_maximum, integer_minimum, integer_modulo, integer_modulo_euclid,
+        integer_multiplication, integer_negation, integer_subtraction, less_than, logarithm,
+        maximum, minimum, modulo, modulo_euclid, multiplication, natural_logarithm,
+        natural_logarithm_one_plus, negation, rational_absolute, rational_addition,
+        rational_division, rational_exponentiation, rational_greater_than, rational_less_than,
+        rational_maximum, rational_minimum, rational_modulo, rational_modulo_euclid,
+        rational_multiplication, rational_negation, rational_subtraction, rational_to_f64,
+        rational_value, real_part, root, sine, square_root, subtraction, tangent, to_float,
+        to_fraction, to_polar, Constant, ElementaryFunc, FunctionReturnType, HigherOrderFunc,
+        UnaryFunc,
     },
+    matrix, numeric,
     parser::{
-        ElementaryFunction, Expression, Function, HigherOrderFunction, MathExpression, Start,
+        Assignment, ElementaryFunction, Equation, Expression, Function, FunctionDefinition,
+        HigherOrderFunction, MathExpression, Start, UnaryFunction, UserFunction,
+    },
+    symbolic::{self, symbolic_value, Symbolic},
+    tokenizer::{Number, Variable},
+    units::{
+        quantity_absolute, quantity_addition, quantity_division, quantity_exponentiation,
+        quantity_greater_than, quantity_less_than, quantity_maximum, quantity_minimum,
+        quantity_modulo, quantity_multiplication, quantity_negation, quantity_subtraction,
+        unit_conversion, Quantity, Unit,
     },
 };
 
-pub fn execute(expression: Start) -> Result<Option<FunctionReturnType>, CLMathError> {
+/// The maximal number of values that the variable of a sum or product takes
+const MAX_ITERATED_TERMS: u32 = 1_000_000;
+
+/// The largest exponent of a matrix power, every integer up to it is exact as a floating point number
+const MAX_EXACT_MATRIX_EXPONENT: f64 = 9_007_199_254_740_992.0;
+
+/// The stack that has to be left to evaluate an expression without its subexpressions. A nested expression takes at most
+/// a few KiB of stack in debug builds between the evaluation of its subexpressions, unless a built-in function recurses.
+pub(crate) const STACK_RED_ZONE: usize = 256 * 1024;
+
+/// The size of the stack segments that are allocated when less than the red zone is left
+pub(crate) const STACK_SEGMENT_SIZE: usize = 4 * 1024 * 1024;
+
+pub fn execute(
+    expression: Start,
+    env: &mut Environment,
+) -> Result<Option<FunctionReturnType>, CLMathError> {
     match expression {
+        Start::Assignment(assignment) => Ok(Some(
+            execute_assignment(assignment, env).map_err(CLMathError::Function)?,
+        )),
+        Start::FunctionDefinition(definition) => {
+            execute_function_definition(definition, env);
+            Ok(None)
+        }
         Start::Expression(expr) => Ok(Some(
-            execute_expression(expr).map_err(CLMathError::Function)?,
+            execute_expression(&expr, env).map_err(CLMathError::Function)?,
         )),
         Start::Empty => Ok(None),
     }
 }
 
-fn execute_expression(expression: Expression) -> Result<FunctionReturnType, FunctionError> {
-    match expression {
-        Expression::MathExpression(expr) => execute_math_expression(*expr),
-        Expression::BracketedExpression(expr) => execute_expression(expr.expression),
-    }
+fn execute_assignment(
+    assignment: Assignment,
+    env: &mut Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    let value = execute_expression(&assignment.expression, env)?;
+    env.set_variable(assignment.variable.name, value.clone());
+
+    Ok(value)
+}
+
+fn execute_function_definition(definition: FunctionDefinition, env: &mut Environment) {
+    env.set_function(
+        definition.name,
+        Closure::new(
+            definition.params.into_iter().map(|var| var.name).collect(),
+            Rc::new(definition.expression),
+            HashMap::new(),
+        ),
+    );
+}
+
+fn execute_expression(
+    expression: &Expression,
+    env: &mut Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    // Deeply nested expressions and recursive functions continue on a new stack segment instead of overflowing the stack
+    stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, || {
+        // The innermost failing expression sets the span of the error
+        match expression {
+            Expression::MathExpression(expr, span) => {
+                execute_math_expression(expr, env).map_err(|err| err.at(*span))
+            }
+            Expression::BracketedExpression(expr, span) => {
+                execute_expression(&expr.expression, env).map_err(|err| err.at(*span))
+            }
+        }
+    })
 }
 
 fn execute_math_expression(
-    expression: MathExpression,
+    expression: &MathExpression,
+    env: &mut Environment,
 ) -> Result<FunctionReturnType, FunctionError> {
     match expression {
-        MathExpression::Function(function) => execute_function(function),
-        MathExpression::Number(num) => Ok(FunctionReturnType::F64(num)),
-        MathExpression::Variable(var) => {
-            Ok(var.value.map_or(FunctionReturnType::Str(var.name), |num| {
-                FunctionReturnType::F64(num)
-            }))
+        MathExpression::Function(function) => execute_function(function, env),
+        MathExpression::Number(Number::Integer(num)) => Ok(FunctionReturnType::BigInt(num.clone())),
+        MathExpression::Number(Number::Decimal(num)) if env.exact_mode => {
+            Ok(rational_value(num.clone()))
         }
+        MathExpression::Number(Number::Decimal(num)) => match env.precision {
+            Some(precision) => Ok(FunctionReturnType::Decimal(decimal::from_rational(
+                num, precision,
+            ))),
+            None => Ok(FunctionReturnType::F64(rational_to_f64(num)?)),
+        },
+        MathExpression::Number(Number::Imaginary) => {
+            Ok(FunctionReturnType::Complex(Complex64::i()))
+        }
+        MathExpression::Constant(constant) => Ok(constant_value(constant, env)),
+        MathExpression::Variable(var) => execute_variable(var, env),
+        MathExpression::Lambda(lambda) => Ok(FunctionReturnType::Lambda(Closure::new(
+            lambda.params.iter().map(|var| var.name.clone()).collect(),
+            lambda.body.clone(),
+            env.capture_scope(),
+        ))),
+        MathExpression::List(list) => list
+            .elements
+            .iter()
+            .map(|element| execute_expression(element, env))
+            .collect::<Result<Vec<_>, _>>()
+            .map(FunctionReturnType::List),
+        MathExpression::Equation(_) => Err(FunctionError::new(
+            "An equation has no value, it can only be the parameter of a function that solves it, e.g. 'Solve(x^2 = 2, x)'.".to_string(),
+            FunctionErrorType::InvalidParameterType,
+        )),
+    }
+}
+
+fn constant_value(constant: &Constant, env: &Environment) -> FunctionReturnType {
+    if env.symbolic_constants {
+        return FunctionReturnType::Symbolic(Symbolic::Constant(constant.clone()));
+    }
+
+    // Physical constants are quantities, e.g. `SpeedOfLight` is 299792458 m/s
+    if let Some(unit) = constant.get_unit() {
+        return FunctionReturnType::Quantity(Quantity::new(constant.get_value(), unit));
+    }
+
+    match env.precision {
+        Some(precision) => decimal::constant(constant, precision),
+        None => FunctionReturnType::F64(constant.get_value()),
+    }
+}
+
+fn execute_variable(
+    variable: &Variable,
+    env: &Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    // Names that follow a number, a conversion or another unit are units even if a variable has the same name,
+    // e.g. `s` in `100 km/h to m/s` after `s = 1`
+    if let Some(unit) = Unit::from_name(&variable.name).filter(|_| variable.is_unit) {
+        return Ok(FunctionReturnType::Quantity(Quantity::new(1.0, unit)));
+    }
+
+    if let Some(value) = env.get_variable(&variable.name) {
+        return Ok(value.clone());
     }
+
+    // Named functions can be passed to other functions like anonymous ones
+    if let Some(closure) = env.get_function(&variable.name) {
+        return Ok(FunctionReturnType::Lambda(closure.clone()));
+    }
+
+    // All other variables without a value are free, so calculations with them result in symbolic expressions, e.g. `2x + 1`
+    Ok(FunctionReturnType::Symbolic(Symbolic::Variable(
+        variable.name.clone(),
+    )))
 }
 
-fn execute_function(function: Function) -> Result<FunctionReturnType, FunctionError> {
+fn execute_function(
+    function: &Function,
+    env: &mut Environment,
+) -> Result<FunctionReturnType, FunctionError> {
     match function {
-        Function::ElementaryFunction(func) => run_elementary_function(func),
-        Function::HigherOrderFunction(func) => run_higher_order_function(func),
+        Function::ElementaryFunction(func) => run_elementary_function(func, env),
+        Function::HigherOrderFunction(func) => run_higher_order_function(func, env),
+        Function::UnaryFunction(func) => run_unary_function(func, env),
+        Function::UserFunction(func) => run_user_function(func, env),
+    }
+}
+
+fn run_user_function(
+    function: &UserFunction,
+    env: &mut Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    let closure = match env.get_function(&function.name) {
+        Some(closure) => closure.clone(),
+        None => execute_variable(&Variable::new(function.name.clone()), env)?.get_lambda()?,
+    };
+
+    let args = std::iter::once(&function.params.expression)
+        .chain(
+            function
+                .params
+                .expression_comma
+                .iter()
+                .flatten()
+                .map(|(_, expr)| expr),
+        )
+        .map(|expr| execute_expression(expr, env))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    call_closure(&function.name, &closure, args, env)
+}
+
+/// Calls a function that was passed to the built-in function `caller`, e.g. `Map`. A wrong number of arguments
+/// is reported for the passed function, since the caller cannot be called differently.
+fn call_passed_closure(
+    caller: &str,
+    closure: &Closure,
+    args: Vec<FunctionReturnType>,
+    env: &mut Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    if args.len() != closure.params.len() {
+        return Err(FunctionError::new(
+            format!(
+                "'{}' calls the function '{}' with {} parameter(s), but it takes {}.",
+                caller,
+                closure,
+                args.len(),
+                closure.params.len()
+            ),
+            FunctionErrorType::InvalidNumberOfParams,
+        ));
+    }
+
+    call_closure(caller, closure, args, env)
+}
+
+/// Evaluates the body of the function with its parameters bound to the provided arguments
+fn call_closure(
+    name: &str,
+    closure: &Closure,
+    args: Vec<FunctionReturnType>,
+    env: &mut Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    if args.len() != closure.params.len() {
+        return Err(FunctionError::new(
+            format!(
+                "The function '{}' takes {} parameter(s) but {} were provided.",
+                name,
+                closure.params.len(),
+                args.len()
+            ),
+            FunctionErrorType::InvalidNumberOfParams,
+        ));
+    }
+
+    if env.get_call_depth() >= env.recursion_limit {
+        return Err(FunctionError::new(
+            format!(
+                "The call of '{}' exceeds the maximum depth of {} nested function calls. Check that the recursion terminates or raise the limit with ':recursion'.",
+                name, env.recursion_limit
+            ),
+            FunctionErrorType::RecursionLimitExceeded,
+        ));
+    }
+
+    let mut scope = closure.captured.clone();
+    scope.extend(closure.params.iter().cloned().zip(args));
+
+    env.push_call_scope(scope);
+    let result = with_symbolic_constants(false, env, |env| execute_expression(&closure.body, env));
+    env.pop_call_scope();
+
+    // The body was parsed from an earlier input, so the error belongs to the call
+    result.map_err(|mut err| {
+        err.span = None;
+        err
+    })
+}
+
+fn run_unary_function(
+    function: &UnaryFunction,
+    env: &mut Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    let value = execute_expression(&function.expression, env)?;
+
+    match (&function.function, value) {
+        (UnaryFunc::Negation, FunctionReturnType::BigInt(num)) => Ok(integer_negation(&num)),
+        (UnaryFunc::Negation, FunctionReturnType::Rational(num)) => Ok(rational_negation(&num)),
+        (UnaryFunc::Negation, FunctionReturnType::Decimal(num)) => Ok(decimal::negation(&num)),
+        (UnaryFunc::Negation, FunctionReturnType::Complex(num)) => Ok(complex_negation(num)),
+        (UnaryFunc::Negation, FunctionReturnType::Quantity(num)) => Ok(quantity_negation(&num)),
+        (UnaryFunc::Negation, FunctionReturnType::Symbolic(expression)) => {
+            Ok(symbolic_value(Symbolic::negation(expression)))
+        }
+        (UnaryFunc::Negation, value) => Ok(negation(value.get_f64()?)),
+        (
+            UnaryFunc::Identity,
+            value @ (FunctionReturnType::BigInt(_)
+            | FunctionReturnType::Rational(_)
+            | FunctionReturnType::Decimal(_)
+            | FunctionReturnType::Complex(_)
+            | FunctionReturnType::Quantity(_)
+            | FunctionReturnType::Symbolic(_)),
+        ) => Ok(value),
+        (UnaryFunc::Identity, value) => Ok(FunctionReturnType::F64(value.get_f64()?)),
     }
 }
 
 fn run_elementary_function(
-    function: ElementaryFunction,
+    function: &ElementaryFunction,
+    env: &mut Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    let mut lhs = execute_expression(&function.expression_lhs, env)?;
+    let mut rhs = execute_expression(&function.expression_rhs, env)?;
+
+    // The side without variables is evaluated again with symbolic constants, so that `Pi*x` stays `Pi*x`.
+    // Only arithmetic is evaluated again, function calls could take long and are calculated with their constants anyway.
+    if !env.symbolic_constants {
+        match (&lhs, &rhs) {
+            (FunctionReturnType::Symbolic(_), FunctionReturnType::Symbolic(_)) => (),
+            (FunctionReturnType::Symbolic(_), _) if is_arithmetic(&function.expression_rhs) => {
+                rhs = with_symbolic_constants(true, env, |env| {
+                    execute_expression(&function.expression_rhs, env)
+                })?
+            }
+            (_, FunctionReturnType::Symbolic(_)) if is_arithmetic(&function.expression_lhs) => {
+                lhs = with_symbolic_constants(true, env, |env| {
+                    execute_expression(&function.expression_lhs, env)
+                })?
+            }
+            _ => (),
+        }
+    }
+
+    calculate_elementary_function(&function.function, lhs, rhs, env)
+}
+
+/// Returns true if the expression only consists of numbers, constants and variables combined by elementary functions,
+/// e.g. `2Pi + x`, whose evaluation is fast and does not call any function
+fn is_arithmetic(expression: &Expression) -> bool {
+    match expression {
+        Expression::BracketedExpression(expr, _) => is_arithmetic(&expr.expression),
+        Expression::MathExpression(expr, _) => match expr.as_ref() {
+            MathExpression::Number(_)
+            | MathExpression::Constant(_)
+            | MathExpression::Variable(_) => true,
+            MathExpression::Function(Function::ElementaryFunction(function)) => {
+                is_arithmetic(&function.expression_lhs) && is_arithmetic(&function.expression_rhs)
+            }
+            MathExpression::Function(Function::UnaryFunction(function)) => {
+                is_arithmetic(&function.expression)
+            }
+            MathExpression::List(list) => list.elements.iter().all(is_arithmetic),
+            MathExpression::Function(_)
+            | MathExpression::Lambda(_)
+            | MathExpression::Equation(_) => false,
+        },
+    }
+}
+
+/// Runs the closure with constants evaluated to symbolic expressions or to numbers
+fn with_symbolic_constants<T>(
+    symbolic_constants: bool,
+    env: &mut Environment,
+    run: impl FnOnce(&mut Environment) -> T,
+) -> T {
+    let previous = std::mem::replace(&mut env.symbolic_constants, symbolic_constants);
+    let result = run(env);
+    env.symbolic_constants = previous;
+    result
+}
+
+/// Runs the elementary function on two values, the variant depends on their types and the modes of the session
+fn calculate_elementary_function(
+    function: &ElementaryFunc,
+    lhs: FunctionReturnType,
+    rhs: FunctionReturnType,
+    env: &Environment,
 ) -> Result<FunctionReturnType, FunctionError> {
-    let lhs = execute_expression(function.expression_lhs)?.get_f64()?;
-    let rhs = execute_expression(function.expression_rhs)?.get_f64()?;
+    if matches!(lhs, FunctionReturnType::List(_)) || matches!(rhs, FunctionReturnType::List(_)) {
+        return run_list_elementary_function(function, lhs, rhs, env);
+    }
+
+    if matches!(lhs, FunctionReturnType::Symbolic(_))
+        || matches!(rhs, FunctionReturnType::Symbolic(_))
+    {
+        return run_symbolic_elementary_function(function, lhs, rhs);
+    }
+
+    // Quantities are checked for matching dimensions, numbers are quantities without a unit
+    if *function == ElementaryFunc::Conversion
+        || matches!(lhs, FunctionReturnType::Quantity(_))
+        || matches!(rhs, FunctionReturnType::Quantity(_))
+    {
+        return run_quantity_elementary_function(function, lhs, rhs);
+    }
+
+    match run_exact_elementary_function(function, &lhs, &rhs, env.exact_mode) {
+        // Exact functions fall back to floating point numbers if the result is not exact, e.g. for 2 ^ 0.5
+        Some(Ok(FunctionReturnType::F64(_))) if env.precision.is_some() => (),
+        Some(result) => return result,
+        None => (),
+    }
 
-    match function.function {
+    if matches!(lhs, FunctionReturnType::Complex(_))
+        || matches!(rhs, FunctionReturnType::Complex(_))
+    {
+        return run_complex_elementary_function(function, lhs.get_complex()?, rhs.get_complex()?);
+    }
+
+    // Powers of negative numbers with fractional exponents have no real result, but a complex one
+    if env.complex_mode && *function == ElementaryFunc::Exponentiation {
+        let (base, exponent) = (lhs.clone().get_f64()?, rhs.clone().get_f64()?);
+
+        if base < 0.0 && exponent.fract() != 0.0 {
+            return complex_exponentiation(Complex64::from(base), Complex64::from(exponent));
+        }
+    }
+
+    if let Some(precision) = env.precision.filter(|_| lhs.is_finite() && rhs.is_finite()) {
+        return run_decimal_elementary_function(
+            function,
+            &lhs.get_decimal(precision)?,
+            &rhs.get_decimal(precision)?,
+            precision,
+        );
+    }
+
+    let lhs = lhs.get_f64()?;
+    let rhs = rhs.get_f64()?;
+
+    match function {
         ElementaryFunc::Addition => addition(lhs, rhs),
         ElementaryFunc::Division => division(lhs, rhs),
-        ElementaryFunc::Modulo => Ok(modulo(lhs, rhs)),
+        ElementaryFunc::Modulo => modulo(lhs, rhs),
         ElementaryFunc::Multiplication => multiplication(lhs, rhs),
         ElementaryFunc::Subtraction => subtraction(lhs, rhs),
         ElementaryFunc::LessThan => Ok(less_than(lhs, rhs)),
         ElementaryFunc::GreaterThan => Ok(greater_than(lhs, rhs)),
+        ElementaryFunc::Exponentiation => exponentiation(lhs, rhs),
+        ElementaryFunc::Conversion => {
+            unreachable!("Conversions are handled before numbers are calculated.")
+        }
+    }
+}
+
+/// Runs the elementary function on lists. Products and powers of matrices are calculated as matrices,
+/// all other functions are applied to each pair of elements or, for a single value, to each element with that value.
+fn run_list_elementary_function(
+    function: &ElementaryFunc,
+    lhs: FunctionReturnType,
+    rhs: FunctionReturnType,
+    env: &Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    let mut arithmetic = |function: &ElementaryFunc, lhs, rhs| {
+        calculate_elementary_function(function, lhs, rhs, env)
+    };
+
+    match (function, lhs, rhs) {
+        (
+            ElementaryFunc::Multiplication,
+            FunctionReturnType::List(lhs),
+            FunctionReturnType::List(rhs),
+        ) if matrix::is_matrix(&lhs) || matrix::is_matrix(&rhs) => {
+            matrix::multiply(lhs, rhs, &mut arithmetic)
+        }
+        (ElementaryFunc::Exponentiation, FunctionReturnType::List(lhs), rhs)
+            if matrix::is_matrix(&lhs) =>
+        {
+            let exponent = rhs.get_f64()?;
+            if exponent.fract() != 0.0 {
+                return Err(FunctionError::new(
+                    format!("Matrices can only be raised to integer powers. You tried to raise a matrix to the power of {}.", exponent),
+                    FunctionErrorType::DomainError,
+                ));
+            }
+
+            // Larger integers are not exact as floating point numbers
+            if exponent.abs() > MAX_EXACT_MATRIX_EXPONENT {
+                return Err(FunctionError::new(
+                    format!("The power of a matrix can only be calculated for exponents up to {}. You tried to raise a matrix to the power of {}.", MAX_EXACT_MATRIX_EXPONENT, exponent),
+                    FunctionErrorType::OverflowInf,
+                ));
+            }
+
+            // Negative powers are calculated with the inverse, whose fractions are converted like the ones of `Inv`
+            approximate_fractions(
+                matrix::power(
+                    matrix::get_square_matrix(FunctionReturnType::List(lhs))?,
+                    exponent as i64,
+                    &mut arithmetic,
+                )?,
+                env,
+            )
+        }
+        (_, FunctionReturnType::List(lhs), FunctionReturnType::List(rhs)) => {
+            if lhs.len() != rhs.len() {
+                return Err(FunctionError::new(
+                    format!(
+                        "Element-wise functions need lists of the same length but got lists of length {} and {}.",
+                        lhs.len(),
+                        rhs.len()
+                    ),
+                    FunctionErrorType::DimensionMismatch,
+                ));
+            }
+
+            lhs.into_iter()
+                .zip(rhs)
+                .map(|(lhs, rhs)| arithmetic(function, lhs, rhs))
+                .collect::<Result<Vec<_>, _>>()
+                .map(FunctionReturnType::List)
+        }
+        (_, FunctionReturnType::List(lhs), rhs) => lhs
+            .into_iter()
+            .map(|lhs| arithmetic(function, lhs, rhs.clone()))
+            .collect::<Result<Vec<_>, _>>()
+            .map(FunctionReturnType::List),
+        (_, lhs, FunctionReturnType::List(rhs)) => rhs
+            .into_iter()
+            .map(|rhs| arithmetic(function, lhs.clone(), rhs))
+            .collect::<Result<Vec<_>, _>>()
+            .map(FunctionReturnType::List),
+        _ => unreachable!("One of the values should be a list."),
+    }
+}
+
+/// Runs the exact variant of the elementary function if both sides are integers or fractions
+fn run_exact_elementary_function(
+    function: &ElementaryFunc,
+    lhs: &FunctionReturnType,
+    rhs: &FunctionReturnType,
+    exact_mode: bool,
+) -> Option<Result<FunctionReturnType, FunctionError>> {
+    if let (FunctionReturnType::BigInt(lhs), FunctionReturnType::BigInt(rhs)) = (lhs, rhs) {
+        return Some(match function {
+            ElementaryFunc::Addition => Ok(integer_addition(lhs, rhs)),
+            ElementaryFunc::Division if exact_mode => rational_division(
+                &BigRational::from(lhs.clone()),
+                &BigRational::from(rhs.clone()),
+            ),
+            ElementaryFunc::Division => integer_division(lhs, rhs),
+            ElementaryFunc::Modulo => integer_modulo(lhs, rhs),
+            ElementaryFunc::Multiplication => Ok(integer_multiplication(lhs, rhs)),
+            ElementaryFunc::Subtraction => Ok(integer_subtraction(lhs, rhs)),
+            ElementaryFunc::LessThan => Ok(integer_less_than(lhs, rhs)),
+            ElementaryFunc::GreaterThan => Ok(integer_greater_than(lhs, rhs)),
+            // Negative powers of integers are fractions, e.g. 2 ^ -1 is 1/2
+            ElementaryFunc::Exponentiation if exact_mode && rhs.is_negative() => {
+                rational_exponentiation(
+                    &BigRational::from(lhs.clone()),
+                    &BigRational::from(rhs.clone()),
+                )
+            }
+            ElementaryFunc::Exponentiation => integer_exponentiation(lhs, rhs),
+            ElementaryFunc::Conversion => {
+                unreachable!("Conversions are handled before numbers are calculated.")
+            }
+        });
+    }
+
+    if let (Some(lhs), Some(rhs)) = (lhs.get_exact(), rhs.get_exact()) {
+        return Some(match function {
+            ElementaryFunc::Addition => Ok(rational_addition(&lhs, &rhs)),
+            ElementaryFunc::Division => rational_division(&lhs, &rhs),
+            ElementaryFunc::Modulo => rational_modulo(&lhs, &rhs),
+            ElementaryFunc::Multiplication => Ok(rational_multiplication(&lhs, &rhs)),
+            ElementaryFunc::Subtraction => Ok(rational_subtraction(&lhs, &rhs)),
+            ElementaryFunc::LessThan => Ok(rational_less_than(&lhs, &rhs)),
+            ElementaryFunc::GreaterThan => Ok(rational_greater_than(&lhs, &rhs)),
+            ElementaryFunc::Exponentiation => rational_exponentiation(&lhs, &rhs),
+            ElementaryFunc::Conversion => {
+                unreachable!("Conversions are handled before numbers are calculated.")
+            }
+        });
+    }
+
+    None
+}
+
+/// Runs the decimal variant of the elementary function with the precision of the session
+fn run_decimal_elementary_function(
+    function: &ElementaryFunc,
+    lhs: &BigDecimal,
+    rhs: &BigDecimal,
+    precision: u64,
+) -> Result<FunctionReturnType, FunctionError> {
+    match function {
+        ElementaryFunc::Addition => Ok(decimal::addition(lhs, rhs, precision)),
+        ElementaryFunc::Division => decimal::division(lhs, rhs, precision),
+        ElementaryFunc::Modulo => decimal::modulo(lhs, rhs, precision),
+        ElementaryFunc::Multiplication => Ok(decimal::multiplication(lhs, rhs, precision)),
+        ElementaryFunc::Subtraction => Ok(decimal::subtraction(lhs, rhs, precision)),
+        ElementaryFunc::LessThan => Ok(decimal::less_than(lhs, rhs)),
+        ElementaryFunc::GreaterThan => Ok(decimal::greater_than(lhs, rhs)),
+        ElementaryFunc::Exponentiation => decimal::exponentiation(lhs, rhs, precision),
+        ElementaryFunc::Conversion => {
+            unreachable!("Conversions are handled before numbers are calculated.")
+        }
+    }
+}
+
+/// Runs the elementary function on quantities, which have to measure the same dimension for sums, differences and comparisons
+fn run_quantity_elementary_function(
+    function: &ElementaryFunc,
+    lhs: FunctionReturnType,
+    rhs: FunctionReturnType,
+) -> Result<FunctionReturnType, FunctionError> {
+    let lhs = lhs.get_quantity()?;
+
+    if *function == ElementaryFunc::Exponentiation {
+        return quantity_exponentiation(&lhs, rhs.get_f64()?);
+    }
+
+    let rhs = rhs.get_quantity()?;
+
+    match function {
+        ElementaryFunc::Addition => quantity_addition(&lhs, &rhs),
+        ElementaryFunc::Division => quantity_division(&lhs, &rhs),
+        ElementaryFunc::Modulo => quantity_modulo(&lhs, &rhs),
+        ElementaryFunc::Multiplication => Ok(quantity_multiplication(&lhs, &rhs)),
+        ElementaryFunc::Subtraction => quantity_subtraction(&lhs, &rhs),
+        ElementaryFunc::LessThan => quantity_less_than(&lhs, &rhs),
+        ElementaryFunc::GreaterThan => quantity_greater_than(&lhs, &rhs),
+        ElementaryFunc::Conversion => unit_conversion(&lhs, &rhs),
+        ElementaryFunc::Exponentiation => {
+            unreachable!("Powers are handled before the exponent is converted.")
+        }
+    }
+}
+
+fn run_complex_elementary_function(
+    function: &ElementaryFunc,
+    lhs: Complex64,
+    rhs: Complex64,
+) -> Result<FunctionReturnType, FunctionError> {
+    match function {
+        ElementaryFunc::Addition => Ok(complex_addition(lhs, rhs)),
+        ElementaryFunc::Division => complex_division(lhs, rhs),
+        ElementaryFunc::Multiplication => Ok(complex_multiplication(lhs, rhs)),
+        ElementaryFunc::Subtraction => Ok(complex_subtraction(lhs, rhs)),
+        ElementaryFunc::Exponentiation => complex_exponentiation(lhs, rhs),
+        ElementaryFunc::Conversion => {
+            unreachable!("Conversions are handled before numbers are calculated.")
+        }
+        ElementaryFunc::Modulo => Err(create_complex_error("modulo")),
+        ElementaryFunc::LessThan | ElementaryFunc::GreaterThan => {
+            Err(create_complex_error("comparison"))
+        }
     }
 }
 
 fn run_higher_order_function(
-    function: HigherOrderFunction,
+    function: &HigherOrderFunction,
+    env: &mut Environment,
 ) -> Result<FunctionReturnType, FunctionError> {
-    let first_param = execute_expression(function.params.expression)?;
-    let mut other_params = function
+    // Constants only stay symbolic in the arithmetic next to a symbolic expression, functions calculate them, e.g. `Sin(Pi/2)`
+    if env.symbolic_constants {
+        return with_symbolic_constants(false, env, |env| run_higher_order_function(function, env));
+    }
+
+    if let Some(result) = run_lazy_function(function, env) {
+        return result;
+    }
+
+    let first_param = execute_expression(&function.params.expression, env)?;
+    let other_params = function
         .params
         .expression_comma
+        .as_ref()
         .map_or(Ok(vec![]), |params| {
             params
-                .into_iter()
-                .map(|(_, expr)| execute_expression(expr))
+                .iter()
+                .map(|(_, expr)| execute_expression(expr, env))
                 .collect::<Result<Vec<_>, _>>()
         })?;
 
-    match function.function {
+    calculate_higher_order_function(&function.function, first_param, other_params, env)
+}
+
+/// Runs the functions that evaluate their parameters themselves instead of getting their values, e.g. because a parameter
+/// is a variable that is bound while another parameter is evaluated. Returns `None` for all other functions.
+fn run_lazy_function(
+    function: &HigherOrderFunction,
+    env: &mut Environment,
+) -> Option<Result<FunctionReturnType, FunctionError>> {
+    let result = match function.function {
+        // The first parameter of `Let` is the name of the variable and must not be evaluated
+        HigherOrderFunc::Let(_) => run_let(function, env),
+        // Only the branch selected by the condition of `If` is evaluated, which allows recursive functions to terminate
+        HigherOrderFunc::If(_) => run_if(function, env),
+        // The variable of the derivative has to stay free while the expression is evaluated
+        HigherOrderFunc::Derivative(_) => run_derivative(function, env),
+        // The variable that is substituted has to stay free while the expression is evaluated as well
+        HigherOrderFunc::Substitution(_) => run_substitution(function, env),
+        // Equations, root finding, integrals, sums and products evaluate the expression for many values of the variable
+        HigherOrderFunc::Solve(_) if get_equation(&function.params.expression).is_some() => {
+            run_solve(function, env)
+        }
+        HigherOrderFunc::FindRoot(_) => run_find_root(function, env),
+        HigherOrderFunc::Integral(_) => run_integral(function, env),
+        HigherOrderFunc::Sum(_) => run_sum_or_product(function, ElementaryFunc::Addition, env),
+        HigherOrderFunc::Product(_) => {
+            run_sum_or_product(function, ElementaryFunc::Multiplication, env)
+        }
+        HigherOrderFunc::NumericDerivative(_) => run_numeric_derivative(function, env),
+        HigherOrderFunc::Limit(_) => run_limit(function, env),
+        _ => return None,
+    };
+
+    Some(result)
+}
+
+/// Runs the function on calculated parameters, the variant depends on their types and the modes of the session
+fn calculate_higher_order_function(
+    function: &HigherOrderFunc,
+    first_param: FunctionReturnType,
+    mut other_params: Vec<FunctionReturnType>,
+    env: &mut Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    if std::iter::once(&first_param)
+        .chain(&other_params)
+        .any(|param| matches!(param, FunctionReturnType::Symbolic(_)))
+    {
+        return run_symbolic_function(function, first_param, other_params);
+    }
+
+    match run_exact_function(function, &first_param, &other_params, env.exact_mode) {
+        // Exact functions fall back to floating point numbers if the result is not exact, e.g. for Pow(2, 0.5)
+        Some(Ok(FunctionReturnType::F64(_))) if env.precision.is_some() => (),
+        Some(result) => return result,
+        None => (),
+    }
+
+    if let Some(result) = run_quantity_function(function, &first_param, &other_params) {
+        return result;
+    }
+
+    if let Some(result) =
+        run_complex_function(function, &first_param, &other_params, env.complex_mode)
+    {
+        return result;
+    }
+
+    if let Some(precision) = env.precision {
+        if let Some(result) = run_decimal_function(
+            function,
+            &first_param,
+            &other_params,
+            precision,
+            &env.angle_mode,
+        ) {
+            return result;
+        }
+    }
+
+    match function {
         HigherOrderFunc::Factorial(_) => factorial(first_param.get_u32()?),
-        HigherOrderFunc::EucleadianModulo(_) => Ok(modulo_euclid(
+        HigherOrderFunc::EucleadianModulo(_) => modulo_euclid(
             first_param.get_f64()?,
             other_params
                 .pop()
                 .expect("There should be enough parameters after parsing.")
                 .get_f64()?,
-        )),
+        ),
         HigherOrderFunc::Minimum(_) => Ok(minimum(
             first_param.get_f64()?,
             other_params
@@ -102,5 +801,1584 @@ fn run_higher_order_function(
                 .get_f64()?,
         )),
         HigherOrderFunc::Absolute(_) => Ok(absolute(first_param.get_f64()?)),
+        HigherOrderFunc::Power(_) => exponentiation(
+            first_param.get_f64()?,
+            other_params
+                .pop()
+                .expect("There should be enough parameters after parsing.")
+                .get_f64()?,
+        ),
+        HigherOrderFunc::SquareRoot(_) => square_root(first_param.get_f64()?),
+        HigherOrderFunc::CubeRoot(_) => Ok(cube_root(first_param.get_f64()?)),
+        HigherOrderFunc::Root(_) => root(
+            first_param.get_f64()?,
+            other_params
+                .pop()
+                .expect("There should be enough parameters after parsing.")
+                .get_f64()?,
+        ),
+        HigherOrderFunc::Sine(_) => sine(first_param.get_f64()?, &env.angle_mode),
+        HigherOrderFunc::Cosine(_) => cosine(first_param.get_f64()?, &env.angle_mode),
+        HigherOrderFunc::Tangent(_) => tangent(first_param.get_f64()?, &env.angle_mode),
+        HigherOrderFunc::Arcsine(_) => arcsine(first_param.get_f64()?, &env.angle_mode),
+        HigherOrderFunc::Arccosine(_) => arccosine(first_param.get_f64()?, &env.angle_mode),
+        HigherOrderFunc::Arctangent(_) => Ok(arctangent(first_param.get_f64()?, &env.angle_mode)),
+        HigherOrderFunc::Arctangent2(_) => Ok(arctangent2(
+            first_param.get_f64()?,
+            other_params
+                .pop()
+                .expect("There should be enough parameters after parsing.")
+                .get_f64()?,
+            &env.angle_mode,
+        )),
+        HigherOrderFunc::HyperbolicSine(_) => hyperbolic_sine(first_param.get_f64()?),
+        HigherOrderFunc::HyperbolicCosine(_) => hyperbolic_cosine(first_param.get_f64()?),
+        HigherOrderFunc::HyperbolicTangent(_) => Ok(hyperbolic_tangent(first_param.get_f64()?)),
+        HigherOrderFunc::AreaHyperbolicSine(_) => Ok(area_hyperbolic_sine(first_param.get_f64()?)),
+        HigherOrderFunc::AreaHyperbolicCosine(_) => area_hyperbolic_cosine(first_param.get_f64()?),
+        HigherOrderFunc::AreaHyperbolicTangent(_) => {
+            area_hyperbolic_tangent(first_param.get_f64()?)
+        }
+        HigherOrderFunc::NaturalLogarithm(_) => natural_logarithm(first_param.get_f64()?),
+        HigherOrderFunc::DecimalLogarithm(_) => decimal_logarithm(first_param.get_f64()?),
+        HigherOrderFunc::BinaryLogarithm(_) => binary_logarithm(first_param.get_f64()?),
+        HigherOrderFunc::Logarithm(_) => logarithm(
+            first_param.get_f64()?,
+            other_params
+                .pop()
+                .expect("There should be enough parameters after parsing.")
+                .get_f64()?,
+        ),
+        HigherOrderFunc::Exponential(_) => exponential(first_param.get_f64()?),
+        HigherOrderFunc::ExponentialMinusOne(_) => exponential_minus_one(first_param.get_f64()?),
+        HigherOrderFunc::NaturalLogarithmOnePlus(_) => {
+            natural_logarithm_one_plus(first_param.get_f64()?)
+        }
+        HigherOrderFunc::ToFloat(_) => to_float(first_param),
+        HigherOrderFunc::ToFraction(_) => to_fraction(first_param),
+        HigherOrderFunc::RealPart(_) => Ok(real_part(first_param.get_complex()?)),
+        HigherOrderFunc::ImaginaryPart(_) => Ok(imaginary_part(first_param.get_complex()?)),
+        HigherOrderFunc::Conjugate(_) => Ok(conjugate(first_param.get_complex()?)),
+        HigherOrderFunc::Argument(_) => Ok(argument(first_param.get_complex()?, &env.angle_mode)),
+        HigherOrderFunc::ToPolar(_) => Ok(to_polar(first_param.get_complex()?, &env.angle_mode)),
+        HigherOrderFunc::FromPolar(_) => Ok(from_polar(
+            first_param.get_f64()?,
+            other_params
+                .pop()
+                .expect("There should be enough parameters after parsing.")
+                .get_f64()?,
+            &env.angle_mode,
+        )),
+        HigherOrderFunc::List(_) => {
+            other_params.insert(0, first_param);
+            Ok(FunctionReturnType::List(other_params))
+        }
+        HigherOrderFunc::Map(_) => {
+            let function = other_params
+                .pop()
+                .expect("There should be enough parameters after parsing.")
+                .get_lambda()?;
+
+            first_param
+                .get_list()?
+                .into_iter()
+                .map(|value| call_passed_closure("Map", &function, vec![value], env))
+                .collect::<Result<Vec<_>, _>>()
+                .map(FunctionReturnType::List)
+        }
+        HigherOrderFunc::Filter(_) => {
+            let predicate = other_params
+                .pop()
+                .expect("There should be enough parameters after parsing.")
+                .get_lambda()?;
+
+            let mut values = Vec::new();
+            for value in first_param.get_list()? {
+                if call_passed_closure("Filter", &predicate, vec![value.clone()], env)?.get_bool()? {
+                    values.push(value);
+                }
+            }
+
+            Ok(FunctionReturnType::List(values))
+        }
+        HigherOrderFunc::Fold(_) => {
+            let function = other_params
+                .pop()
+                .expect("There should be enough parameters after parsing.")
+                .get_lambda()?;
+            let init = other_params
+                .pop()
+                .expect("There should be enough parameters after parsing.");
+
+            first_param
+                .get_list()?
+                .into_iter()
+                .try_fold(init, |acc, value| {
+                    call_passed_closure("Fold", &function, vec![acc, value], env)
+                })
+        }
+        HigherOrderFunc::Apply(_) => {
+            call_passed_closure("Apply", &first_param.get_lambda()?, other_params, env)
+        }
+        // Numbers are already as simple as possible
+        HigherOrderFunc::Simplify(_) | HigherOrderFunc::Expand(_) | HigherOrderFunc::Factor(_) => {
+            Ok(first_param)
+        }
+        HigherOrderFunc::Dot(_)
+        | HigherOrderFunc::Cross(_)
+        | HigherOrderFunc::Determinant(_)
+        | HigherOrderFunc::Inverse(_)
+        | HigherOrderFunc::Transpose(_)
+        | HigherOrderFunc::Trace(_)
+        | HigherOrderFunc::Rank(_) => {
+            run_matrix_function(function, first_param, other_params.pop(), env)
+        }
+        // Solving equations is handled before the parameters are evaluated, so this is a system of linear equations
+        HigherOrderFunc::Solve(_) if other_params.len() == 1 => {
+            run_matrix_function(function, first_param, other_params.pop(), env)
+        }
+        HigherOrderFunc::Solve(_) => Err(FunctionError::new(
+            format!(
+                "'Solve' takes a matrix and a vector, e.g. 'Solve([[2, 1], [1, 3]], [3, 5])', or an equation, e.g. 'Solve(x^2 = 2, x)', but {} parameters were provided.",
+                other_params.len() + 1
+            ),
+            FunctionErrorType::InvalidNumberOfParams,
+        )),
+        HigherOrderFunc::Let(_)
+        | HigherOrderFunc::If(_)
+        | HigherOrderFunc::Derivative(_)
+        | HigherOrderFunc::Substitution(_)
+        | HigherOrderFunc::FindRoot(_)
+        | HigherOrderFunc::Integral(_)
+        | HigherOrderFunc::Sum(_)
+        | HigherOrderFunc::Product(_)
+        | HigherOrderFunc::NumericDerivative(_)
+        | HigherOrderFunc::Limit(_) => {
+            unreachable!("Functions with lazily evaluated parameters are run before their parameters are evaluated.")
+        }
+    }
+}
+
+/// Runs an elementary function of which at least one side is a symbolic expression, the result is simplified, e.g. `2x + 3x` is `5x`
+fn run_symbolic_elementary_function(
+    function: &ElementaryFunc,
+    lhs: FunctionReturnType,
+    rhs: FunctionReturnType,
+) -> Result<FunctionReturnType, FunctionError> {
+    let lhs = Symbolic::from_value(lhs)?;
+    let rhs = Symbolic::from_value(rhs)?;
+
+    let result = match function {
+        ElementaryFunc::Addition => Symbolic::sum(vec![lhs, rhs]),
+        ElementaryFunc::Subtraction => Symbolic::sum(vec![lhs, Symbolic::negation(rhs)]),
+        ElementaryFunc::Multiplication => Symbolic::product(vec![lhs, rhs]),
+        ElementaryFunc::Division if rhs.is_zero() => {
+            return Err(FunctionError::new(
+                format!("You cannot divide by zero. You tried to divide {} by {} which has no result.", lhs, rhs),
+                FunctionErrorType::DivisionByZero,
+            ))
+        }
+        ElementaryFunc::Division => {
+            Symbolic::product(vec![lhs, Symbolic::power(rhs, Symbolic::integer(-1))])
+        }
+        ElementaryFunc::Exponentiation => Symbolic::power(lhs, rhs),
+        ElementaryFunc::Modulo
+        | ElementaryFunc::LessThan
+        | ElementaryFunc::GreaterThan
+        | ElementaryFunc::Conversion => {
+            return Err(FunctionError::new(
+                format!("Modulo, comparisons and conversions need numbers but got the symbolic expressions {} and {}.", lhs, rhs),
+                FunctionErrorType::InvalidParameterType,
+            ))
+        }
+    };
+
+    Ok(symbolic_value(result))
+}
+
+/// Runs a function of which at least one parameter is a symbolic expression, the result is the call of the function as an expression
+fn run_symbolic_function(
+    function: &HigherOrderFunc,
+    first_param: FunctionReturnType,
+    other_params: Vec<FunctionReturnType>,
+) -> Result<FunctionReturnType, FunctionError> {
+    let mut params = std::iter::once(first_param)
+        .chain(other_params)
+        .map(Symbolic::from_value)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let result = match function {
+        HigherOrderFunc::Simplify(_) => symbolic::simplify(&params[0]),
+        HigherOrderFunc::Expand(_) => symbolic::expand(&params[0]),
+        HigherOrderFunc::Factor(_) => symbolic::factor(&params[0]),
+        HigherOrderFunc::SquareRoot(_) => {
+            Symbolic::power(params.remove(0), Symbolic::fraction(1, 2))
+        }
+        HigherOrderFunc::Power(_) => {
+            let exponent = params.remove(1);
+            Symbolic::power(params.remove(0), exponent)
+        }
+        HigherOrderFunc::Root(_) => {
+            let degree = params.remove(1);
+            Symbolic::power(
+                params.remove(0),
+                Symbolic::power(degree, Symbolic::integer(-1)),
+            )
+        }
+        HigherOrderFunc::CubeRoot(_)
+        | HigherOrderFunc::Absolute(_)
+        | HigherOrderFunc::Sine(_)
+        | HigherOrderFunc::Cosine(_)
+        | HigherOrderFunc::Tangent(_)
+        | HigherOrderFunc::Arcsine(_)
+        | HigherOrderFunc::Arccosine(_)
+        | HigherOrderFunc::Arctangent(_)
+        | HigherOrderFunc::Arctangent2(_)
+        | HigherOrderFunc::HyperbolicSine(_)
+        | HigherOrderFunc::HyperbolicCosine(_)
+        | HigherOrderFunc::HyperbolicTangent(_)
+        | HigherOrderFunc::AreaHyperbolicSine(_)
+        | HigherOrderFunc::AreaHyperbolicCosine(_)
+        | HigherOrderFunc::AreaHyperbolicTangent(_)
+        | HigherOrderFunc::NaturalLogarithm(_)
+        | HigherOrderFunc::DecimalLogarithm(_)
+        | HigherOrderFunc::BinaryLogarithm(_)
+        | HigherOrderFunc::Logarithm(_)
+        | HigherOrderFunc::Exponential(_)
+        | HigherOrderFunc::ExponentialMinusOne(_)
+        | HigherOrderFunc::NaturalLogarithmOnePlus(_) => {
+            Symbolic::function(function.clone(), params)
+        }
+        function => {
+            return Err(FunctionError::new(
+                format!(
+                    "The function '{}' cannot be applied to symbolic expressions.",
+                    function.get_keyword()
+                ),
+                FunctionErrorType::InvalidParameterType,
+            ))
+        }
+    };
+
+    Ok(symbolic_value(result))
+}
+
+/// Runs a function of vectors or matrices. Their elements are calculated like elementary functions,
+/// so integer matrices stay exact during elimination.
+fn run_matrix_function(
+    function: &HigherOrderFunc,
+    first_param: FunctionReturnType,
+    second_param: Option<FunctionReturnType>,
+    env: &Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    let mut arithmetic = |function: &ElementaryFunc, lhs, rhs| {
+        calculate_elementary_function(function, lhs, rhs, env)
+    };
+    let second_param = || second_param.expect("There should be enough parameters after parsing.");
+
+    let result = match function {
+        HigherOrderFunc::Dot(_) => matrix::dot(
+            matrix::get_vector(first_param)?,
+            matrix::get_vector(second_param())?,
+            &mut arithmetic,
+        ),
+        HigherOrderFunc::Cross(_) => matrix::cross(
+            matrix::get_vector(first_param)?,
+            matrix::get_vector(second_param())?,
+            &mut arithmetic,
+        ),
+        HigherOrderFunc::Determinant(_) => {
+            matrix::determinant(matrix::get_square_matrix(first_param)?, &mut arithmetic)
+        }
+        HigherOrderFunc::Inverse(_) => {
+            matrix::inverse(matrix::get_square_matrix(first_param)?, &mut arithmetic)
+        }
+        HigherOrderFunc::Transpose(_) => Ok(matrix::transpose(matrix::get_matrix(first_param)?)),
+        HigherOrderFunc::Trace(_) => {
+            matrix::trace(matrix::get_square_matrix(first_param)?, &mut arithmetic)
+        }
+        HigherOrderFunc::Rank(_) => matrix::rank(matrix::get_matrix(first_param)?, &mut arithmetic),
+        HigherOrderFunc::Solve(_) => matrix::solve(
+            matrix::get_square_matrix(first_param)?,
+            second_param().get_list()?,
+            &mut arithmetic,
+        ),
+        _ => unreachable!(
+            "Only functions of vectors and matrices should be run as matrix functions."
+        ),
+    }?;
+
+    approximate_fractions(result, env)
+}
+
+/// Converts the fractions that the exact elimination of matrices results in to floating point numbers
+/// or decimals, like the division of integers does, unless the session is in exact mode
+fn approximate_fractions(
+    value: FunctionReturnType,
+    env: &Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    match value {
+        FunctionReturnType::Rational(num) if !env.exact_mode => match env.precision {
+            Some(precision) => Ok(FunctionReturnType::Decimal(decimal::from_rational(
+                &num, precision,
+            ))),
+            None => Ok(FunctionReturnType::F64(rational_to_f64(&num)?)),
+        },
+        FunctionReturnType::List(values) => values
+            .into_iter()
+            .map(|value| approximate_fractions(value, env))
+            .collect::<Result<Vec<_>, _>>()
+            .map(FunctionReturnType::List),
+        value => Ok(value),
+    }
+}
+
+/// Runs the exact variant of the function if it has one and all parameters are integers or fractions
+fn run_exact_function(
+    function: &HigherOrderFunc,
+    first_param: &FunctionReturnType,
+    other_params: &[FunctionReturnType],
+    exact_mode: bool,
+) -> Option<Result<FunctionReturnType, FunctionError>> {
+    if let FunctionReturnType::BigInt(first) = first_param {
+        match (function, other_params) {
+            (HigherOrderFunc::Absolute(_), []) => return Some(Ok(integer_absolute(first))),
+            (HigherOrderFunc::Minimum(_), [FunctionReturnType::BigInt(second)]) => {
+                return Some(Ok(integer_minimum(first, second)))
+            }
+            (HigherOrderFunc::Maximum(_), [FunctionReturnType::BigInt(second)]) => {
+                return Some(Ok(integer_maximum(first, second)))
+            }
+            (HigherOrderFunc::EucleadianModulo(_), [FunctionReturnType::BigInt(second)]) => {
+                return Some(integer_modulo_euclid(first, second))
+            }
+            // Negative powers are calculated as fractions in exact mode
+            (HigherOrderFunc::Power(_), [FunctionReturnType::BigInt(second)])
+                if !(exact_mode && second.is_negative()) =>
+            {
+                return Some(integer_exponentiation(first, second))
+            }
+            _ => (),
+        }
+    }
+
+    let first = first_param.get_exact()?;
+    let second = match other_params {
+        [] => None,
+        [second] => Some(second.get_exact()?),
+        _ => return None,
+    };
+
+    match (function, second) {
+        (HigherOrderFunc::Absolute(_), None) => Some(Ok(rational_absolute(&first))),
+        (HigherOrderFunc::Minimum(_), Some(second)) => Some(Ok(rational_minimum(&first, &second))),
+        (HigherOrderFunc::Maximum(_), Some(second)) => Some(Ok(rational_maximum(&first, &second))),
+        (HigherOrderFunc::EucleadianModulo(_), Some(second)) => {
+            Some(rational_modulo_euclid(&first, &second))
+        }
+        (HigherOrderFunc::Power(_), Some(second)) => Some(rational_exponentiation(&first, &second)),
+        _ => None,
+    }
+}
+
+/// Runs the quantity variant of the function if it has one and a parameter is a quantity
+fn run_quantity_function(
+    function: &HigherOrderFunc,
+    first_param: &FunctionReturnType,
+    other_params: &[FunctionReturnType],
+) -> Option<Result<FunctionReturnType, FunctionError>> {
+    let is_quantity = |param: &FunctionReturnType| matches!(param, FunctionReturnType::Quantity(_));
+
+    if !is_quantity(first_param) && !other_params.iter().any(is_quantity) {
+        return None;
+    }
+
+    let quantities = std::iter::once(first_param)
+        .chain(other_params)
+        .map(|param| param.clone().get_quantity())
+        .collect::<Result<Vec<_>, _>>();
+    let quantities = match quantities {
+        Ok(quantities) => quantities,
+        Err(err) => return Some(Err(err)),
+    };
+
+    match (function, quantities.as_slice()) {
+        (HigherOrderFunc::Absolute(_), [num]) => Some(Ok(quantity_absolute(num))),
+        (HigherOrderFunc::Minimum(_), [a, b]) => Some(quantity_minimum(a, b)),
+        (HigherOrderFunc::Maximum(_), [a, b]) => Some(quantity_maximum(a, b)),
+        (HigherOrderFunc::SquareRoot(_), [num]) => Some(quantity_exponentiation(num, 0.5)),
+        (HigherOrderFunc::CubeRoot(_), [num]) => Some(quantity_exponentiation(num, 1.0 / 3.0)),
+        _ => None,
+    }
+}
+
+/// Runs the complex variant of the function if it has one and a parameter is complex or,
+/// in complex mode, if the first parameter is a negative number
+fn run_complex_function(
+    function: &HigherOrderFunc,
+    first_param: &FunctionReturnType,
+    other_params: &[FunctionReturnType],
+    complex_mode: bool,
+) -> Option<Result<FunctionReturnType, FunctionError>> {
+    let is_complex = |param: &FunctionReturnType| matches!(param, FunctionReturnType::Complex(_));
+    let is_negative = first_param.clone().get_f64().is_ok_and(|num| num < 0.0);
+
+    let needs_complex = is_complex(first_param)
+        || other_params.iter().any(is_complex)
+        || (complex_mode && is_negative);
+
+    if !needs_complex {
+        return None;
+    }
+
+    let first = match first_param.clone().get_complex() {
+        Ok(first) => first,
+        Err(err) => return Some(Err(err)),
+    };
+
+    match (function, other_params) {
+        (HigherOrderFunc::Absolute(_), []) => Some(Ok(complex_absolute(first))),
+        (HigherOrderFunc::SquareRoot(_), []) => Some(Ok(complex_square_root(first))),
+        (HigherOrderFunc::NaturalLogarithm(_), []) => Some(complex_natural_logarithm(first)),
+        (HigherOrderFunc::Exponential(_), []) => Some(Ok(complex_exponential(first))),
+        (HigherOrderFunc::Power(_), [exponent]) => Some(
+            exponent
+                .clone()
+                .get_complex()
+                .and_then(|exponent| complex_exponentiation(first, exponent)),
+        ),
+        _ => None,
+    }
+}
+
+/// Runs the decimal variant of the function with the precision of the session if it has one
+/// and all parameters are finite numbers
+fn run_decimal_function(
+    function: &HigherOrderFunc,
+    first_param: &FunctionReturnType,
+    other_params: &[FunctionReturnType],
+    precision: u64,
+    angle_mode: &AngleMode,
+) -> Option<Result<FunctionReturnType, FunctionError>> {
+    if !first_param.is_finite() {
+        return None;
+    }
+    let first = first_param.clone().get_decimal(precision).ok()?;
+    let second = match other_params {
+        [] => None,
+        [second] if second.is_finite() => Some(second.clone().get_decimal(precision).ok()?),
+        _ => return None,
+    };
+
+    let result = match (function, second) {
+        (HigherOrderFunc::EucleadianModulo(_), Some(second)) => {
+            decimal::modulo_euclid(&first, &second, precision)
+        }
+        (HigherOrderFunc::Minimum(_), Some(second)) => Ok(decimal::minimum(&first, &second)),
+        (HigherOrderFunc::Maximum(_), Some(second)) => Ok(decimal::maximum(&first, &second)),
+        (HigherOrderFunc::Absolute(_), None) => Ok(decimal::absolute(&first)),
+        (HigherOrderFunc::Power(_), Some(second)) => {
+            decimal::exponentiation(&first, &second, precision)
+        }
+        (HigherOrderFunc::SquareRoot(_), None) => decimal::square_root(&first, precision),
+        (HigherOrderFunc::CubeRoot(_), None) => decimal::cube_root(&first, precision),
+        (HigherOrderFunc::Root(_), Some(second)) => decimal::root(&first, &second, precision),
+        (HigherOrderFunc::Sine(_), None) => Ok(decimal::sine(&first, precision, angle_mode)),
+        (HigherOrderFunc::Cosine(_), None) => Ok(decimal::cosine(&first, precision, angle_mode)),
+        (HigherOrderFunc::Tangent(_), None) => decimal::tangent(&first, precision, angle_mode),
+        (HigherOrderFunc::Arcsine(_), None) => decimal::arcsine(&first, precision, angle_mode),
+        (HigherOrderFunc::Arccosine(_), None) => decimal::arccosine(&first, precision, angle_mode),
+        (HigherOrderFunc::Arctangent(_), None) => {
+            Ok(decimal::arctangent(&first, precision, angle_mode))
+        }
+        (HigherOrderFunc::Arctangent2(_), Some(second)) => {
+            Ok(decimal::arctangent2(&first, &second, precision, angle_mode))
+        }
+        (HigherOrderFunc::HyperbolicSine(_), None) => decimal::hyperbolic_sine(&first, precision),
+        (HigherOrderFunc::HyperbolicCosine(_), None) => {
+            decimal::hyperbolic_cosine(&first, precision)
+        }
+        (HigherOrderFunc::HyperbolicTangent(_), None) => {
+            Ok(decimal::hyperbolic_tangent(&first, precision))
+        }
+        (HigherOrderFunc::AreaHyperbolicSine(_), None) => {
+            Ok(decimal::area_hyperbolic_sine(&first, precision))
+        }
+        (HigherOrderFunc::AreaHyperbolicCosine(_), None) => {
+            decimal::area_hyperbolic_cosine(&first, precision)
+        }
+        (HigherOrderFunc::AreaHyperbolicTangent(_), None) => {
+            decimal::area_hyperbolic_tangent(&first, precision)
+        }
+        (HigherOrderFunc::NaturalLogarithm(_), None) => {
+            decimal::natural_logarithm(&first, precision)
+        }
+        (HigherOrderFunc::DecimalLogarithm(_), None) => {
+            decimal::decimal_logarithm(&first, precision)
+        }
+        (HigherOrderFunc::BinaryLogarithm(_), None) => decimal::binary_logarithm(&first, precision),
+        (HigherOrderFunc::Logarithm(_), Some(second)) => {
+            decimal::logarithm(&first, &second, precision)
+        }
+        (HigherOrderFunc::Exponential(_), None) => decimal::exponential(&first, precision),
+        (HigherOrderFunc::ExponentialMinusOne(_), None) => {
+            decimal::exponential_minus_one(&first, precision)
+        }
+        (HigherOrderFunc::NaturalLogarithmOnePlus(_), None) => {
+            decimal::natural_logarithm_one_plus(&first, precision)
+        }
+        _ => return None,
+    };
+
+    Some(result)
+}
+
+/// Binds the value of the second parameter to the variable given as the first parameter
+fn run_let(
+    function: &HigherOrderFunction,
+    env: &mut Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    let variable = get_variable(&function.params.expression).ok_or_else(|| {
+        FunctionError::new(
+            "The first parameter of 'Let' has to be a variable name.".to_string(),
+            FunctionErrorType::InvalidParameterType,
+        )
+    })?;
+
+    let (_, expression) = function
+        .params
+        .expression_comma
+        .as_ref()
+        .and_then(|params| params.first())
+        .expect("There should be enough parameters after parsing.");
+
+    let value = execute_expression(expression, env)?;
+    env.set_variable(variable.name.clone(), value.clone());
+
+    Ok(value)
+}
+
+/// Returns the variable if the expression is only the name of a variable
+fn get_variable(expression: &Expression) -> Option<&Variable> {
+    match expression {
+        Expression::MathExpression(expr, _) => match expr.as_ref() {
+            MathExpression::Variable(var) => Some(var),
+            _ => None,
+        },
+        Expression::BracketedExpression(..) => None,
+    }
+}
+
+/// Differentiates the first parameter with respect to the variable of the second parameter.
+/// The first parameter is evaluated with the variable bound to itself, so that it stays free even if it has a value.
+fn run_derivative(
+    function: &HigherOrderFunction,
+    env: &mut Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    let (expression, variable) = execute_with_free_variable(function, "Diff(x^2, x)", env)?;
+    let derivative = symbolic::derivative(&expression, &variable, &env.angle_mode)?;
+
+    approximate_fractions(symbolic_value(derivative), env)
+}
+
+/// Replaces the variable of the second parameter in the first parameter with the value of the third parameter
+/// and calculates the result, e.g. `Subst(x^2 + 1, x, 3)` is `10`
+fn run_substitution(
+    function: &HigherOrderFunction,
+    env: &mut Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    let (expression, variable) = execute_with_free_variable(function, "Subst(x^2, x, 3)", env)?;
+
+    let (_, value) = function
+        .params
+        .expression_comma
+        .as_ref()
+        .and_then(|params| params.get(1))
+        .expect("There should be enough parameters after parsing.");
+    let value = execute_expression(value, env)?;
+
+    let result = calculate_symbolic(&expression, &variable, &value, env)?;
+
+    approximate_fractions(result, env)
+}
+
+/// Evaluates the first parameter of the function with the variable of the second parameter bound to itself, so that
+/// it stays free even if it has a value. Returns the evaluated expression and the name of the variable.
+fn execute_with_free_variable(
+    function: &HigherOrderFunction,
+    example: &str,
+    env: &mut Environment,
+) -> Result<(Symbolic, String), FunctionError> {
+    let variable = get_variable_param(function, example)?;
+
+    let value = with_binding(
+        &variable.name,
+        FunctionReturnType::Symbolic(Symbolic::Variable(variable.name.clone())),
+        env,
+        |env| execute_expression(&function.params.expression, env),
+    );
+
+    Ok((Symbolic::from_value(value?)?, variable.name.clone()))
+}
+
+/// Returns the variable of the second parameter, in which the first parameter of the function is calculated
+fn get_variable_param<'a>(
+    function: &'a HigherOrderFunction,
+    example: &str,
+) -> Result<&'a Variable, FunctionError> {
+    function
+        .params
+        .expression_comma
+        .as_ref()
+        .and_then(|params| params.first())
+        .and_then(|(_, expression)| get_variable(expression))
+        .ok_or_else(|| {
+            FunctionError::new(
+                format!(
+                    "The second parameter of '{}' has to be a variable name, e.g. '{}'.",
+                    function.function.get_keyword(),
+                    example
+                ),
+                FunctionErrorType::InvalidParameterType,
+            )
+        })
+}
+
+/// Calculates the expression with the value in place of the variable. All functions are run again, so calls with numbers
+/// as parameters are calculated, e.g. `Sin(x)` becomes `0` for `x = 0`, and the result is a number if no other variables remain.
+fn calculate_symbolic(
+    expression: &Symbolic,
+    variable: &str,
+    value: &FunctionReturnType,
+    env: &mut Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    let calculate_all = |params: &[Symbolic], env: &mut Environment| {
+        params
+            .iter()
+            .map(|param| calculate_symbolic(param, variable, value, env))
+            .collect::<Result<Vec<_>, _>>()
+    };
+
+    match expression {
+        Symbolic::Number(num) => Ok(rational_value(num.clone())),
+        Symbolic::Float(num) => Ok(FunctionReturnType::F64(*num)),
+        Symbolic::Constant(constant) => Ok(constant_value(constant, env)),
+        Symbolic::Variable(name) if name == variable => Ok(value.clone()),
+        Symbolic::Variable(_) => Ok(FunctionReturnType::Symbolic(expression.clone())),
+        Symbolic::Sum(terms) => calculate_all(terms, env)?
+            .into_iter()
+            .try_fold(FunctionReturnType::BigInt(BigInt::zero()), |sum, term| {
+                calculate_elementary_function(&ElementaryFunc::Addition, sum, term, env)
+            }),
+        Symbolic::Product(factors) => calculate_all(factors, env)?.into_iter().try_fold(
+            FunctionReturnType::BigInt(BigInt::one()),
+            |product, factor| {
+                calculate_elementary_function(&ElementaryFunc::Multiplication, product, factor, env)
+            },
+        ),
+        Symbolic::Power(base, exponent) => {
+            let mut params = calculate_all(&[*base.clone(), *exponent.clone()], env)?;
+            let exponent = params.pop().expect("A power has an exponent.");
+            let base = params.pop().expect("A power has a base.");
+            calculate_elementary_function(&ElementaryFunc::Exponentiation, base, exponent, env)
+        }
+        Symbolic::Function(function, params) => {
+            let mut params = calculate_all(params, env)?;
+            let first_param = params.remove(0);
+            calculate_higher_order_function(function, first_param, params, env)
+        }
+    }
+}
+
+/// Finds all real solutions of the equation in the first parameter for the variable of the second parameter.
+/// They are searched in the interval between the third and the fourth parameter if provided, e.g. `Solve(Sin(x) = 0, x, 0, 10)`.
+fn run_solve(
+    function: &HigherOrderFunction,
+    env: &mut Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    let params = get_params(function);
+
+    let (from, to) = match params.len() {
+        2 => (-numeric::DEFAULT_SEARCH_BOUND, numeric::DEFAULT_SEARCH_BOUND),
+        4 => (
+            execute_expression(params[2], env)?.get_f64()?,
+            execute_expression(params[3], env)?.get_f64()?,
+        ),
+        found => {
+            return Err(FunctionError::new(
+                format!(
+                    "'Solve' takes an equation, a variable and optionally the bounds of an interval, e.g. 'Solve(x^2 = 2, x, 0, 10)', but {} parameters were provided.",
+                    found
+                ),
+                FunctionErrorType::InvalidNumberOfParams,
+            ))
+        }
+    };
+
+    let variable = get_variable_param(function, "Solve(x^2 = 2, x)")?;
+    let roots = numeric::find_roots(
+        &mut create_real_function(params[0], &variable.name, env),
+        from,
+        to,
+    );
+    let roots =
+        roots.map_err(|err| explain_missing_variable(err, params[0], &variable.name, env))?;
+
+    Ok(FunctionReturnType::List(
+        roots.into_iter().map(FunctionReturnType::F64).collect(),
+    ))
+}
+
+/// Finds a root of the first parameter, or a solution if it is an equation, for the variable of the second parameter
+/// near the value of the third parameter, e.g. `FindRoot(Cos(x) = x, x, 1)`
+fn run_find_root(
+    function: &HigherOrderFunction,
+    env: &mut Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    let params = get_params(function);
+
+    let guess = execute_expression(params[2], env)?.get_f64()?;
+    let variable = get_variable_param(function, "FindRoot(x^2 - 2, x, 1)")?;
+    let root = numeric::find_root(
+        &mut create_real_function(params[0], &variable.name, env),
+        guess,
+    );
+    let root = root.map_err(|err| explain_missing_variable(err, params[0], &variable.name, env))?;
+
+    Ok(FunctionReturnType::F64(root))
+}
+
+/// Returns the expression as a real function of the variable. It is evaluated again for every value with the variable
+/// bound to it. An equation is turned into the difference of its sides, which is zero where the equation holds.
+fn create_real_function<'a>(
+    expression: &'a Expression,
+    variable: &'a str,
+    env: &'a mut Environment,
+) -> impl FnMut(f64) -> Result<f64, FunctionError> + 'a {
+    move |value| {
+        with_binding(
+            variable,
+            FunctionReturnType::F64(value),
+            env,
+            |env| match get_equation(expression) {
+                Some(equation) => execute_expression(&equation.lhs, env).and_then(|lhs| {
+                    Ok(lhs.get_f64()? - execute_expression(&equation.rhs, env)?.get_f64()?)
+                }),
+                None => execute_expression(expression, env).and_then(FunctionReturnType::get_f64),
+            },
+        )
+    }
+}
+
+/// Explains why a real function of the variable has no numeric values if it does not depend on the variable,
+/// e.g. `x = 1` solved for `y`. Other errors are returned unchanged.
+fn explain_missing_variable(
+    err: FunctionError,
+    expression: &Expression,
+    variable: &str,
+    env: &mut Environment,
+) -> FunctionError {
+    if !matches!(err.error_type, FunctionErrorType::InvalidParameterType) {
+        return err;
+    }
+
+    let (kind, sides) = match get_equation(expression) {
+        Some(equation) => ("equation", vec![&equation.lhs, &equation.rhs]),
+        None => ("expression", vec![expression]),
+    };
+    let depends_on_variable = with_binding(
+        variable,
+        FunctionReturnType::Symbolic(Symbolic::Variable(variable.to_string())),
+        env,
+        |env| {
+            sides
+                .iter()
+                .any(|side| match execute_expression(side, env) {
+                    Ok(FunctionReturnType::Symbolic(value)) => value.contains_variable(variable),
+                    Ok(_) => false,
+                    Err(_) => true,
+                })
+        },
+    );
+
+    if depends_on_variable {
+        err
+    } else {
+        FunctionError::new(
+            format!(
+                "The {} does not depend on {}, so it cannot be solved for it.",
+                kind, variable
+            ),
+            FunctionErrorType::InvalidParameterType,
+        )
+    }
+}
+
+/// Runs the closure in a new scope in which the variable is bound to the value, all other variables keep their values
+fn with_binding<T>(
+    variable: &str,
+    value: FunctionReturnType,
+    env: &mut Environment,
+    run: impl FnOnce(&mut Environment) -> T,
+) -> T {
+    let mut scope = env.capture_scope();
+    scope.insert(variable.to_string(), value);
+
+    env.push_scope(scope);
+    let result = run(env);
+    env.pop_scope();
+
+    result
+}
+
+/// Integrates the first parameter over the variable of the second parameter from the third to the fourth parameter,
+/// e.g. `Integrate(x^2, x, 0, 1)`. The bounds can be infinite, e.g. `Integrate(Exp(-x^2), x, -Inf, Inf)`.
+fn run_integral(
+    function: &HigherOrderFunction,
+    env: &mut Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    let params = get_params(function);
+
+    let from = execute_expression(params[2], env)?.get_f64()?;
+    let to = execute_expression(params[3], env)?.get_f64()?;
+    let variable = get_variable_param(function, "Integrate(x^2, x, 0, 1)")?;
+    let integral = numeric::integrate(
+        &mut create_real_function(params[0], &variable.name, env),
+        from,
+        to,
+    )?;
+
+    Ok(FunctionReturnType::F64(integral))
+}
+
+/// Calculates the derivative of the first parameter with respect to the variable of the second parameter
+/// at the value of the third parameter numerically, e.g. `NDiff(Sin(x), x, 0)`
+fn run_numeric_derivative(
+    function: &HigherOrderFunction,
+    env: &mut Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    let params = get_params(function);
+
+    let at = execute_expression(params[2], env)?.get_f64()?;
+    let variable = get_variable_param(function, "NDiff(x^2, x, 3)")?;
+    let derivative = numeric::derivative(
+        &mut create_real_function(params[0], &variable.name, env),
+        at,
+    )?;
+
+    Ok(FunctionReturnType::F64(derivative))
+}
+
+/// Estimates the limit of the first parameter for the variable of the second parameter tending towards the third parameter,
+/// e.g. `Limit(Sin(x)/x, x, 0)`. The optional fourth parameter is the side from which the limit is approached,
+/// a negative number for from below and a positive number for from above.
+fn run_limit(
+    function: &HigherOrderFunction,
+    env: &mut Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    let params = get_params(function);
+
+    let direction = match params.len() {
+        3 => numeric::Direction::Both,
+        4 => match execute_expression(params[3], env)?.get_f64()? {
+            side if side < 0.0 => numeric::Direction::Below,
+            side if side > 0.0 => numeric::Direction::Above,
+            _ => numeric::Direction::Both,
+        },
+        found => {
+            return Err(FunctionError::new(
+                format!(
+                    "'Limit' takes an expression, a variable, a point and optionally a side, e.g. 'Limit(1/x, x, 0, 1)', but {} parameters were provided.",
+                    found
+                ),
+                FunctionErrorType::InvalidNumberOfParams,
+            ))
+        }
+    };
+
+    let at = execute_expression(params[2], env)?.get_f64()?;
+    let variable = get_variable_param(function, "Limit(Sin(x)/x, x, 0)")?;
+    let limit = numeric::limit(
+        &mut create_real_function(params[0], &variable.name, env),
+        at,
+        direction,
+    )?;
+
+    Ok(FunctionReturnType::F64(limit))
+}
+
+/// Adds or multiplies the values of the first parameter for all integers from the third to the fourth parameter
+/// bound to the variable of the second parameter, e.g. `Sum(k^2, k, 1, 10)`. The empty sum is 0 and the empty product is 1.
+fn run_sum_or_product(
+    function: &HigherOrderFunction,
+    operation: ElementaryFunc,
+    env: &mut Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    let params = get_params(function);
+    let keyword = function.function.get_keyword();
+
+    let get_bound = |value: FunctionReturnType| match value {
+        FunctionReturnType::BigInt(num) => Ok(num),
+        other => Err(FunctionError::new(
+            format!(
+                "The bounds of '{}' have to be integers, e.g. '{}(k^2, k, 1, 10)', but got {}.",
+                keyword, keyword, other
+            ),
+            FunctionErrorType::InvalidParameterType,
+        )),
+    };
+    let from = get_bound(execute_expression(params[2], env)?)?;
+    let to = get_bound(execute_expression(params[3], env)?)?;
+
+    if &to - &from >= BigInt::from(MAX_ITERATED_TERMS) {
+        return Err(FunctionError::new(
+            format!(
+                "'{}' can iterate at most {} values, but the bounds are {} and {}.",
+                keyword, MAX_ITERATED_TERMS, from, to
+            ),
+            FunctionErrorType::DomainError,
+        ));
+    }
+
+    let variable = get_variable_param(function, &format!("{}(k^2, k, 1, 10)", keyword))?;
+    let mut result = FunctionReturnType::BigInt(match operation {
+        ElementaryFunc::Multiplication => BigInt::one(),
+        _ => BigInt::zero(),
+    });
+
+    let mut value = from;
+    while value <= to {
+        let term = with_binding(
+            &variable.name,
+            FunctionReturnType::BigInt(value.clone()),
+            env,
+            |env| execute_expression(params[0], env),
+        )?;
+        result = calculate_elementary_function(&operation, result, term, env)?;
+        value += 1;
+    }
+
+    Ok(result)
+}
+
+/// Returns the equation if the expression is one
+fn get_equation(expression: &Expression) -> Option<&Equation> {
+    match expression {
+        Expression::MathExpression(expr, _) => match expr.as_ref() {
+            MathExpression::Equation(equation) => Some(equation),
+            _ => None,
+        },
+        Expression::BracketedExpression(..) => None,
+    }
+}
+
+/// Returns all parameters of the function in order
+fn get_params(function: &HigherOrderFunction) -> Vec<&Expression> {
+    std::iter::once(&function.params.expression)
+        .chain(
+            function
+                .params
+                .expression_comma
+                .iter()
+                .flatten()
+                .map(|(_, expr)| expr),
+        )
+        .collect()
+}
+
+/// Evaluates the second parameter if the first parameter is true and the third parameter otherwise
+fn run_if(
+    function: &HigherOrderFunction,
+    env: &mut Environment,
+) -> Result<FunctionReturnType, FunctionError> {
+    let condition = execute_expression(&function.params.expression, env)?.get_bool()?;
+
+    let branches = function
+        .params
+        .expression_comma
+        .as_ref()
+        .expect("There should be enough parameters after parsing.");
+
+    let (_, branch) = if condition {
+        &branches[0]
+    } else {
+        &branches[1]
+    };
+
+    execute_expression(branch, env)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        environment::AngleMode,
+        error::{ParserError, ParserErrorType},
+        parser::Parser,
+        tokenizer::tokenize,
+    };
+
+    fn evaluate_in(input: &str, env: &mut Environment) -> Result<String, CLMathError> {
+        let tokens = tokenize(input.to_string(), env).unwrap();
+        let expression = Parser::parse(tokens, env).map_err(|mut errors| errors.remove(0))?;
+        Ok(execute(expression, env)?.map_or(String::new(), |result| result.to_string()))
+    }
+
+    fn evaluate(input: &str) -> String {
+        evaluate_in(input, &mut Environment::new()).unwrap()
+    }
+
+    #[test]
+    fn test_precedence() {
+        assert_eq!(evaluate("2 + 3 * 4"), "14");
+        assert_eq!(evaluate("2 * 3 + 4"), "10");
+        assert_eq!(evaluate("(2 + 3) * 4"), "20");
+        assert_eq!(evaluate("1 + 2 < 2 * 2"), "true");
+        assert_eq!(evaluate("2 + 10 % 4 * 3"), "8");
+    }
+
+    #[test]
+    fn test_left_associativity() {
+        assert_eq!(evaluate("10 - 4 - 3"), "3");
+        assert_eq!(evaluate("64 / 4 / 2"), "8");
+        assert_eq!(evaluate("Max(1, 2 + 3) - 1"), "4");
+    }
+
+    #[test]
+    fn test_assignment() {
+        let mut env = Environment::new();
+        assert_eq!(evaluate_in("x = 5", &mut env).unwrap(), "5");
+        assert_eq!(evaluate_in("x * 2", &mut env).unwrap(), "10");
+        assert_eq!(evaluate_in("Let(y, x + 1)", &mut env).unwrap(), "6");
+        assert_eq!(evaluate_in("x = x + y", &mut env).unwrap(), "11");
+        assert_eq!(evaluate_in("2x", &mut env).unwrap(), "22");
+    }
+
+    #[test]
+    fn test_symbolic_expressions() {
+        assert_eq!(evaluate("z + 1"), "z + 1");
+        assert_eq!(evaluate("2x + 3x"), "5x");
+        assert_eq!(evaluate("x * 1"), "x");
+        assert_eq!(evaluate("2(x + y) - 2x"), "2y");
+        assert_eq!(evaluate("-(x + y) + x"), "-y");
+        assert_eq!(evaluate("x * y / x"), "y");
+        assert_eq!(evaluate("Expand((x + 1)^2)"), "x^2 + 2x + 1");
+        assert_eq!(evaluate("Factor(x^2 - 1)"), "(x + 1)*(x - 1)");
+        assert_eq!(evaluate("Factor(2x^2 + 4x)"), "2x*(x + 2)");
+        assert_eq!(evaluate("Factor(2x + 4)"), "2*(x + 2)");
+        assert_eq!(evaluate("Factor(-6x^2 + 6)"), "-6*(x + 1)*(x - 1)");
+        assert_eq!(evaluate("Simplify((x^2 - 1) / (x - 1))"), "x + 1");
+        assert_eq!(evaluate("Subst(x^2 + y, x, 3)"), "y + 9");
+        assert_eq!(evaluate("Subst(Sin(x) + x/2, x, 0)"), "0");
+        assert_eq!(evaluate("Simplify(4)"), "4");
+
+        let mut env = Environment::new();
+        evaluate_in("p = x^2 - 2x", &mut env).unwrap();
+        evaluate_in("x = 10", &mut env).unwrap();
+        assert_eq!(evaluate_in("Subst(p, x, x)", &mut env).unwrap(), "80");
+        assert!(evaluate_in("x < y", &mut env).is_err());
+        assert!(evaluate_in("Fac(y)", &mut env).is_err());
+    }
+
+    #[test]
+    fn test_unary_functions() {
+        assert_eq!(evaluate("-5"), "-5");
+        assert_eq!(evaluate("3 * -2"), "-6");
+        assert_eq!(evaluate("-2 * 3 + 1"), "-5");
+        assert_eq!(evaluate("+4 - -(1 + 1)"), "6");
+        assert_eq!(evaluate("Max(-1, -3)"), "-1");
+        assert_eq!(evaluate("--3"), "3");
+    }
+
+    #[test]
+    fn test_exponentiation() {
+        assert_eq!(evaluate("2 ^ 3 ^ 2"), "512");
+        assert_eq!(evaluate("2 * 3 ^ 2"), "18");
+        assert_eq!(evaluate("-2 ^ 2"), "-4");
+        assert_eq!(evaluate("2 ^ -1"), "0.5");
+
+        let mut env = Environment::new();
+        for input in ["0 ^ -1", "0.0 ^ -1.5", "1 / (3.0 - 3)", "Subst(1/x, x, 0)"] {
+            assert!(matches!(
+                evaluate_in(input, &mut env).unwrap_err(),
+                CLMathError::Function(FunctionError {
+                    error_type: FunctionErrorType::DivisionByZero,
+                    ..
+                })
+            ));
+        }
+        assert_eq!(
+            evaluate("Pow(2, 8) + Sqrt(9) - Root(16, 4) * Cbrt(27)"),
+            "253"
+        );
+    }
+
+    #[test]
+    fn test_integer_arithmetic() {
+        assert_eq!(evaluate("2 ^ 64 + 1"), "18446744073709551617");
+        assert_eq!(evaluate("9007199254740993 - 1"), "9007199254740992");
+        assert_eq!(evaluate("Fac(13) / Fac(11)"), "156");
+        assert_eq!(evaluate("Fac(30) % 7 + EMod(-7, 3)"), "2");
+        assert_eq!(evaluate("2 ^ 100 > 2 ^ 99 * 2 - 1"), "true");
+        assert_eq!(evaluate("-Abs(-3) * Min(2, 5)"), "-6");
+        assert_eq!(evaluate("7 / 2 + 0.5"), "4");
+        assert!(evaluate_in("5 % 0", &mut Environment::new()).is_err());
+    }
+
+    #[test]
+    fn test_exact_mode() {
+        let mut env = Environment::new();
+        assert_eq!(
+            evaluate_in("0.1 + 0.2", &mut env).unwrap(),
+            "0.30000000000000004"
+        );
+        assert_eq!(evaluate_in("ToFraction(1 / 3)", &mut env).unwrap(), "1/3");
+
+        env.exact_mode = true;
+        assert_eq!(evaluate_in("0.1 + 0.2", &mut env).unwrap(), "3/10");
+        assert_eq!(evaluate_in("1 / 3 + 1 / 6", &mut env).unwrap(), "1/2");
+        assert_eq!(evaluate_in("(2 / 3) ^ -2 - 0.25", &mut env).unwrap(), "2");
+        assert_eq!(evaluate_in("2 ^ -1", &mut env).unwrap(), "1/2");
+        assert_eq!(
+            evaluate_in("(-2) ^ -3 + Pow(2, -2)", &mut env).unwrap(),
+            "1/8"
+        );
+        assert_eq!(evaluate_in("-Max(1 / 3, 0.3)", &mut env).unwrap(), "-1/3");
+        assert_eq!(evaluate_in("ToFloat(1 / 4)", &mut env).unwrap(), "0.25");
+        assert_eq!(evaluate_in("Sqrt(1 / 4)", &mut env).unwrap(), "0.5");
+        assert!(evaluate_in("1 / (0.5 - 1 / 2)", &mut env).is_err());
+    }
+
+    #[test]
+    fn test_complex_numbers() {
+        let mut env = Environment::new();
+        assert_eq!(
+            evaluate_in("(1 + 2i) * (3 - i)", &mut env).unwrap(),
+            "5 + 5i"
+        );
+        assert_eq!(evaluate_in("i ^ 2 + Abs(3 + 4i)", &mut env).unwrap(), "4");
+        assert_eq!(evaluate_in("Conj(2i) / i", &mut env).unwrap(), "-2");
+        assert_eq!(
+            evaluate_in("Re(Sqrt(-4i ^ 2)) + Im(-2 - 3i)", &mut env).unwrap(),
+            "-1"
+        );
+        assert!(evaluate_in("Sqrt(-4)", &mut env).is_err());
+        assert!(evaluate_in("i < 1", &mut env).is_err());
+
+        env.complex_mode = true;
+        env.angle_mode = AngleMode::Degrees;
+        assert_eq!(evaluate_in("Sqrt(-4)", &mut env).unwrap(), "2i");
+        assert_eq!(evaluate_in("Im(Ln(-1)) / Pi", &mut env).unwrap(), "1");
+        assert_eq!(evaluate_in("Arg(-1 - i)", &mut env).unwrap(), "-135");
+        assert_eq!(
+            evaluate_in("Polar(Rect(2, 90))", &mut env).unwrap(),
+            "[2, 90]"
+        );
+    }
+
+    #[test]
+    fn test_precision_mode() {
+        let mut env = Environment::new();
+        env.precision = Some(50);
+        assert_eq!(
+            evaluate_in("Pi", &mut env).unwrap(),
+            "3.1415926535897932384626433832795028841971693993751"
+        );
+        assert_eq!(
+            evaluate_in("Sqrt(2)", &mut env).unwrap(),
+            "1.4142135623730950488016887242096980785696718753769"
+        );
+        assert_eq!(evaluate_in("0.1 + 0.2", &mut env).unwrap(), "0.3");
+        assert_eq!(evaluate_in("Ln(Exp(10))", &mut env).unwrap(), "10");
+        assert_eq!(
+            evaluate_in("2 ^ 100", &mut env).unwrap(),
+            "1267650600228229401496703205376"
+        );
+
+        env.precision = Some(30);
+        assert_eq!(
+            evaluate_in("1 / 3", &mut env).unwrap(),
+            "0.333333333333333333333333333333"
+        );
+        assert_eq!(evaluate_in("2 ^ 0.5 * -2 ^ 0.5", &mut env).unwrap(), "-2");
+        assert_eq!(evaluate_in("ToFraction(0.75)", &mut env).unwrap(), "3/4");
+        assert!(evaluate_in("Ln(0)", &mut env).is_err());
+        assert_eq!(evaluate_in("-Inf < 0", &mut env).unwrap(), "true");
+
+        env.angle_mode = AngleMode::Degrees;
+        assert_eq!(evaluate_in("Sin(30)", &mut env).unwrap(), "0.5");
+        assert_eq!(evaluate_in("Atan(1)", &mut env).unwrap(), "45");
+    }
+
+    #[test]
+    fn test_units() {
+        assert_eq!(evaluate("100 km/h to m/s"), "27.77777777777778 m/s");
+        assert_eq!(evaluate("9.81 m/s^2 * 2 kg to N"), "19.62 N");
+        assert_eq!(evaluate("1 km + 500 m"), "1.5 km");
+        assert_eq!(evaluate("1 km / 2 m"), "500");
+        assert_eq!(evaluate("Sqrt(9 m^2) < 4 m"), "true");
+        assert_eq!(evaluate("3 s^-1"), "3 s^-1");
+
+        let mut env = Environment::new();
+        assert!(evaluate_in("5 m + 3 s", &mut env).is_err());
+        assert!(evaluate_in("1 kg to m", &mut env).is_err());
+        assert_eq!(evaluate_in("5 N m to J", &mut env).unwrap(), "5 J");
+        assert_eq!(evaluate_in("1 N m / 2 s", &mut env).unwrap(), "0.5 N*m/s");
+
+        // Units after a number, a conversion or another unit are not shadowed by variables
+        evaluate_in("m = 2", &mut env).unwrap();
+        evaluate_in("s = 1", &mut env).unwrap();
+        evaluate_in("h = 2", &mut env).unwrap();
+        assert_eq!(evaluate_in("3 m", &mut env).unwrap(), "3 m");
+        assert_eq!(evaluate_in("3 km/h", &mut env).unwrap(), "3 km/h");
+        assert_eq!(
+            evaluate_in("100 km/h to m/s", &mut env).unwrap(),
+            "27.77777777777778 m/s"
+        );
+        assert_eq!(evaluate_in("3 * m + s", &mut env).unwrap(), "7");
+
+        // Names of units are only units after a number, a conversion or another unit, otherwise they are free variables
+        assert_eq!(evaluate("t + 1"), "t + 1");
+        assert_eq!(evaluate("Expand((t + 1)^2)"), "t^2 + 2*t + 1");
+        assert_eq!(evaluate("Diff(s^2, s)"), "2*s");
+        assert_eq!(evaluate("3 m^2/s to m^2/h"), "10800 m^2/h");
+    }
+
+    #[test]
+    fn test_vectors_and_matrices() {
+        assert_eq!(evaluate("[1, 2] + [3, 4] * 2"), "[7, 10]");
+        assert_eq!(
+            evaluate("[[1, 2], [3, 4]] * [[5, 6], [7, 8]]"),
+            "[[19, 22], [43, 50]]"
+        );
+        assert_eq!(evaluate("[[1, 1], [1, 0]] ^ 10"), "[[89, 55], [55, 34]]");
+        assert_eq!(evaluate("Det([[1, 2], [3, 4]])"), "-2");
+        assert_eq!(evaluate("Inv([[1, 2], [3, 4]])"), "[[-2, 1], [1.5, -0.5]]");
+        assert_eq!(evaluate("[[1, 2], [3, 4]] ^ -1"), "[[-2, 1], [1.5, -0.5]]");
+        assert_eq!(evaluate("Det([[x, 1], [1, 1]])"), "x - 1");
+        assert_eq!(evaluate("Det([[a, b], [c, q]])"), "a*q - b*c");
+        assert_eq!(
+            evaluate("Inv([[x, 1], [1, 1]])"),
+            "[[1/(x - 1), -1/(x - 1)], [-1/(x - 1), x/(x - 1)]]"
+        );
+        assert_eq!(evaluate("Solve([[2, 1], [1, 3]], [3, 5])"), "[0.8, 1.4]");
+        assert_eq!(evaluate("Cross([1, 0, 0], [0, 1, 0])"), "[0, 0, 1]");
+        assert_eq!(evaluate("Dot([1, 2, 3], [4, 5, 6])"), "32");
+        assert_eq!(
+            evaluate("Transpose([[1, 2, 3], [4, 5, 6]])"),
+            "[[1, 4], [2, 5], [3, 6]]"
+        );
+        assert_eq!(
+            evaluate("Trace([[1, 2], [3, 4]]) + Rank([[1, 2], [2, 4]])"),
+            "6"
+        );
+
+        let mut env = Environment::new();
+        assert!(evaluate_in("[1, 2] + [1, 2, 3]", &mut env).is_err());
+        assert!(evaluate_in("Inv([[1, 2], [2, 4]])", &mut env).is_err());
+        assert!(evaluate_in("Det([[1, 2, 3], [4, 5, 6]])", &mut env).is_err());
+        assert!(evaluate_in("Rank([[x, 1], [1, 1]])", &mut env).is_err());
+        assert!(evaluate_in("Inv([[x, x], [1, 1]])", &mut env).is_err());
+        assert!(evaluate_in("[[2]] ^ (10 ^ 30)", &mut env).is_err());
+        assert!(evaluate_in("[[2]] ^ (2 ^ 40)", &mut env).is_err());
+        env.exact_mode = true;
+        assert_eq!(
+            evaluate_in("Inv([[1, 2], [3, 4]])", &mut env).unwrap(),
+            "[[-2, 1], [3/2, -1/2]]"
+        );
+    }
+
+    #[test]
+    fn test_derivatives() {
+        assert_eq!(evaluate("Diff(x^3/3 + 2x, x)"), "x^2 + 2");
+        assert_eq!(evaluate("Diff(x * Sin(x), x)"), "x*Cos(x) + Sin(x)");
+        assert_eq!(evaluate("Diff(Sqrt(x), x)"), "1/(2Sqrt(x))");
+        assert_eq!(evaluate("Diff(Exp(x^2), x)"), "2x*Exp(x^2)");
+        assert_eq!(evaluate("Diff(Diff(x^3, x), x)"), "6x");
+        assert_eq!(evaluate("Diff(x / 2, x)"), "0.5");
+
+        let mut env = Environment::new();
+        evaluate_in("f(t) = t^2 + 1", &mut env).unwrap();
+        evaluate_in("x = 5", &mut env).unwrap();
+        assert_eq!(evaluate_in("Diff(3f(x), x)", &mut env).unwrap(), "6x");
+        assert_eq!(evaluate_in("Diff(x^2, x) + x", &mut env).unwrap(), "2x + 5");
+        assert!(evaluate_in("Diff(Fac(x), x)", &mut env).is_err());
+        assert!(evaluate_in("Diff(x^2, 3)", &mut env).is_err());
+        env.angle_mode = AngleMode::Degrees;
+        assert_eq!(
+            evaluate_in("Diff(Sin(x), x)", &mut env).unwrap(),
+            "Pi*Cos(x)/180"
+        );
+    }
+
+    #[test]
+    fn test_equations() {
+        assert_eq!(evaluate("Solve(x^2 = 4, x)"), "[-2, 2]");
+        assert_eq!(evaluate("Solve(x^2 = 4, x, 0, 10)"), "[2]");
+        assert_eq!(evaluate("Solve((x - 1)^2 = 0, x)"), "[1]");
+        assert_eq!(evaluate("Solve(x^2 + 1 = 0, x)"), "[]");
+        assert_eq!(evaluate("Solve(Ln(x) = 1, x)"), "[2.718281828459045]");
+        assert_eq!(evaluate("FindRoot(Cos(x) = x, x, 1)"), "0.7390851332151607");
+        assert_eq!(evaluate("FindRoot(x^2 - 9, x, -1)"), "-3");
+
+        let mut env = Environment::new();
+        evaluate_in("f(t) = t^3 - t", &mut env).unwrap();
+        evaluate_in("x = 5", &mut env).unwrap();
+        assert_eq!(
+            evaluate_in("Solve(f(x) = 0, x)", &mut env).unwrap(),
+            "[-1, 0, 1]"
+        );
+        assert_eq!(evaluate_in("x", &mut env).unwrap(), "5");
+        assert!(evaluate_in("Solve(x^2 = y, x)", &mut env).is_err());
+        assert!(evaluate_in("Solve(x^2 = 4, x, 1)", &mut env).is_err());
+        assert!(evaluate_in("Solve(x = x, x)", &mut env).is_err());
+        assert!(evaluate_in("FindRoot(x^2 + 1, x, 0)", &mut env).is_err());
+        assert!(evaluate_in("x^2 = 4", &mut env).is_err());
+        assert!(evaluate_in("2 + (x = 4)", &mut env).is_err());
+        assert!(evaluate_in("FindRoot(1 / (x - 3), x, 2.5)", &mut env).is_err());
+
+        for input in [
+            "Solve(x - x = 0, x)",
+            "Solve(Ln(x) = Ln(x), x)",
+            "Solve(Sqrt(x) * 0 = 0, x, -1, 1)",
+        ] {
+            assert!(matches!(
+                evaluate_in(input, &mut env).unwrap_err(),
+                CLMathError::Function(FunctionError {
+                    error_type: FunctionErrorType::InfinitelyManySolutions,
+                    ..
+                })
+            ));
+        }
+
+        let err = evaluate_in("Solve(z = 1, y)", &mut env).unwrap_err();
+        assert!(
+            matches!(err, CLMathError::Function(FunctionError { error, .. }) if error == "The equation does not depend on y, so it cannot be solved for it.")
+        );
+    }
+
+    #[test]
+    fn test_integrals_sums_and_products() {
+        assert_eq!(evaluate("Integrate(x^2, x, 0, 1)"), "0.3333333333333333");
+        assert_eq!(evaluate("Integrate(Sin(x), x, 0, Pi)"), "2");
+        assert_eq!(evaluate("Integrate(1/x^2, x, 1, Inf)"), "1");
+        assert_eq!(evaluate("Sum(k^2, k, 1, 10)"), "385");
+        assert_eq!(evaluate("Product(k, k, 1, 20)"), "2432902008176640000");
+        assert_eq!(evaluate("Sum(k, k, 5, 1)"), "0");
+        assert_eq!(evaluate("Product(k, k, 5, 1)"), "1");
+        assert_eq!(evaluate("Sum(k y, k, 1, 3)"), "6y");
+
+        let mut env = Environment::new();
+        evaluate_in("k = 3", &mut env).unwrap();
+        assert_eq!(evaluate_in("Sum(k, k, 1, 4) + k", &mut env).unwrap(), "13");
+        assert!(evaluate_in("Sum(k, k, 1, 0.5)", &mut env).is_err());
+        assert!(evaluate_in("Sum(k, 2, 1, 3)", &mut env).is_err());
+        assert!(evaluate_in("Integrate(1/x, x, -1, 1)", &mut env).is_err());
+        assert!(evaluate_in("Integrate(x y, x, 0, 1)", &mut env).is_err());
+    }
+
+    #[test]
+    fn test_numeric_derivatives_and_limits() {
+        assert_eq!(evaluate("NDiff(x^2, x, 3)"), "6");
+        assert_eq!(evaluate("NDiff(Sin(x), x, 0)"), "1");
+        assert_eq!(evaluate("Limit(Sin(x)/x, x, 0)"), "1");
+        assert_eq!(evaluate("Limit((x^2 - 4)/(x - 2), x, 2)"), "4");
+        assert_eq!(evaluate("Limit(1/x, x, 0, 1)"), "inf");
+        assert_eq!(evaluate("Limit(Ln(x), x, 0, 1)"), "-inf");
+        assert_eq!(evaluate("Limit(Sqrt(x), x, 0)"), "0");
+        assert_eq!(evaluate("Limit(1/x, x, Inf)"), "0");
+
+        let mut env = Environment::new();
+        evaluate_in("x = 5", &mut env).unwrap();
+        assert_eq!(evaluate_in("NDiff(x^3, x, 1) + x", &mut env).unwrap(), "8");
+        assert!(evaluate_in("NDiff(Sqrt(x), x, 0)", &mut env).is_err());
+        assert!(evaluate_in("NDiff(Abs(x), x, 0)", &mut env).is_err());
+        assert_eq!(evaluate_in("NDiff(Abs(x), x, -2)", &mut env).unwrap(), "-1");
+        assert!(evaluate_in("Limit(1/x, x, 0)", &mut env).is_err());
+        assert!(evaluate_in("Limit(Sin(1/x), x, 0, 1)", &mut env).is_err());
+        assert!(evaluate_in("Limit(x, x, 0, 1, 2)", &mut env).is_err());
+    }
+
+    #[test]
+    fn test_angle_mode() {
+        let mut env = Environment::new();
+        assert_eq!(evaluate_in("Cos(0) + Sin(0)", &mut env).unwrap(), "1");
+        env.angle_mode = AngleMode::Degrees;
+        assert_eq!(evaluate_in("Sin(270) * Cos(360)", &mut env).unwrap(), "-1");
+        assert_eq!(evaluate_in("Atan2(1, -1)", &mut env).unwrap(), "135");
+        assert_eq!(evaluate_in("Acos(0)", &mut env).unwrap(), "90");
+        env.angle_mode = AngleMode::Gradians;
+        assert_eq!(evaluate_in("Asin(1)", &mut env).unwrap(), "100");
+        assert!(evaluate_in("Asin(2)", &mut env).is_err());
+    }
+
+    #[test]
+    fn test_logarithm_and_exponential() {
+        assert_eq!(evaluate("Log10(100) + Log2(8)"), "5");
+        assert_eq!(evaluate("Log(8, 2) * Ln(Exp(2))"), "6");
+        assert_eq!(evaluate("Ln1p(0) + Expm1(0)"), "0");
+        assert!(evaluate_in("Ln(0)", &mut Environment::new()).is_err());
+    }
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(evaluate("2Pi - Tau"), "0");
+        assert_eq!(evaluate("Ln(E)"), "1");
+        assert_eq!(evaluate("Phi ^ 2 - Phi"), "1");
+        assert_eq!(evaluate("-Inf < 0"), "true");
+        for input in ["Inf - Inf", "Inf * 0", "Inf / Inf", "Inf % 2", "Sin(Inf)"] {
+            assert!(evaluate_in(input, &mut Environment::new()).is_err());
+        }
+        assert_eq!(evaluate("Gravity * 2"), "19.6133 m/s^2");
+        assert_eq!(evaluate("2 kg * Gravity to N"), "19.6133 N");
+        assert_eq!(evaluate("SpeedOfLight to km/h"), "1079252848.8 km/h");
+        assert_eq!(evaluate("ElementaryCharge * 1 V to eV"), "1 eV");
+        assert_eq!(evaluate("Pi(1 + 1) / Pi"), "2");
+        assert_eq!(evaluate("Pi*x"), "Pi*x");
+        assert_eq!(evaluate("Diff(E^x, x)"), "E^x");
+        assert_eq!(evaluate("Diff(Pi*x, x)"), "Pi");
+        assert_eq!(evaluate("x + Sin(Pi/2)"), "x + 1");
+
+        // Function calls next to symbolic expressions are only evaluated once, otherwise every level of the recursion
+        // would evaluate the next level twice
+        let mut env = Environment::new();
+        evaluate_in("f(n) = If(n < 1, Pi, f(n - 1)) + x", &mut env).unwrap();
+        assert_eq!(
+            evaluate_in("f(60)", &mut env).unwrap(),
+            "61x + 3.141592653589793"
+        );
+    }
+
+    #[test]
+    fn test_user_functions() {
+        let mut env = Environment::new();
+        assert_eq!(evaluate_in("f(x, y) = x^2 + y", &mut env).unwrap(), "");
+        assert_eq!(evaluate_in("f(2, 3)", &mut env).unwrap(), "7");
+        assert_eq!(evaluate_in("x = 10", &mut env).unwrap(), "10");
+        assert_eq!(evaluate_in("2f(1, x) + x", &mut env).unwrap(), "32");
+        assert_eq!(evaluate_in("g(x) = f(x, x) * a", &mut env).unwrap(), "");
+        assert_eq!(evaluate_in("g(1)", &mut env).unwrap(), "2a");
+
+        let err = evaluate_in("f(1)", &mut env).unwrap_err();
+        assert!(matches!(
+            err,
+            CLMathError::Parser(ParserError {
+                error_type: ParserErrorType::InvalidNumberOfParams {
+                    expected: 2,
+                    found: 1,
+                    ..
+                },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_recursion() {
+        let mut env = Environment::new();
+        evaluate_in("fac(n) = If(n < 1, 1, n * fac(n - 1))", &mut env).unwrap();
+        assert_eq!(evaluate_in("fac(5)", &mut env).unwrap(), "120");
+
+        evaluate_in("loop(n) = loop(n + 1)", &mut env).unwrap();
+        let err = evaluate_in("loop(0)", &mut env).unwrap_err();
+        assert!(matches!(
+            err,
+            CLMathError::Function(FunctionError {
+                error_type: FunctionErrorType::RecursionLimitExceeded,
+                ..
+            })
+        ));
+
+        env.recursion_limit = 3;
+        assert!(evaluate_in("fac(3)", &mut env).is_err());
+
+        // Only function calls count towards the limit, not the variables bound by sums
+        assert_eq!(
+            evaluate_in("Sum(Sum(Sum(fac(1), a, 1, 2), b, 1, 2), c, 1, 2)", &mut env).unwrap(),
+            "8"
+        );
+
+        // Deeply nested bodies continue on new stack segments instead of overflowing the stack
+        env.recursion_limit = 1000;
+        let brackets = 150;
+        evaluate_in(
+            &format!(
+                "deep(n) = If(n < 1, 0, {}deep(n - 1){} + 1)",
+                "(".repeat(brackets),
+                ")".repeat(brackets)
+            ),
+            &mut env,
+        )
+        .unwrap();
+        assert_eq!(evaluate_in("deep(999)", &mut env).unwrap(), "999");
+    }
+
+    #[test]
+    fn test_lambdas() {
+        let mut env = Environment::new();
+        assert_eq!(
+            evaluate_in("double = x -> x * 2", &mut env).unwrap(),
+            "x -> x*2"
+        );
+        assert_eq!(evaluate_in("double(4) + 1", &mut env).unwrap(), "9");
+        assert_eq!(
+            evaluate_in("Apply((a, b) -> a - b, 5, 3)", &mut env).unwrap(),
+            "2"
+        );
+        assert!(evaluate_in("Apply(double, 1, 2)", &mut env).is_err());
+
+        evaluate_in("adder(n) = x -> x + n", &mut env).unwrap();
+        assert_eq!(
+            evaluate_in("addThree = adder(3)", &mut env).unwrap(),
+            "x -> x + n"
+        );
+        assert_eq!(evaluate_in("addThree(4)", &mut env).unwrap(), "7");
+        assert_eq!(
+            evaluate_in("(a, b) -> -a^2 + [1.5, b] to km", &mut env).unwrap(),
+            "(a, b) -> -a^2 + [1.5, b] to km"
+        );
+        assert_eq!(evaluate_in("Sin", &mut env).unwrap(), "x -> Sin(x)");
+    }
+
+    #[test]
+    fn test_list_functions() {
+        let mut env = Environment::new();
+        evaluate_in("numbers = List(1, 2, 3, 4)", &mut env).unwrap();
+        assert_eq!(
+            evaluate_in("Map(numbers, x -> x ^ 2)", &mut env).unwrap(),
+            "[1, 4, 9, 16]"
+        );
+        assert_eq!(
+            evaluate_in("Filter(numbers, x -> x > 2)", &mut env).unwrap(),
+            "[3, 4]"
+        );
+        assert_eq!(
+            evaluate_in("Fold(numbers, 0, (acc, x) -> acc + x)", &mut env).unwrap(),
+            "10"
+        );
+
+        evaluate_in("square(x) = x * x", &mut env).unwrap();
+        assert_eq!(
+            evaluate_in("Map(numbers, square)", &mut env).unwrap(),
+            "[1, 4, 9, 16]"
+        );
+        assert!(evaluate_in("Map(numbers, 1)", &mut env).is_err());
+
+        // Built-in functions can be passed without brackets
+        assert_eq!(
+            evaluate_in("Map(numbers, Sqrt)", &mut env).unwrap(),
+            "[1, 1.4142135623730951, 1.7320508075688772, 2]"
+        );
+        assert_eq!(evaluate_in("Fold(numbers, 0, Max)", &mut env).unwrap(), "4");
+        assert!(evaluate_in("Map(numbers, Diff)", &mut env).is_err());
+
+        // A function with the wrong number of parameters is reported instead of the function it is passed to
+        match evaluate_in("Map(numbers, (a, b) -> a)", &mut env) {
+            Err(CLMathError::Function(err)) => assert_eq!(
+                err.error,
+                "'Map' calls the function '(a, b) -> a' with 1 parameter(s), but it takes 2."
+            ),
+            result => panic!("Expected an error, but got {:?}", result),
+        }
     }
 }
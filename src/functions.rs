@@ -1,32 +1,69 @@
 use std::{
     fmt::{Debug, Display},
-    ops::{Add, Div, Mul, Rem, Sub},
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
 };
 
+use crate::decimal;
+use crate::environment::{AngleMode, Closure};
 use crate::error::{FunctionError, FunctionErrorType};
+use crate::symbolic::Symbolic;
+use crate::units::{Quantity, Unit};
+use bigdecimal::BigDecimal;
+use num_bigint::BigInt;
+use num_complex::Complex64;
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
 use phf_macros::phf_map;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Func {
     Elementary(ElementaryFunc),
     HigherOrder(HigherOrderFunc),
+    Unary(UnaryFunc),
+    User(String),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum FunctionReturnType {
     F64(f64),
     Bool(bool),
-    U32(u32),
-    Str(String),
+    BigInt(BigInt),
+    Rational(BigRational),
+    Decimal(BigDecimal),
+    Complex(Complex64),
+    Quantity(Quantity),
+    List(Vec<FunctionReturnType>),
+    Lambda(Closure),
+    Symbolic(Symbolic),
 }
 
 impl Display for FunctionReturnType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
             FunctionReturnType::F64(num) => num.to_string(),
-            FunctionReturnType::U32(num) => num.to_string(),
+            FunctionReturnType::BigInt(num) => num.to_string(),
+            FunctionReturnType::Rational(num) => num.to_string(),
+            FunctionReturnType::Decimal(num) => decimal::format(num),
+            FunctionReturnType::Complex(num) if num.re == 0.0 => format_imaginary(num.im),
+            FunctionReturnType::Complex(num) if num.im.is_sign_negative() => {
+                format!("{} - {}", num.re, format_imaginary(-num.im))
+            }
+            FunctionReturnType::Complex(num) => {
+                format!("{} + {}", num.re, format_imaginary(num.im))
+            }
+            FunctionReturnType::Quantity(quantity) => quantity.to_string(),
             FunctionReturnType::Bool(val) => val.to_string(),
-            FunctionReturnType::Str(s) => s.into(),
+            FunctionReturnType::List(values) => format!(
+                "[{}]",
+                values
+                    .iter()
+                    .map(|value| value.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            FunctionReturnType::Lambda(closure) => closure.to_string(),
+            FunctionReturnType::Symbolic(expression) => expression.to_string(),
         };
 
         write!(f, "{s}")
@@ -34,45 +71,141 @@ impl Display for FunctionReturnType {
 }
 
 impl FunctionReturnType {
+    /// Returns the name of the type of the value for error messages
+    pub fn get_type_name(&self) -> &'static str {
+        match self {
+            FunctionReturnType::F64(_) => "f64",
+            FunctionReturnType::Bool(_) => "boolean",
+            FunctionReturnType::BigInt(_) => "integer",
+            FunctionReturnType::Rational(_) => "fraction",
+            FunctionReturnType::Decimal(_) => "decimal",
+            FunctionReturnType::Complex(_) => "complex number",
+            FunctionReturnType::List(_) => "list",
+            FunctionReturnType::Quantity(_) => "quantity",
+            FunctionReturnType::Lambda(_) => "function",
+            FunctionReturnType::Symbolic(_) => "expression",
+        }
+    }
+
+    fn create_type_error(self, expected: &str) -> FunctionError {
+        FunctionError::new(
+            format!(
+                "Expected a {} as a parameter but got a {} {}",
+                expected,
+                self.get_type_name(),
+                self
+            ),
+            FunctionErrorType::InvalidParameterType,
+        )
+    }
+
+    /// Returns the value as a 64bit floating point number. Integers, fractions and decimals are converted if they fit into its range.
     pub fn get_f64(self) -> Result<f64, FunctionError> {
         match self {
             FunctionReturnType::F64(num) => Ok(num),
-            FunctionReturnType::U32(num) => Err(FunctionError::new(
-                format!("Expected a f64 as a parameter but got a u32 {:?}", num),
-                FunctionErrorType::InvalidParameterType,
-            )),
-            FunctionReturnType::Bool(val) => Err(FunctionError::new(
-                format!("Expected a f64 as a parameter but got a boolean {:?}", val),
-                FunctionErrorType::InvalidParameterType,
-            )),
-            FunctionReturnType::Str(s) => Err(FunctionError::new(
-                format!("Expected a f64 as a parameter but got a string {:?}", s),
-                FunctionErrorType::InvalidParameterType,
-            )),
+            FunctionReturnType::BigInt(num) => integer_to_f64(&num),
+            FunctionReturnType::Rational(num) => rational_to_f64(&num),
+            FunctionReturnType::Decimal(num) => rational_to_f64(&decimal::to_rational(&num)),
+            other => Err(other.create_type_error("f64")),
+        }
+    }
+
+    /// Returns the value as a fraction if it is exact, i.e. an integer or a fraction
+    pub fn get_exact(&self) -> Option<BigRational> {
+        match self {
+            FunctionReturnType::BigInt(num) => Some(BigRational::from(num.clone())),
+            FunctionReturnType::Rational(num) => Some(num.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a decimal number with the provided number of significant digits.
+    /// Integers are exact, fractions are rounded and floating point numbers keep their shortest representation.
+    pub fn get_decimal(self, precision: u64) -> Result<BigDecimal, FunctionError> {
+        match self {
+            FunctionReturnType::Decimal(num) => Ok(num),
+            FunctionReturnType::BigInt(num) => Ok(BigDecimal::from(num)),
+            FunctionReturnType::Rational(num) => Ok(decimal::from_rational(&num, precision)),
+            FunctionReturnType::F64(num) => decimal::from_f64(num).ok_or_else(|| {
+                FunctionError::new(
+                    format!(
+                        "Only finite numbers can be calculated with a precision. You tried to use {}.",
+                        num
+                    ),
+                    FunctionErrorType::InvalidParameterType,
+                )
+            }),
+            other => Err(other.create_type_error("decimal")),
+        }
+    }
+
+    /// Returns false if the value is an infinite or NaN floating point number, which cannot be converted to a decimal
+    pub fn is_finite(&self) -> bool {
+        match self {
+            FunctionReturnType::F64(num) => num.is_finite(),
+            _ => true,
+        }
+    }
+
+    pub fn get_bool(self) -> Result<bool, FunctionError> {
+        match self {
+            FunctionReturnType::Bool(val) => Ok(val),
+            other => Err(other.create_type_error("boolean")),
         }
     }
 
     pub fn get_u32(self) -> Result<u32, FunctionError> {
         match self {
-            FunctionReturnType::F64(num) => {
-                if num.fract() == 0.0 {
-                    Ok(num as u32)
-                } else {
-                    Err(FunctionError::new(
-                        format!("Expected a u32 as a parameter but got a f64 {:?}", num),
-                        FunctionErrorType::InvalidParameterType,
-                    ))
-                }
+            FunctionReturnType::F64(num)
+                if num.fract() == 0.0 && (0.0..=u32::MAX.into()).contains(&num) =>
+            {
+                Ok(num as u32)
             }
-            FunctionReturnType::U32(num) => Ok(num),
-            FunctionReturnType::Bool(val) => Err(FunctionError::new(
-                format!("Expected a u32 as a parameter but got a boolean {:?}", val),
-                FunctionErrorType::InvalidParameterType,
-            )),
-            FunctionReturnType::Str(s) => Err(FunctionError::new(
-                format!("Expected a u32 as a parameter but got a string {:?}", s),
-                FunctionErrorType::InvalidParameterType,
-            )),
+            FunctionReturnType::BigInt(ref num) if num.to_u32().is_some() => {
+                Ok(num.to_u32().unwrap())
+            }
+            FunctionReturnType::Decimal(ref num) if num.is_integer() && num.to_u32().is_some() => {
+                Ok(num.to_u32().unwrap())
+            }
+            other => Err(other.create_type_error("u32")),
+        }
+    }
+
+    /// Returns the value as a complex number. Real numbers have an imaginary part of zero.
+    pub fn get_complex(self) -> Result<Complex64, FunctionError> {
+        match self {
+            FunctionReturnType::Complex(num) => Ok(num),
+            FunctionReturnType::F64(_)
+            | FunctionReturnType::BigInt(_)
+            | FunctionReturnType::Rational(_)
+            | FunctionReturnType::Decimal(_) => Ok(Complex64::from(self.get_f64()?)),
+            other => Err(other.create_type_error("complex number")),
+        }
+    }
+
+    /// Returns the value as a quantity. Real numbers are quantities without a unit.
+    pub fn get_quantity(self) -> Result<Quantity, FunctionError> {
+        match self {
+            FunctionReturnType::Quantity(quantity) => Ok(quantity),
+            FunctionReturnType::F64(_)
+            | FunctionReturnType::BigInt(_)
+            | FunctionReturnType::Rational(_)
+            | FunctionReturnType::Decimal(_) => Ok(Quantity::new(self.get_f64()?, Unit::default())),
+            other => Err(other.create_type_error("quantity")),
+        }
+    }
+
+    pub fn get_list(self) -> Result<Vec<FunctionReturnType>, FunctionError> {
+        match self {
+            FunctionReturnType::List(values) => Ok(values),
+            other => Err(other.create_type_error("list")),
+        }
+    }
+
+    pub fn get_lambda(self) -> Result<Closure, FunctionError> {
+        match self {
+            FunctionReturnType::Lambda(closure) => Ok(closure),
+            other => Err(other.create_type_error("function")),
         }
     }
 }
@@ -85,6 +218,7 @@ pub const ELEMENTARY_FUNC_KEYWORDS: phf::Map<char, ElementaryFunc> = phf_map! {
     '-' => ElementaryFunc::Subtraction,
     '<' => ElementaryFunc::LessThan,
     '>' => ElementaryFunc::GreaterThan,
+    '^' => ElementaryFunc::Exponentiation,
 };
 
 #[derive(Debug, PartialEq, Clone)]
@@ -96,6 +230,87 @@ pub enum ElementaryFunc {
     Subtraction,
     LessThan,
     GreaterThan,
+    Exponentiation,
+    /// Converts a quantity to another unit, written as `to`, e.g. `100 km/h to m/s`
+    Conversion,
+}
+
+/// The keyword of the conversion of units, it is the only elementary function that is a word
+pub const CONVERSION_KEYWORD: &str = "to";
+
+pub const UNARY_FUNC_KEYWORDS: phf::Map<char, UnaryFunc> = phf_map! {
+    '-' => UnaryFunc::Negation,
+    '+' => UnaryFunc::Identity,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum UnaryFunc {
+    Negation,
+    Identity,
+}
+
+impl UnaryFunc {
+    /// Returns the sign with which the function is written
+    pub fn get_keyword(&self) -> &'static str {
+        match self {
+            UnaryFunc::Negation => "-",
+            UnaryFunc::Identity => "+",
+        }
+    }
+
+    /// Returns the binding strength of the prefix function. It binds tighter than multiplication but looser than exponentiation.
+    pub fn get_precedence(&self) -> u8 {
+        4
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Associativity {
+    Left,
+    Right,
+}
+
+impl ElementaryFunc {
+    /// Returns the operator with which the function is written, e.g. `+` or `to`
+    pub fn get_keyword(&self) -> &'static str {
+        match self {
+            ElementaryFunc::Addition => "+",
+            ElementaryFunc::Division => "/",
+            ElementaryFunc::Modulo => "%",
+            ElementaryFunc::Multiplication => "*",
+            ElementaryFunc::Subtraction => "-",
+            ElementaryFunc::LessThan => "<",
+            ElementaryFunc::GreaterThan => ">",
+            ElementaryFunc::Exponentiation => "^",
+            ElementaryFunc::Conversion => CONVERSION_KEYWORD,
+        }
+    }
+
+    /// Returns the binding strength of the function. Functions with a higher precedence are evaluated first.
+    pub fn get_precedence(&self) -> u8 {
+        match self {
+            ElementaryFunc::Conversion => 0,
+            ElementaryFunc::LessThan | ElementaryFunc::GreaterThan => 1,
+            ElementaryFunc::Addition | ElementaryFunc::Subtraction => 2,
+            ElementaryFunc::Multiplication | ElementaryFunc::Division | ElementaryFunc::Modulo => 3,
+            ElementaryFunc::Exponentiation => 5,
+        }
+    }
+
+    /// Returns the side from which chained functions of the same precedence are grouped
+    pub fn get_associativity(&self) -> Associativity {
+        match self {
+            ElementaryFunc::Addition
+            | ElementaryFunc::Subtraction
+            | ElementaryFunc::Multiplication
+            | ElementaryFunc::Division
+            | ElementaryFunc::Modulo
+            | ElementaryFunc::LessThan
+            | ElementaryFunc::GreaterThan
+            | ElementaryFunc::Conversion => Associativity::Left,
+            ElementaryFunc::Exponentiation => Associativity::Right,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -105,18 +320,171 @@ pub enum HigherOrderFunc {
     Minimum(u8),
     Maximum(u8),
     Absolute(u8),
+    Let(u8),
+    Power(u8),
+    SquareRoot(u8),
+    CubeRoot(u8),
+    Root(u8),
+    Sine(u8),
+    Cosine(u8),
+    Tangent(u8),
+    Arcsine(u8),
+    Arccosine(u8),
+    Arctangent(u8),
+    Arctangent2(u8),
+    HyperbolicSine(u8),
+    HyperbolicCosine(u8),
+    HyperbolicTangent(u8),
+    AreaHyperbolicSine(u8),
+    AreaHyperbolicCosine(u8),
+    AreaHyperbolicTangent(u8),
+    NaturalLogarithm(u8),
+    DecimalLogarithm(u8),
+    BinaryLogarithm(u8),
+    Logarithm(u8),
+    Exponential(u8),
+    ExponentialMinusOne(u8),
+    NaturalLogarithmOnePlus(u8),
+    ToFloat(u8),
+    ToFraction(u8),
+    RealPart(u8),
+    ImaginaryPart(u8),
+    Conjugate(u8),
+    Argument(u8),
+    ToPolar(u8),
+    FromPolar(u8),
+    If(u8),
+    List(u8),
+    Map(u8),
+    Filter(u8),
+    Fold(u8),
+    Apply(u8),
+    Dot(u8),
+    Cross(u8),
+    Determinant(u8),
+    Inverse(u8),
+    Transpose(u8),
+    Trace(u8),
+    Rank(u8),
+    Solve(u8),
+    Derivative(u8),
+    Simplify(u8),
+    Expand(u8),
+    Factor(u8),
+    Substitution(u8),
+    FindRoot(u8),
+    Integral(u8),
+    Sum(u8),
+    Product(u8),
+    NumericDerivative(u8),
+    Limit(u8),
 }
 
 impl HigherOrderFunc {
+    /// Returns the keyword with which the function is called
+    pub fn get_keyword(&self) -> &'static str {
+        HIGHER_ORDER_FUNC_KEYWORDS
+            .entries()
+            .find(|(_, func)| *func == self)
+            .map(|(keyword, _)| *keyword)
+            .expect("Every higher order function has a keyword.")
+    }
+
     pub fn get_param_count(&self) -> &u8 {
         match self {
             HigherOrderFunc::Absolute(num_of_params)
             | HigherOrderFunc::Factorial(num_of_params)
             | HigherOrderFunc::Maximum(num_of_params)
             | HigherOrderFunc::Minimum(num_of_params)
-            | HigherOrderFunc::EucleadianModulo(num_of_params) => num_of_params,
+            | HigherOrderFunc::EucleadianModulo(num_of_params)
+            | HigherOrderFunc::Let(num_of_params)
+            | HigherOrderFunc::Power(num_of_params)
+            | HigherOrderFunc::SquareRoot(num_of_params)
+            | HigherOrderFunc::CubeRoot(num_of_params)
+            | HigherOrderFunc::Root(num_of_params)
+            | HigherOrderFunc::Sine(num_of_params)
+            | HigherOrderFunc::Cosine(num_of_params)
+            | HigherOrderFunc::Tangent(num_of_params)
+            | HigherOrderFunc::Arcsine(num_of_params)
+            | HigherOrderFunc::Arccosine(num_of_params)
+            | HigherOrderFunc::Arctangent(num_of_params)
+            | HigherOrderFunc::Arctangent2(num_of_params)
+            | HigherOrderFunc::HyperbolicSine(num_of_params)
+            | HigherOrderFunc::HyperbolicCosine(num_of_params)
+            | HigherOrderFunc::HyperbolicTangent(num_of_params)
+            | HigherOrderFunc::AreaHyperbolicSine(num_of_params)
+            | HigherOrderFunc::AreaHyperbolicCosine(num_of_params)
+            | HigherOrderFunc::AreaHyperbolicTangent(num_of_params)
+            | HigherOrderFunc::NaturalLogarithm(num_of_params)
+            | HigherOrderFunc::DecimalLogarithm(num_of_params)
+            | HigherOrderFunc::BinaryLogarithm(num_of_params)
+            | HigherOrderFunc::Logarithm(num_of_params)
+            | HigherOrderFunc::Exponential(num_of_params)
+            | HigherOrderFunc::ExponentialMinusOne(num_of_params)
+            | HigherOrderFunc::NaturalLogarithmOnePlus(num_of_params)
+            | HigherOrderFunc::ToFloat(num_of_params)
+            | HigherOrderFunc::ToFraction(num_of_params)
+            | HigherOrderFunc::RealPart(num_of_params)
+            | HigherOrderFunc::ImaginaryPart(num_of_params)
+            | HigherOrderFunc::Conjugate(num_of_params)
+            | HigherOrderFunc::Argument(num_of_params)
+            | HigherOrderFunc::ToPolar(num_of_params)
+            | HigherOrderFunc::FromPolar(num_of_params)
+            | HigherOrderFunc::If(num_of_params)
+            | HigherOrderFunc::List(num_of_params)
+            | HigherOrderFunc::Map(num_of_params)
+            | HigherOrderFunc::Filter(num_of_params)
+            | HigherOrderFunc::Fold(num_of_params)
+            | HigherOrderFunc::Apply(num_of_params)
+            | HigherOrderFunc::Dot(num_of_params)
+            | HigherOrderFunc::Cross(num_of_params)
+            | HigherOrderFunc::Determinant(num_of_params)
+            | HigherOrderFunc::Inverse(num_of_params)
+            | HigherOrderFunc::Transpose(num_of_params)
+            | HigherOrderFunc::Trace(num_of_params)
+            | HigherOrderFunc::Rank(num_of_params)
+            | HigherOrderFunc::Solve(num_of_params)
+            | HigherOrderFunc::Derivative(num_of_params)
+            | HigherOrderFunc::Simplify(num_of_params)
+            | HigherOrderFunc::Expand(num_of_params)
+            | HigherOrderFunc::Factor(num_of_params)
+            | HigherOrderFunc::Substitution(num_of_params)
+            | HigherOrderFunc::FindRoot(num_of_params)
+            | HigherOrderFunc::Integral(num_of_params)
+            | HigherOrderFunc::Sum(num_of_params)
+            | HigherOrderFunc::Product(num_of_params)
+            | HigherOrderFunc::NumericDerivative(num_of_params)
+            | HigherOrderFunc::Limit(num_of_params) => num_of_params,
         }
     }
+
+    /// Returns true if a parameter of the function is the name of a variable instead of a value, e.g. `x` in `Diff(x^2, x)`.
+    /// These functions cannot be passed to other functions like `Map`.
+    pub fn has_variable_param(&self) -> bool {
+        matches!(
+            self,
+            HigherOrderFunc::Let(_)
+                | HigherOrderFunc::Derivative(_)
+                | HigherOrderFunc::Substitution(_)
+                | HigherOrderFunc::FindRoot(_)
+                | HigherOrderFunc::Integral(_)
+                | HigherOrderFunc::Sum(_)
+                | HigherOrderFunc::Product(_)
+                | HigherOrderFunc::NumericDerivative(_)
+                | HigherOrderFunc::Limit(_)
+        )
+    }
+
+    /// Returns true if the function takes any number of parameters, but at least the parameter count
+    pub fn is_variadic(&self) -> bool {
+        matches!(
+            self,
+            HigherOrderFunc::List(_)
+                | HigherOrderFunc::Apply(_)
+                | HigherOrderFunc::Solve(_)
+                | HigherOrderFunc::Limit(_)
+        )
+    }
 }
 
 pub const HIGHER_ORDER_FUNC_KEYWORDS: phf::Map<&'static str, HigherOrderFunc> = phf_map! {
@@ -124,9 +492,141 @@ pub const HIGHER_ORDER_FUNC_KEYWORDS: phf::Map<&'static str, HigherOrderFunc> =
     "EMod" => HigherOrderFunc::EucleadianModulo( 2),
     "Min" => HigherOrderFunc::Minimum( 2),
     "Max" => HigherOrderFunc::Maximum( 2),
-    "Abs" => HigherOrderFunc::Absolute( 1)
+    "Abs" => HigherOrderFunc::Absolute( 1),
+    "Let" => HigherOrderFunc::Let( 2),
+    "Pow" => HigherOrderFunc::Power( 2),
+    "Sqrt" => HigherOrderFunc::SquareRoot( 1),
+    "Cbrt" => HigherOrderFunc::CubeRoot( 1),
+    "Root" => HigherOrderFunc::Root( 2),
+    "Sin" => HigherOrderFunc::Sine( 1),
+    "Cos" => HigherOrderFunc::Cosine( 1),
+    "Tan" => HigherOrderFunc::Tangent( 1),
+    "Asin" => HigherOrderFunc::Arcsine( 1),
+    "Acos" => HigherOrderFunc::Arccosine( 1),
+    "Atan" => HigherOrderFunc::Arctangent( 1),
+    "Atan2" => HigherOrderFunc::Arctangent2( 2),
+    "Sinh" => HigherOrderFunc::HyperbolicSine( 1),
+    "Cosh" => HigherOrderFunc::HyperbolicCosine( 1),
+    "Tanh" => HigherOrderFunc::HyperbolicTangent( 1),
+    "Asinh" => HigherOrderFunc::AreaHyperbolicSine( 1),
+    "Acosh" => HigherOrderFunc::AreaHyperbolicCosine( 1),
+    "Atanh" => HigherOrderFunc::AreaHyperbolicTangent( 1),
+    "Ln" => HigherOrderFunc::NaturalLogarithm( 1),
+    "Log10" => HigherOrderFunc::DecimalLogarithm( 1),
+    "Log2" => HigherOrderFunc::BinaryLogarithm( 1),
+    "Log" => HigherOrderFunc::Logarithm( 2),
+    "Exp" => HigherOrderFunc::Exponential( 1),
+    "Expm1" => HigherOrderFunc::ExponentialMinusOne( 1),
+    "Ln1p" => HigherOrderFunc::NaturalLogarithmOnePlus( 1),
+    "ToFloat" => HigherOrderFunc::ToFloat( 1),
+    "ToFraction" => HigherOrderFunc::ToFraction( 1),
+    "Re" => HigherOrderFunc::RealPart( 1),
+    "Im" => HigherOrderFunc::ImaginaryPart( 1),
+    "Conj" => HigherOrderFunc::Conjugate( 1),
+    "Arg" => HigherOrderFunc::Argument( 1),
+    "Polar" => HigherOrderFunc::ToPolar( 1),
+    "Rect" => HigherOrderFunc::FromPolar( 2),
+    "If" => HigherOrderFunc::If( 3),
+    "List" => HigherOrderFunc::List( 1),
+    "Map" => HigherOrderFunc::Map( 2),
+    "Filter" => HigherOrderFunc::Filter( 2),
+    "Fold" => HigherOrderFunc::Fold( 3),
+    "Apply" => HigherOrderFunc::Apply( 2),
+    "Dot" => HigherOrderFunc::Dot( 2),
+    "Cross" => HigherOrderFunc::Cross( 2),
+    "Det" => HigherOrderFunc::Determinant( 1),
+    "Inv" => HigherOrderFunc::Inverse( 1),
+    "Transpose" => HigherOrderFunc::Transpose( 1),
+    "Trace" => HigherOrderFunc::Trace( 1),
+    "Rank" => HigherOrderFunc::Rank( 1),
+    "Solve" => HigherOrderFunc::Solve( 2),
+    "Diff" => HigherOrderFunc::Derivative( 2),
+    "Simplify" => HigherOrderFunc::Simplify( 1),
+    "Expand" => HigherOrderFunc::Expand( 1),
+    "Factor" => HigherOrderFunc::Factor( 1),
+    "Subst" => HigherOrderFunc::Substitution( 3),
+    "FindRoot" => HigherOrderFunc::FindRoot( 3),
+    "Integrate" => HigherOrderFunc::Integral( 4),
+    "Sum" => HigherOrderFunc::Sum( 4),
+    "Product" => HigherOrderFunc::Product( 4),
+    "NDiff" => HigherOrderFunc::NumericDerivative( 3),
+    "Limit" => HigherOrderFunc::Limit( 3),
+};
+
+pub const CONSTANT_KEYWORDS: phf::Map<&'static str, Constant> = phf_map! {
+    "Pi" => Constant::Pi,
+    "E" => Constant::E,
+    "Tau" => Constant::Tau,
+    "Phi" => Constant::Phi,
+    "Inf" => Constant::Infinity,
+    "SpeedOfLight" => Constant::SpeedOfLight,
+    "Planck" => Constant::Planck,
+    "Avogadro" => Constant::Avogadro,
+    "Boltzmann" => Constant::Boltzmann,
+    "Gravity" => Constant::StandardGravity,
+    "ElementaryCharge" => Constant::ElementaryCharge,
 };
 
+#[derive(Debug, PartialEq, Clone)]
+pub enum Constant {
+    Pi,
+    E,
+    Tau,
+    Phi,
+    Infinity,
+    SpeedOfLight,
+    Planck,
+    Avogadro,
+    Boltzmann,
+    StandardGravity,
+    ElementaryCharge,
+}
+
+impl Constant {
+    /// Returns the keyword with which the constant is written
+    pub fn get_keyword(&self) -> &'static str {
+        CONSTANT_KEYWORDS
+            .entries()
+            .find(|(_, constant)| *constant == self)
+            .map(|(keyword, _)| *keyword)
+            .expect("Every constant has a keyword.")
+    }
+
+    /// Returns the value of the constant. Physical constants are given in the SI units of `get_unit`.
+    pub fn get_value(&self) -> f64 {
+        match self {
+            Constant::Pi => std::f64::consts::PI,
+            Constant::E => std::f64::consts::E,
+            Constant::Tau => std::f64::consts::TAU,
+            Constant::Phi => 1.618_033_988_749_895,
+            Constant::Infinity => f64::INFINITY,
+            Constant::SpeedOfLight => 299_792_458.0,
+            Constant::Planck => 6.626_070_15e-34,
+            Constant::Avogadro => 6.022_140_76e23,
+            Constant::Boltzmann => 1.380_649e-23,
+            Constant::StandardGravity => 9.806_65,
+            Constant::ElementaryCharge => 1.602_176_634e-19,
+        }
+    }
+
+    /// Returns the unit of a physical constant, mathematical constants are plain numbers
+    pub fn get_unit(&self) -> Option<Unit> {
+        let factors: &[(&'static str, i32)] = match self {
+            Constant::Pi | Constant::E | Constant::Tau | Constant::Phi | Constant::Infinity => {
+                return None
+            }
+            Constant::SpeedOfLight => &[("m", 1), ("s", -1)],
+            Constant::Planck => &[("J", 1), ("s", 1)],
+            Constant::Avogadro => &[("mol", -1)],
+            Constant::Boltzmann => &[("J", 1), ("K", -1)],
+            Constant::StandardGravity => &[("m", 1), ("s", -2)],
+            Constant::ElementaryCharge => &[("C", 1)],
+        };
+
+        Some(Unit::from_factors(factors))
+    }
+}
+
 /// Returns true if the first number of the two provided 64bit floating point numbers is smaller than the second else false
 pub fn less_than(a: f64, b: f64) -> FunctionReturnType {
     FunctionReturnType::Bool(a < b)
@@ -142,6 +642,11 @@ pub fn absolute(num: f64) -> FunctionReturnType {
     FunctionReturnType::F64(num.abs())
 }
 
+/// Returns the negated value of the provided 64bit floating point number
+pub fn negation(num: f64) -> FunctionReturnType {
+    FunctionReturnType::F64(-num)
+}
+
 /// Returns the minimum of the two provided 64bit floating point numbers
 pub fn minimum(a: f64, b: f64) -> FunctionReturnType {
     FunctionReturnType::F64(a.min(b))
@@ -152,39 +657,359 @@ pub fn maximum(a: f64, b: f64) -> FunctionReturnType {
     FunctionReturnType::F64(a.max(b))
 }
 
-/// Calculates the factorial of a 32bit unsigned integer
+/// The largest number of which the factorial is calculated, larger factorials take too long to be useful
+pub const MAX_FACTORIAL_INPUT: u32 = 20_000;
+
+/// The largest number of bits an integer power may have before it is calculated as a 64bit floating point number
+pub const MAX_INTEGER_BITS: u64 = 1_000_000;
+
+/// Calculates the factorial of a 32bit unsigned integer as an arbitrary-precision integer
 pub fn factorial(num: u32) -> Result<FunctionReturnType, FunctionError> {
-    let mut val = num;
-    let mut result = 1;
+    if num > MAX_FACTORIAL_INPUT {
+        return Err(create_error(
+            FunctionErrorType::FactorialError,
+            num.into(),
+            None,
+            None,
+        ));
+    }
 
-    while val > 1 {
-        result *= val;
+    Ok(FunctionReturnType::BigInt(
+        (1..=num).map(BigInt::from).product(),
+    ))
+}
 
-        if result > u32::MAX / (val - 1) {
-            return Err(create_error(
-                FunctionErrorType::FactorialError,
-                num.into(),
-                None,
-                None,
-            ));
+/// Converts an arbitrary-precision integer to a 64bit floating point number if it fits into its range
+pub fn integer_to_f64(num: &BigInt) -> Result<f64, FunctionError> {
+    match num.to_f64() {
+        Some(result) if result.is_finite() => Ok(result),
+        _ => Err(FunctionError::new(
+            format!(
+                "The integer with {} digits is too large to fit into the range of a 64bit floating point number ({:e}).",
+                num.magnitude().to_string().len(),
+                f64::MAX
+            ),
+            if num.is_negative() {
+                FunctionErrorType::UnderflowInf
+            } else {
+                FunctionErrorType::OverflowInf
+            },
+        )),
+    }
+}
+
+/// Returns true if the first of the two provided arbitrary-precision integers is smaller than the second else false
+pub fn integer_less_than(a: &BigInt, b: &BigInt) -> FunctionReturnType {
+    FunctionReturnType::Bool(a < b)
+}
+
+/// Returns true if the first of the two provided arbitrary-precision integers is greater than the second else false
+pub fn integer_greater_than(a: &BigInt, b: &BigInt) -> FunctionReturnType {
+    FunctionReturnType::Bool(a > b)
+}
+
+/// Returns the absolute value of the provided arbitrary-precision integer
+pub fn integer_absolute(num: &BigInt) -> FunctionReturnType {
+    FunctionReturnType::BigInt(num.abs())
+}
+
+/// Returns the negated value of the provided arbitrary-precision integer
+pub fn integer_negation(num: &BigInt) -> FunctionReturnType {
+    FunctionReturnType::BigInt(-num)
+}
+
+/// Returns the minimum of the two provided arbitrary-precision integers
+pub fn integer_minimum(a: &BigInt, b: &BigInt) -> FunctionReturnType {
+    FunctionReturnType::BigInt(a.min(b).clone())
+}
+
+/// Returns the maximum of the two provided arbitrary-precision integers
+pub fn integer_maximum(a: &BigInt, b: &BigInt) -> FunctionReturnType {
+    FunctionReturnType::BigInt(a.max(b).clone())
+}
+
+/// Calculates the sum of two arbitrary-precision integers
+pub fn integer_addition(a: &BigInt, b: &BigInt) -> FunctionReturnType {
+    FunctionReturnType::BigInt(a + b)
+}
+
+/// Calculates the difference of two arbitrary-precision integers
+pub fn integer_subtraction(a: &BigInt, b: &BigInt) -> FunctionReturnType {
+    FunctionReturnType::BigInt(a - b)
+}
+
+/// Calculates the product of two arbitrary-precision integers
+pub fn integer_multiplication(a: &BigInt, b: &BigInt) -> FunctionReturnType {
+    FunctionReturnType::BigInt(a * b)
+}
+
+/// Calculates the quotient of two arbitrary-precision integers.
+/// The result is only an integer if the division has no remainder, otherwise it is a 64bit floating point number.
+pub fn integer_division(a: &BigInt, b: &BigInt) -> Result<FunctionReturnType, FunctionError> {
+    if b.is_zero() {
+        return Err(create_error(
+            FunctionErrorType::DivisionByZero,
+            integer_to_f64(a)?,
+            Some(0.0),
+            None,
+        ));
+    }
+
+    match a.div_rem(b) {
+        (quotient, remainder) if remainder.is_zero() => Ok(FunctionReturnType::BigInt(quotient)),
+        _ => division(integer_to_f64(a)?, integer_to_f64(b)?),
+    }
+}
+
+/// Calculates the modulus of the division of two arbitrary-precision integers. The result has the sign of the dividend.
+pub fn integer_modulo(a: &BigInt, n: &BigInt) -> Result<FunctionReturnType, FunctionError> {
+    if n.is_zero() {
+        return Err(create_error(
+            FunctionErrorType::DivisionByZero,
+            integer_to_f64(a)?,
+            Some(0.0),
+            None,
+        ));
+    }
+
+    Ok(FunctionReturnType::BigInt(a % n))
+}
+
+/// Calculates the euclidean modulus of the division of two arbitrary-precision integers. The result is never negative.
+pub fn integer_modulo_euclid(a: &BigInt, n: &BigInt) -> Result<FunctionReturnType, FunctionError> {
+    if n.is_zero() {
+        return Err(create_error(
+            FunctionErrorType::DivisionByZero,
+            integer_to_f64(a)?,
+            Some(0.0),
+            None,
+        ));
+    }
+
+    Ok(FunctionReturnType::BigInt(a.mod_floor(&n.abs())))
+}
+
+/// Raises the first arbitrary-precision integer to the power of the second.
+/// Negative exponents and results that would be too large to calculate fall back to 64bit floating point numbers.
+pub fn integer_exponentiation(
+    base: &BigInt,
+    exponent: &BigInt,
+) -> Result<FunctionReturnType, FunctionError> {
+    match exponent.to_u32() {
+        Some(exp) if base.bits().saturating_mul(exp.into()) <= MAX_INTEGER_BITS => {
+            Ok(FunctionReturnType::BigInt(base.pow(exp)))
+        }
+        _ => exponentiation(integer_to_f64(base)?, integer_to_f64(exponent)?),
+    }
+}
+
+/// Returns the fraction as an integer if its denominator is one, otherwise as a fraction
+pub fn rational_value(num: BigRational) -> FunctionReturnType {
+    if num.is_integer() {
+        FunctionReturnType::BigInt(num.to_integer())
+    } else {
+        FunctionReturnType::Rational(num)
+    }
+}
+
+/// Converts a fraction to the nearest 64bit floating point number if it fits into its range
+pub fn rational_to_f64(num: &BigRational) -> Result<f64, FunctionError> {
+    match num.to_f64() {
+        Some(result) if result.is_finite() => Ok(result),
+        _ => integer_to_f64(&num.to_integer()),
+    }
+}
+
+/// Converts an integer or a fraction to a 64bit floating point number
+pub fn to_float(value: FunctionReturnType) -> Result<FunctionReturnType, FunctionError> {
+    Ok(FunctionReturnType::F64(value.get_f64()?))
+}
+
+/// Converts a 64bit floating point number to the simplest fraction which has exactly this floating point value.
+/// Integers and fractions are already exact and are returned unchanged, decimals are converted to their exact value.
+pub fn to_fraction(value: FunctionReturnType) -> Result<FunctionReturnType, FunctionError> {
+    if value.get_exact().is_some() {
+        return Ok(value);
+    }
+
+    if let FunctionReturnType::Decimal(num) = value {
+        return Ok(rational_value(decimal::to_rational(&num)));
+    }
+
+    let num = value.get_f64()?;
+    let mut remainder = BigRational::from_float(num).ok_or_else(|| {
+        FunctionError::new(
+            format!(
+                "Only finite numbers can be converted to a fraction. You tried to convert {}.",
+                num
+            ),
+            FunctionErrorType::InvalidParameterType,
+        )
+    })?;
+
+    // The convergents of the continued fraction of the number approach it with growing denominators,
+    // the first one that rounds to the floating point number is the simplest fraction
+    let (mut prev_numer, mut prev_denom) = (BigInt::zero(), BigInt::one());
+    let (mut numer, mut denom) = (BigInt::one(), BigInt::zero());
+
+    loop {
+        let whole = remainder.floor();
+        let term = whole.to_integer();
+
+        (prev_numer, numer) = (numer.clone(), &term * numer + prev_numer);
+        (prev_denom, denom) = (denom.clone(), &term * denom + prev_denom);
+
+        let convergent = BigRational::new(numer.clone(), denom.clone());
+        if remainder == whole || convergent.to_f64() == Some(num) {
+            return Ok(rational_value(convergent));
         }
 
-        val -= 1;
+        remainder = (remainder - whole).recip();
+    }
+}
+
+/// Returns true if the first of the two provided fractions is smaller than the second else false
+pub fn rational_less_than(a: &BigRational, b: &BigRational) -> FunctionReturnType {
+    FunctionReturnType::Bool(a < b)
+}
+
+/// Returns true if the first of the two provided fractions is greater than the second else false
+pub fn rational_greater_than(a: &BigRational, b: &BigRational) -> FunctionReturnType {
+    FunctionReturnType::Bool(a > b)
+}
+
+/// Returns the absolute value of the provided fraction
+pub fn rational_absolute(num: &BigRational) -> FunctionReturnType {
+    rational_value(num.abs())
+}
+
+/// Returns the negated value of the provided fraction
+pub fn rational_negation(num: &BigRational) -> FunctionReturnType {
+    rational_value(-num)
+}
+
+/// Returns the minimum of the two provided fractions
+pub fn rational_minimum(a: &BigRational, b: &BigRational) -> FunctionReturnType {
+    rational_value(a.min(b).clone())
+}
+
+/// Returns the maximum of the two provided fractions
+pub fn rational_maximum(a: &BigRational, b: &BigRational) -> FunctionReturnType {
+    rational_value(a.max(b).clone())
+}
+
+/// Calculates the sum of two fractions
+pub fn rational_addition(a: &BigRational, b: &BigRational) -> FunctionReturnType {
+    rational_value(a + b)
+}
+
+/// Calculates the difference of two fractions
+pub fn rational_subtraction(a: &BigRational, b: &BigRational) -> FunctionReturnType {
+    rational_value(a - b)
+}
+
+/// Calculates the product of two fractions
+pub fn rational_multiplication(a: &BigRational, b: &BigRational) -> FunctionReturnType {
+    rational_value(a * b)
+}
+
+/// Calculates the quotient of two fractions
+pub fn rational_division(
+    a: &BigRational,
+    b: &BigRational,
+) -> Result<FunctionReturnType, FunctionError> {
+    if b.is_zero() {
+        return Err(create_error(
+            FunctionErrorType::DivisionByZero,
+            rational_to_f64(a)?,
+            Some(0.0),
+            None,
+        ));
+    }
+
+    Ok(rational_value(a / b))
+}
+
+/// Calculates the modulus of the division of two fractions. The result has the sign of the dividend.
+pub fn rational_modulo(
+    a: &BigRational,
+    n: &BigRational,
+) -> Result<FunctionReturnType, FunctionError> {
+    if n.is_zero() {
+        return Err(create_error(
+            FunctionErrorType::DivisionByZero,
+            rational_to_f64(a)?,
+            Some(0.0),
+            None,
+        ));
+    }
+
+    Ok(rational_value(a % n))
+}
+
+/// Calculates the euclidean modulus of the division of two fractions. The result is never negative.
+pub fn rational_modulo_euclid(
+    a: &BigRational,
+    n: &BigRational,
+) -> Result<FunctionReturnType, FunctionError> {
+    if n.is_zero() {
+        return Err(create_error(
+            FunctionErrorType::DivisionByZero,
+            rational_to_f64(a)?,
+            Some(0.0),
+            None,
+        ));
     }
 
-    Ok(FunctionReturnType::U32(result))
+    let n = n.abs();
+    Ok(rational_value(a - (a / &n).floor() * n))
 }
 
-/// Calculates the sum of two 64bit floating point numbers
+/// Raises the first fraction to the power of the second.
+/// Exponents that are not integers and results that would be too large to calculate fall back to 64bit floating point numbers.
+pub fn rational_exponentiation(
+    base: &BigRational,
+    exponent: &BigRational,
+) -> Result<FunctionReturnType, FunctionError> {
+    let bits = base.numer().bits() + base.denom().bits();
+
+    match exponent.to_integer().to_i32() {
+        Some(exp)
+            if exponent.is_integer()
+                && (exp >= 0 || !base.is_zero())
+                && bits.saturating_mul(exp.unsigned_abs().into()) <= MAX_INTEGER_BITS =>
+        {
+            Ok(rational_value(base.pow(exp)))
+        }
+        _ => exponentiation(rational_to_f64(base)?, rational_to_f64(exponent)?),
+    }
+}
+
+/// Calculates the sum of two 64bit floating point numbers.
+/// Infinite results are only reported as errors if both numbers are finite, undefined results of infinite numbers are
+/// reported as indeterminate forms.
 pub fn addition(a: f64, b: f64) -> Result<FunctionReturnType, FunctionError> {
     match a.add(b) {
-        result if result.is_sign_negative() && result.is_infinite() => Err(create_error(
-            FunctionErrorType::UnderflowInf,
+        result if result.is_nan() => Err(create_error(
+            FunctionErrorType::IndeterminateForm,
             a,
             Some(b),
             Some("addition"),
         )),
-        result if result.is_infinite() => Err(create_error(
+        result
+            if result.is_sign_negative()
+                && result.is_infinite()
+                && a.is_finite()
+                && b.is_finite() =>
+        {
+            Err(create_error(
+                FunctionErrorType::UnderflowInf,
+                a,
+                Some(b),
+                Some("addition"),
+            ))
+        }
+        result if result.is_infinite() && a.is_finite() && b.is_finite() => Err(create_error(
             FunctionErrorType::OverflowInf,
             a,
             Some(b),
@@ -194,16 +1019,31 @@ pub fn addition(a: f64, b: f64) -> Result<FunctionReturnType, FunctionError> {
     }
 }
 
-/// Calculates the difference of two 64bit floating point numbers
+/// Calculates the difference of two 64bit floating point numbers.
+/// Infinite results are only reported as errors if both numbers are finite, undefined results of infinite numbers are
+/// reported as indeterminate forms.
 pub fn subtraction(a: f64, b: f64) -> Result<FunctionReturnType, FunctionError> {
     match a.sub(b) {
-        result if result.is_sign_negative() && result.is_infinite() => Err(create_error(
-            FunctionErrorType::UnderflowInf,
+        result if result.is_nan() => Err(create_error(
+            FunctionErrorType::IndeterminateForm,
             a,
             Some(b),
             Some("subtraction"),
         )),
-        result if result.is_infinite() => Err(create_error(
+        result
+            if result.is_sign_negative()
+                && result.is_infinite()
+                && a.is_finite()
+                && b.is_finite() =>
+        {
+            Err(create_error(
+                FunctionErrorType::UnderflowInf,
+                a,
+                Some(b),
+                Some("subtraction"),
+            ))
+        }
+        result if result.is_infinite() && a.is_finite() && b.is_finite() => Err(create_error(
             FunctionErrorType::OverflowInf,
             a,
             Some(b),
@@ -214,25 +1054,59 @@ pub fn subtraction(a: f64, b: f64) -> Result<FunctionReturnType, FunctionError>
 }
 
 /// Calculates the modulus of the division of two 64bit floating point numbers
-pub fn modulo(a: f64, n: f64) -> FunctionReturnType {
-    FunctionReturnType::F64(a.rem(n))
+pub fn modulo(a: f64, n: f64) -> Result<FunctionReturnType, FunctionError> {
+    check_modulo(a.rem(n), a, n)
 }
 
 /// Calculates the euclidean modulus of the division of two 64bit floating point numbers
-pub fn modulo_euclid(a: f64, n: f64) -> FunctionReturnType {
-    FunctionReturnType::F64(a.rem_euclid(n))
+pub fn modulo_euclid(a: f64, n: f64) -> Result<FunctionReturnType, FunctionError> {
+    check_modulo(a.rem_euclid(n), a, n)
+}
+
+/// Returns an error if the modulus is not a number, e.g. for a division by zero or an infinite dividend
+fn check_modulo(result: f64, a: f64, n: f64) -> Result<FunctionReturnType, FunctionError> {
+    match result {
+        result if result.is_nan() && n == 0.0 => Err(create_error(
+            FunctionErrorType::DivisionByZero,
+            a,
+            Some(n),
+            None,
+        )),
+        result if result.is_nan() => Err(create_error(
+            FunctionErrorType::IndeterminateForm,
+            a,
+            Some(n),
+            Some("modulo"),
+        )),
+        result => Ok(FunctionReturnType::F64(result)),
+    }
 }
 
-/// Calculates the product of two 64bit floating point numbers
+/// Calculates the product of two 64bit floating point numbers.
+/// Infinite results are only reported as errors if both numbers are finite, undefined results of infinite numbers are
+/// reported as indeterminate forms.
 pub fn multiplication(a: f64, b: f64) -> Result<FunctionReturnType, FunctionError> {
     match a.mul(b) {
-        result if result.is_sign_negative() && result.is_infinite() => Err(create_error(
-            FunctionErrorType::UnderflowInf,
+        result if result.is_nan() => Err(create_error(
+            FunctionErrorType::IndeterminateForm,
             a,
             Some(b),
             Some("multiplication"),
         )),
-        result if result.is_infinite() => Err(create_error(
+        result
+            if result.is_sign_negative()
+                && result.is_infinite()
+                && a.is_finite()
+                && b.is_finite() =>
+        {
+            Err(create_error(
+                FunctionErrorType::UnderflowInf,
+                a,
+                Some(b),
+                Some("multiplication"),
+            ))
+        }
+        result if result.is_infinite() && a.is_finite() && b.is_finite() => Err(create_error(
             FunctionErrorType::OverflowInf,
             a,
             Some(b),
@@ -242,22 +1116,37 @@ pub fn multiplication(a: f64, b: f64) -> Result<FunctionReturnType, FunctionErro
     }
 }
 
-/// Calculates the quotient of two 64bit floating point numbers
+/// Calculates the quotient of two 64bit floating point numbers.
+/// Infinite results are only reported as errors if both numbers are finite, undefined results of infinite numbers are
+/// reported as indeterminate forms.
 pub fn division(a: f64, b: f64) -> Result<FunctionReturnType, FunctionError> {
     match a.div(b) {
-        result if result.is_nan() => Err(create_error(
+        _ if b == 0.0 => Err(create_error(
             FunctionErrorType::DivisionByZero,
             a,
             Some(b),
             None,
         )),
-        result if result.is_sign_negative() && result.is_infinite() => Err(create_error(
-            FunctionErrorType::UnderflowInf,
+        result if result.is_nan() => Err(create_error(
+            FunctionErrorType::IndeterminateForm,
             a,
             Some(b),
             Some("division"),
         )),
-        result if result.is_infinite() => Err(create_error(
+        result
+            if result.is_sign_negative()
+                && result.is_infinite()
+                && a.is_finite()
+                && b.is_finite() =>
+        {
+            Err(create_error(
+                FunctionErrorType::UnderflowInf,
+                a,
+                Some(b),
+                Some("division"),
+            ))
+        }
+        result if result.is_infinite() && a.is_finite() && b.is_finite() => Err(create_error(
             FunctionErrorType::OverflowInf,
             a,
             Some(b),
@@ -267,43 +1156,582 @@ pub fn division(a: f64, b: f64) -> Result<FunctionReturnType, FunctionError> {
     }
 }
 
-fn create_error(
+/// Raises the first 64bit floating point number to the power of the second.
+/// Negative powers of zero are divisions by zero.
+pub fn exponentiation(base: f64, exponent: f64) -> Result<FunctionReturnType, FunctionError> {
+    match base.powf(exponent) {
+        _ if base == 0.0 && exponent < 0.0 => Err(create_error(
+            FunctionErrorType::DivisionByZero,
+            base,
+            Some(exponent),
+            Some("exponentiation"),
+        )),
+        result if result.is_nan() => Err(create_error(
+            FunctionErrorType::DomainError,
+            base,
+            Some(exponent),
+            Some("exponentiation"),
+        )),
+        result
+            if result.is_sign_negative()
+                && result.is_infinite()
+                && base.is_finite()
+                && exponent.is_finite() =>
+        {
+            Err(create_error(
+                FunctionErrorType::UnderflowInf,
+                base,
+                Some(exponent),
+                Some("exponentiation"),
+            ))
+        }
+        result if result.is_infinite() && base.is_finite() && exponent.is_finite() => {
+            Err(create_error(
+                FunctionErrorType::OverflowInf,
+                base,
+                Some(exponent),
+                Some("exponentiation"),
+            ))
+        }
+        result => Ok(FunctionReturnType::F64(result)),
+    }
+}
+
+/// Calculates the square root of a 64bit floating point number
+pub fn square_root(num: f64) -> Result<FunctionReturnType, FunctionError> {
+    root(num, 2.0)
+}
+
+/// Calculates the cube root of a 64bit floating point number
+pub fn cube_root(num: f64) -> FunctionReturnType {
+    FunctionReturnType::F64(num.cbrt())
+}
+
+/// Calculates the n-th root of a 64bit floating point number.
+/// Odd roots of negative numbers are negative, even roots of negative numbers have no real result.
+pub fn root(num: f64, n: f64) -> Result<FunctionReturnType, FunctionError> {
+    if n == 0.0 {
+        return Err(create_error(
+            FunctionErrorType::DomainError,
+            num,
+            Some(n),
+            Some("root"),
+        ));
+    }
+
+    if num.is_sign_negative() && num != 0.0 {
+        if n.fract() != 0.0 || n % 2.0 == 0.0 {
+            return Err(create_error(
+                FunctionErrorType::DomainError,
+                num,
+                Some(n),
+                Some("root"),
+            ));
+        }
+
+        return Ok(FunctionReturnType::F64(-(-num).powf(n.recip())));
+    }
+
+    match num.powf(n.recip()) {
+        result if result.is_infinite() && num.is_finite() => Err(create_error(
+            FunctionErrorType::OverflowInf,
+            num,
+            Some(n),
+            Some("root"),
+        )),
+        result => Ok(FunctionReturnType::F64(result)),
+    }
+}
+
+/// Returns the exact sine and cosine of an angle that is a multiple of 30° or 45°, which are not exact after
+/// a conversion to radians, e.g. the sine of 30° would be 0.49999999999999994.
+/// Angles in radians are never exact, as these multiples are irrational in radians.
+fn get_exact_sine_cosine(angle: f64, angle_mode: &AngleMode) -> Option<(f64, f64)> {
+    // Steps of 15° are the greatest common divisor of 30° and 45°
+    let steps = angle * 24.0 / angle_mode.get_full_turn();
+
+    if *angle_mode == AngleMode::Radians || steps.fract() != 0.0 {
+        return None;
+    }
+
+    let steps = steps.rem_euclid(24.0) as u8;
+    let sine = |steps: u8| match steps % 24 {
+        steps @ 0..=6 => get_first_quadrant_sine(steps),
+        steps @ 7..=12 => get_first_quadrant_sine(12 - steps),
+        steps @ 13..=18 => get_first_quadrant_sine(steps - 12).map(Neg::neg),
+        steps => get_first_quadrant_sine(24 - steps).map(Neg::neg),
+    };
+
+    Some((sine(steps)?, sine(steps + 6)?))
+}
+
+/// Returns the sine of an angle in steps of 15° between 0° and 90° if it is exact
+fn get_first_quadrant_sine(steps: u8) -> Option<f64> {
+    match steps {
+        0 => Some(0.0),
+        2 => Some(0.5),
+        3 => Some(std::f64::consts::FRAC_1_SQRT_2),
+        4 => Some(3.0_f64.sqrt() / 2.0),
+        6 => Some(1.0),
+        _ => None,
+    }
+}
+
+/// Returns an error for infinite angles, at which trigonometric functions oscillate without a limit
+fn check_finite_angle(angle: f64, operation_name: &str) -> Result<(), FunctionError> {
+    if angle.is_infinite() {
+        Err(create_error(
+            FunctionErrorType::DomainError,
+            angle,
+            None,
+            Some(operation_name),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Calculates the sine of an angle given in the unit of the angle mode
+pub fn sine(angle: f64, angle_mode: &AngleMode) -> Result<FunctionReturnType, FunctionError> {
+    check_finite_angle(angle, "sine")?;
+
+    Ok(FunctionReturnType::F64(
+        match get_exact_sine_cosine(angle, angle_mode) {
+            Some((sine, _)) => sine,
+            None => angle_mode.convert_to_radians(angle).sin(),
+        },
+    ))
+}
+
+/// Calculates the cosine of an angle given in the unit of the angle mode
+pub fn cosine(angle: f64, angle_mode: &AngleMode) -> Result<FunctionReturnType, FunctionError> {
+    check_finite_angle(angle, "cosine")?;
+
+    Ok(FunctionReturnType::F64(
+        match get_exact_sine_cosine(angle, angle_mode) {
+            Some((_, cosine)) => cosine,
+            None => angle_mode.convert_to_radians(angle).cos(),
+        },
+    ))
+}
+
+/// Calculates the tangent of an angle given in the unit of the angle mode
+pub fn tangent(angle: f64, angle_mode: &AngleMode) -> Result<FunctionReturnType, FunctionError> {
+    check_finite_angle(angle, "tangent")?;
+
+    match get_exact_sine_cosine(angle, angle_mode) {
+        Some((_, 0.0)) => Err(create_error(
+            FunctionErrorType::DomainError,
+            angle,
+            None,
+            Some("tangent"),
+        )),
+        Some((sine, cosine)) => Ok(FunctionReturnType::F64(sine / cosine)),
+        None => Ok(FunctionReturnType::F64(
+            angle_mode.convert_to_radians(angle).tan(),
+        )),
+    }
+}
+
+/// Calculates the arcsine of a number in the range [-1, 1] and returns it in the unit of the angle mode
+pub fn arcsine(num: f64, angle_mode: &AngleMode) -> Result<FunctionReturnType, FunctionError> {
+    if !(-1.0..=1.0).contains(&num) {
+        return Err(create_error(
+            FunctionErrorType::DomainError,
+            num,
+            None,
+            Some("arcsine"),
+        ));
+    }
+
+    Ok(FunctionReturnType::F64(
+        angle_mode.convert_from_radians(num.asin()),
+    ))
+}
+
+/// Calculates the arccosine of a number in the range [-1, 1] and returns it in the unit of the angle mode
+pub fn arccosine(num: f64, angle_mode: &AngleMode) -> Result<FunctionReturnType, FunctionError> {
+    if !(-1.0..=1.0).contains(&num) {
+        return Err(create_error(
+            FunctionErrorType::DomainError,
+            num,
+            None,
+            Some("arccosine"),
+        ));
+    }
+
+    Ok(FunctionReturnType::F64(
+        angle_mode.convert_from_radians(num.acos()),
+    ))
+}
+
+/// Calculates the arctangent of a number and returns it in the unit of the angle mode
+pub fn arctangent(num: f64, angle_mode: &AngleMode) -> FunctionReturnType {
+    FunctionReturnType::F64(angle_mode.convert_from_radians(num.atan()))
+}
+
+/// Calculates the angle of the point (x, y) to the positive x axis and returns it in the unit of the angle mode
+pub fn arctangent2(y: f64, x: f64, angle_mode: &AngleMode) -> FunctionReturnType {
+    FunctionReturnType::F64(angle_mode.convert_from_radians(y.atan2(x)))
+}
+
+/// Calculates the hyperbolic sine of a 64bit floating point number
+pub fn hyperbolic_sine(num: f64) -> Result<FunctionReturnType, FunctionError> {
+    match num.sinh() {
+        result if result.is_sign_negative() && result.is_infinite() && num.is_finite() => {
+            Err(create_error(
+                FunctionErrorType::UnderflowInf,
+                num,
+                None,
+                Some("hyperbolic sine"),
+            ))
+        }
+        result if result.is_infinite() && num.is_finite() => Err(create_error(
+            FunctionErrorType::OverflowInf,
+            num,
+            None,
+            Some("hyperbolic sine"),
+        )),
+        result => Ok(FunctionReturnType::F64(result)),
+    }
+}
+
+/// Calculates the hyperbolic cosine of a 64bit floating point number
+pub fn hyperbolic_cosine(num: f64) -> Result<FunctionReturnType, FunctionError> {
+    match num.cosh() {
+        result if result.is_infinite() && num.is_finite() => Err(create_error(
+            FunctionErrorType::OverflowInf,
+            num,
+            None,
+            Some("hyperbolic cosine"),
+        )),
+        result => Ok(FunctionReturnType::F64(result)),
+    }
+}
+
+/// Calculates the hyperbolic tangent of a 64bit floating point number
+pub fn hyperbolic_tangent(num: f64) -> FunctionReturnType {
+    FunctionReturnType::F64(num.tanh())
+}
+
+/// Calculates the inverse hyperbolic sine of a 64bit floating point number
+pub fn area_hyperbolic_sine(num: f64) -> FunctionReturnType {
+    FunctionReturnType::F64(num.asinh())
+}
+
+/// Calculates the inverse hyperbolic cosine of a 64bit floating point number that is at least 1
+pub fn area_hyperbolic_cosine(num: f64) -> Result<FunctionReturnType, FunctionError> {
+    if num < 1.0 {
+        return Err(create_error(
+            FunctionErrorType::DomainError,
+            num,
+            None,
+            Some("inverse hyperbolic cosine"),
+        ));
+    }
+
+    Ok(FunctionReturnType::F64(num.acosh()))
+}
+
+/// Calculates the inverse hyperbolic tangent of a 64bit floating point number in the range (-1, 1)
+pub fn area_hyperbolic_tangent(num: f64) -> Result<FunctionReturnType, FunctionError> {
+    if num <= -1.0 || num >= 1.0 {
+        return Err(create_error(
+            FunctionErrorType::DomainError,
+            num,
+            None,
+            Some("inverse hyperbolic tangent"),
+        ));
+    }
+
+    Ok(FunctionReturnType::F64(num.atanh()))
+}
+
+/// Returns an error if the number is not in the domain of logarithms, i.e. if it is not positive
+fn check_logarithm_domain(num: f64, operation_name: &str) -> Result<(), FunctionError> {
+    if num == 0.0 {
+        Err(create_error(
+            FunctionErrorType::LogarithmOfZero,
+            num,
+            None,
+            Some(operation_name),
+        ))
+    } else if num < 0.0 {
+        Err(create_error(
+            FunctionErrorType::LogarithmOfNegative,
+            num,
+            None,
+            Some(operation_name),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Calculates the natural logarithm of a positive 64bit floating point number
+pub fn natural_logarithm(num: f64) -> Result<FunctionReturnType, FunctionError> {
+    check_logarithm_domain(num, "natural logarithm")?;
+
+    Ok(FunctionReturnType::F64(num.ln()))
+}
+
+/// Calculates the logarithm to the base 10 of a positive 64bit floating point number
+pub fn decimal_logarithm(num: f64) -> Result<FunctionReturnType, FunctionError> {
+    check_logarithm_domain(num, "decimal logarithm")?;
+
+    Ok(FunctionReturnType::F64(num.log10()))
+}
+
+/// Calculates the logarithm to the base 2 of a positive 64bit floating point number
+pub fn binary_logarithm(num: f64) -> Result<FunctionReturnType, FunctionError> {
+    check_logarithm_domain(num, "binary logarithm")?;
+
+    Ok(FunctionReturnType::F64(num.log2()))
+}
+
+/// Calculates the logarithm of a positive 64bit floating point number to a positive base other than 1
+pub fn logarithm(num: f64, base: f64) -> Result<FunctionReturnType, FunctionError> {
+    if base <= 0.0 || base == 1.0 {
+        return Err(create_error(
+            FunctionErrorType::InvalidLogarithmBase,
+            num,
+            Some(base),
+            Some("logarithm"),
+        ));
+    }
+
+    check_logarithm_domain(num, "logarithm")?;
+
+    Ok(FunctionReturnType::F64(num.log(base)))
+}
+
+/// Calculates the natural logarithm of one plus a 64bit floating point number.
+/// It is more accurate than `Ln(1 + x)` for numbers close to zero.
+pub fn natural_logarithm_one_plus(num: f64) -> Result<FunctionReturnType, FunctionError> {
+    // The domain of the sum is checked, but the error reports the number that was passed
+    if let Err(err) = check_logarithm_domain(1.0 + num, "Ln1p") {
+        return Err(create_error(err.error_type, num, None, Some("Ln1p")));
+    }
+
+    Ok(FunctionReturnType::F64(num.ln_1p()))
+}
+
+/// Calculates e raised to the power of a 64bit floating point number
+pub fn exponential(num: f64) -> Result<FunctionReturnType, FunctionError> {
+    match num.exp() {
+        result if result.is_infinite() && num.is_finite() => Err(create_error(
+            FunctionErrorType::OverflowInf,
+            num,
+            None,
+            Some("exponential"),
+        )),
+        result => Ok(FunctionReturnType::F64(result)),
+    }
+}
+
+/// Calculates e raised to the power of a 64bit floating point number minus one.
+/// It is more accurate than `Exp(x) - 1` for numbers close to zero.
+pub fn exponential_minus_one(num: f64) -> Result<FunctionReturnType, FunctionError> {
+    match num.exp_m1() {
+        result if result.is_infinite() && num.is_finite() => Err(create_error(
+            FunctionErrorType::OverflowInf,
+            num,
+            None,
+            Some("exponential minus one"),
+        )),
+        result => Ok(FunctionReturnType::F64(result)),
+    }
+}
+
+/// Returns the complex number as a 64bit floating point number if its imaginary part is zero
+pub fn complex_value(num: Complex64) -> FunctionReturnType {
+    if num.im == 0.0 {
+        FunctionReturnType::F64(num.re)
+    } else {
+        FunctionReturnType::Complex(num)
+    }
+}
+
+/// Returns the real part of a complex number
+pub fn real_part(num: Complex64) -> FunctionReturnType {
+    FunctionReturnType::F64(num.re)
+}
+
+/// Returns the imaginary part of a complex number
+pub fn imaginary_part(num: Complex64) -> FunctionReturnType {
+    FunctionReturnType::F64(num.im)
+}
+
+/// Returns the complex conjugate of a complex number, i.e. the number with the negated imaginary part
+pub fn conjugate(num: Complex64) -> FunctionReturnType {
+    complex_value(num.conj())
+}
+
+/// Returns the angle between the positive real axis and a complex number in the unit of the angle mode
+pub fn argument(num: Complex64, angle_mode: &AngleMode) -> FunctionReturnType {
+    FunctionReturnType::F64(angle_mode.convert_from_radians(num.arg()))
+}
+
+/// Returns the absolute value, i.e. the distance from zero, of a complex number
+pub fn complex_absolute(num: Complex64) -> FunctionReturnType {
+    FunctionReturnType::F64(num.norm())
+}
+
+/// Converts a complex number to the list of its absolute value and its argument in the unit of the angle mode
+pub fn to_polar(num: Complex64, angle_mode: &AngleMode) -> FunctionReturnType {
+    FunctionReturnType::List(vec![complex_absolute(num), argument(num, angle_mode)])
+}
+
+/// Converts the absolute value and the argument in the unit of the angle mode to a complex number
+pub fn from_polar(r: f64, angle: f64, angle_mode: &AngleMode) -> FunctionReturnType {
+    complex_value(Complex64::from_polar(
+        r,
+        angle_mode.convert_to_radians(angle),
+    ))
+}
+
+/// Returns the negated value of a complex number
+pub fn complex_negation(num: Complex64) -> FunctionReturnType {
+    complex_value(-num)
+}
+
+/// Calculates the sum of two complex numbers
+pub fn complex_addition(a: Complex64, b: Complex64) -> FunctionReturnType {
+    complex_value(a + b)
+}
+
+/// Calculates the difference of two complex numbers
+pub fn complex_subtraction(a: Complex64, b: Complex64) -> FunctionReturnType {
+    complex_value(a - b)
+}
+
+/// Calculates the product of two complex numbers
+pub fn complex_multiplication(a: Complex64, b: Complex64) -> FunctionReturnType {
+    complex_value(a * b)
+}
+
+/// Calculates the quotient of two complex numbers
+pub fn complex_division(a: Complex64, b: Complex64) -> Result<FunctionReturnType, FunctionError> {
+    if b.is_zero() {
+        return Err(FunctionError::new(
+            format!(
+                "You cannot divide by zero. You tried to divide {} by 0 which has no result.",
+                complex_value(a)
+            ),
+            FunctionErrorType::DivisionByZero,
+        ));
+    }
+
+    Ok(complex_value(a / b))
+}
+
+/// Raises the first complex number to the power of the second.
+/// Integer exponents are calculated by repeated multiplication, which keeps e.g. `i^2` exact.
+pub fn complex_exponentiation(
+    base: Complex64,
+    exponent: Complex64,
+) -> Result<FunctionReturnType, FunctionError> {
+    if base.is_zero() {
+        return match exponent.re {
+            re if re > 0.0 => Ok(FunctionReturnType::F64(0.0)),
+            _ => Err(FunctionError::new(
+                format!(
+                    "Zero can only be raised to powers with a positive real part. You tried to raise 0 to the power of {}.",
+                    complex_value(exponent)
+                ),
+                FunctionErrorType::DomainError,
+            )),
+        };
+    }
+
+    match exponent.re {
+        re if exponent.im == 0.0 && re.fract() == 0.0 && re.abs() <= i32::MAX.into() => {
+            Ok(complex_value(base.powi(re as i32)))
+        }
+        _ => Ok(complex_value(base.powc(exponent))),
+    }
+}
+
+/// Calculates the principal square root of a complex number, the square root of negative numbers is imaginary
+pub fn complex_square_root(num: Complex64) -> FunctionReturnType {
+    complex_value(num.sqrt())
+}
+
+/// Calculates the principal natural logarithm of a complex number
+pub fn complex_natural_logarithm(num: Complex64) -> Result<FunctionReturnType, FunctionError> {
+    if num.is_zero() {
+        return Err(create_error(
+            FunctionErrorType::LogarithmOfZero,
+            0.0,
+            None,
+            Some("natural logarithm"),
+        ));
+    }
+
+    Ok(complex_value(num.ln()))
+}
+
+/// Calculates e raised to the power of a complex number
+pub fn complex_exponential(num: Complex64) -> FunctionReturnType {
+    complex_value(num.exp())
+}
+
+/// Returns the error for operations that are only defined for real numbers, e.g. comparisons
+pub fn create_complex_error(operation_name: &str) -> FunctionError {
+    FunctionError::new(
+        format!("The {} is not defined for complex numbers.", operation_name),
+        FunctionErrorType::DomainError,
+    )
+}
+
+/// Formats the imaginary part of a complex number with the imaginary unit, e.g. `2i` or `-i`
+fn format_imaginary(im: f64) -> String {
+    match im {
+        1.0 => "i".to_string(),
+        -1.0 => "-i".to_string(),
+        im => format!("{}i", im),
+    }
+}
+
+/// Formats a number in scientific notation if it is very small or very large
+fn format_number(num: f64) -> String {
+    if !(0.00001..=10000.0).contains(&num.abs()) && num != 0.0 {
+        format!("{:e}", num)
+    } else {
+        format!("{}", num)
+    }
+}
+
+pub fn create_error(
     error_type: FunctionErrorType,
     first_num: f64,
     second_num: Option<f64>,
     operation_name: Option<&str>,
 ) -> FunctionError {
     match error_type {
-        FunctionErrorType::DivisionByZero => {
-            FunctionError::new(
-                if !(0.00001..=10000.0).contains(&first_num) {
-                    format!("You cannot divide by zero. You tried to divide {:e} by {} which has no result.", first_num, second_num.unwrap())
-                } else {
-                    format!("You cannot divide by zero. You tried to divide {} by {} which has no result.", first_num, second_num.unwrap())
-                },
-                FunctionErrorType::DivisionByZero,
-            )
-        }
+        FunctionErrorType::DivisionByZero => match operation_name {
+            Some("exponentiation") => FunctionError::new(
+                format!("You cannot divide by zero. You tried to raise 0 to the power of {}, which divides 1 by 0 and has no result.", format_number(second_num.unwrap())),
+                error_type,
+            ),
+            _ => FunctionError::new(
+                format!("You cannot divide by zero. You tried to divide {} by {} which has no result.", format_number(first_num), format_number(second_num.unwrap())),
+                error_type,
+            ),
+        },
         FunctionErrorType::FactorialError => FunctionError::new(
-            if first_num > 10000.0 {
-                format!("The factorial of this number ({:e}) is too large to fit into the maximum range of a 32bit unsigned integer ({:e}).", first_num, u32::MAX)
-            } else {
-                format!("The factorial of this number ({}) is too large to fit into the maximum range of a 32bit unsigned integer ({:e}).", first_num, u32::MAX)
-            },
+            format!("The factorial of this number ({}) is too large to be calculated. The largest supported number is {}.", first_num, MAX_FACTORIAL_INPUT),
             FunctionErrorType::FactorialError,
         ),
         FunctionErrorType::OverflowInf | FunctionErrorType::UnderflowInf => {
-            let mut error_message = format!("The {} of ", operation_name.unwrap());
-            if !(0.00001..=10000.0).contains(&first_num) {
-                error_message.push_str(format!("{:e}", first_num).as_str());
-            } else {
-                error_message.push_str(format!("{}", first_num).as_str());
-            }
-            error_message.push_str(" and ");
-            if !(0.00001..=10000.0).contains(&second_num.unwrap()) {
-                error_message.push_str(format!("{:e}", second_num.unwrap()).as_str());
-            } else {
-                error_message.push_str(format!("{}", second_num.unwrap()).as_str());
+            let mut error_message = format!(
+                "The {} of {}",
+                operation_name.unwrap(),
+                format_number(first_num)
+            );
+            if let Some(second_num) = second_num {
+                error_message.push_str(" and ");
+                error_message.push_str(&format_number(second_num));
             }
             if matches!(error_type, FunctionErrorType::OverflowInf) {
                 error_message.push_str(format!(" results in an overflow of the 64bit floating point range ({:e}) and can only be displayed as {}.", f64::MAX, f64::INFINITY).as_str());
@@ -313,7 +1741,47 @@ fn create_error(
 
             FunctionError::new(error_message, error_type)
         }
-        FunctionErrorType::InvalidParameterType => {
+        FunctionErrorType::DomainError => match (operation_name, second_num) {
+            (Some("root"), Some(0.0)) => FunctionError::new(
+                format!("The root of degree 0 is not defined. You tried to take the root of degree 0 of {} which has no result.", format_number(first_num)),
+                error_type,
+            ),
+            (Some("root"), Some(n)) => FunctionError::new(
+                format!("Even roots of negative numbers are not defined for real numbers. You tried to take the root of degree {} of {} which has no real result.", format_number(n), format_number(first_num)),
+                error_type,
+            ),
+            (operation_name, Some(second_num)) => FunctionError::new(
+                format!("The {} of {} and {} is not defined for real numbers.", operation_name.unwrap(), format_number(first_num), format_number(second_num)),
+                error_type,
+            ),
+            (operation_name, None) => FunctionError::new(
+                format!("The {} of {} is not defined for real numbers.", operation_name.unwrap(), format_number(first_num)),
+                error_type,
+            ),
+        },
+        FunctionErrorType::IndeterminateForm => FunctionError::new(
+            format!("The {} of {} and {} is an indeterminate form and has no value.", operation_name.unwrap(), format_number(first_num), format_number(second_num.unwrap())),
+            error_type,
+        ),
+        FunctionErrorType::LogarithmOfZero => FunctionError::new(
+            format!("The logarithm of zero is not defined, it tends towards negative infinity. You tried to take the {} of {}.", operation_name.unwrap(), format_number(first_num)),
+            error_type,
+        ),
+        FunctionErrorType::LogarithmOfNegative => FunctionError::new(
+            format!("Logarithms of negative numbers are not defined for real numbers. You tried to take the {} of {}.", operation_name.unwrap(), format_number(first_num)),
+            error_type,
+        ),
+        FunctionErrorType::InvalidLogarithmBase => FunctionError::new(
+            format!("The base of a logarithm has to be positive and not equal to 1. You tried to take the logarithm of {} to the base {}.", format_number(first_num), format_number(second_num.unwrap())),
+            error_type,
+        ),
+        FunctionErrorType::InvalidParameterType
+        | FunctionErrorType::RecursionLimitExceeded
+        | FunctionErrorType::InvalidNumberOfParams
+        | FunctionErrorType::DimensionMismatch
+        | FunctionErrorType::SingularMatrix
+        | FunctionErrorType::NoConvergence
+        | FunctionErrorType::InfinitelyManySolutions => {
             panic!("Not a error from a math function.")
         }
     }
@@ -327,8 +1795,125 @@ mod tests {
     fn test_factorial() {
         assert_eq!(factorial(0).unwrap().get_u32().unwrap(), 1);
         assert_eq!(factorial(5).unwrap().get_u32().unwrap(), 120);
-        let err = factorial(500).unwrap_err();
-        assert_eq!(err.error, "The factorial of this number (500) is too large to fit into the maximum range of a 32bit unsigned integer (4.294967295e9).".to_string());
+        assert_eq!(
+            factorial(25).unwrap().to_string(),
+            "15511210043330985984000000"
+        );
+        assert_eq!(factorial(500).unwrap().to_string().len(), 1135);
+        let err = factorial(30000).unwrap_err();
+        assert_eq!(err.error, "The factorial of this number (30000) is too large to be calculated. The largest supported number is 20000.".to_string());
+    }
+
+    #[test]
+    fn test_integer_arithmetic() {
+        let a = BigInt::from(u64::MAX);
+        let b = BigInt::from(2);
+        assert_eq!(integer_addition(&a, &b).to_string(), "18446744073709551617");
+        assert_eq!(
+            integer_division(&BigInt::from(7), &b).unwrap().to_string(),
+            "3.5"
+        );
+        assert_eq!(
+            integer_division(&BigInt::from(10), &b).unwrap().to_string(),
+            "5"
+        );
+        assert_eq!(
+            integer_modulo_euclid(&BigInt::from(-7), &BigInt::from(-3))
+                .unwrap()
+                .to_string(),
+            "2"
+        );
+        assert_eq!(
+            integer_exponentiation(&b, &BigInt::from(100))
+                .unwrap()
+                .to_string(),
+            "1267650600228229401496703205376"
+        );
+        assert_eq!(
+            integer_exponentiation(&b, &BigInt::from(-2))
+                .unwrap()
+                .to_string(),
+            "0.25"
+        );
+        assert!(integer_modulo(&a, &BigInt::zero()).is_err());
+        assert!(integer_to_f64(&BigInt::from(10).pow(400)).is_err());
+    }
+
+    #[test]
+    fn test_rational_arithmetic() {
+        let third = BigRational::new(BigInt::from(1), BigInt::from(3));
+        let half = BigRational::new(BigInt::from(1), BigInt::from(2));
+        assert_eq!(rational_addition(&third, &half).to_string(), "5/6");
+        assert_eq!(rational_addition(&half, &half).to_string(), "1");
+        assert_eq!(rational_division(&third, &half).unwrap().to_string(), "2/3");
+        assert_eq!(
+            rational_modulo_euclid(&-half.clone(), &third)
+                .unwrap()
+                .to_string(),
+            "1/6"
+        );
+        assert_eq!(
+            rational_exponentiation(&half, &BigRational::from(BigInt::from(-3)))
+                .unwrap()
+                .to_string(),
+            "8"
+        );
+        assert!(rational_division(&half, &BigRational::zero()).is_err());
+
+        assert_eq!(
+            to_fraction(FunctionReturnType::F64(0.1))
+                .unwrap()
+                .to_string(),
+            "1/10"
+        );
+        assert_eq!(
+            to_fraction(FunctionReturnType::F64(1.0 / 3.0))
+                .unwrap()
+                .to_string(),
+            "1/3"
+        );
+        assert_eq!(
+            to_fraction(FunctionReturnType::F64(-2.75))
+                .unwrap()
+                .to_string(),
+            "-11/4"
+        );
+        assert!(to_fraction(FunctionReturnType::F64(f64::NAN)).is_err());
+        assert_eq!(
+            to_float(FunctionReturnType::Rational(third))
+                .unwrap()
+                .to_string(),
+            "0.3333333333333333"
+        );
+    }
+
+    #[test]
+    fn test_complex_numbers() {
+        let i = Complex64::i();
+        assert_eq!(complex_multiplication(i, i).to_string(), "-1");
+        assert_eq!(
+            complex_addition(Complex64::from(3.0), i * 4.0).to_string(),
+            "3 + 4i"
+        );
+        assert_eq!(
+            complex_subtraction(Complex64::from(1.0), i).to_string(),
+            "1 - i"
+        );
+        assert_eq!(complex_absolute(Complex64::new(3.0, 4.0)).to_string(), "5");
+        assert_eq!(complex_square_root(Complex64::from(-4.0)).to_string(), "2i");
+        assert_eq!(
+            complex_exponentiation(i, Complex64::from(3.0))
+                .unwrap()
+                .to_string(),
+            "-i"
+        );
+        assert_eq!(argument(-i, &AngleMode::Degrees).to_string(), "-90");
+        assert_eq!(
+            to_polar(i * 2.0, &AngleMode::Degrees).to_string(),
+            "[2, 90]"
+        );
+        assert!(complex_division(i, Complex64::zero()).is_err());
+        assert!(complex_natural_logarithm(Complex64::zero()).is_err());
     }
 
     #[test]
@@ -343,4 +1928,177 @@ mod tests {
         let err = addition(f64::MAX, f64::MAX - 1.0).unwrap_err();
         assert_eq!(err.error, format!("The addition of {:e} and {:e} results in an overflow of the 64bit floating point range ({:e}) and can only be displayed as {}.",f64::MAX, f64::MAX - 1.0, f64::MAX, f64::INFINITY));
     }
+
+    #[test]
+    fn test_exponentiation() {
+        assert_eq!(
+            exponentiation(2.0, 10.0).unwrap().get_f64().unwrap(),
+            1024.0
+        );
+        assert_eq!(exponentiation(4.0, -0.5).unwrap().get_f64().unwrap(), 0.5);
+
+        let err = exponentiation(10.0, 400.0).unwrap_err();
+        assert!(matches!(err.error_type, FunctionErrorType::OverflowInf));
+
+        let err = exponentiation(-8.0, 0.5).unwrap_err();
+        assert_eq!(
+            err.error,
+            "The exponentiation of -8 and 0.5 is not defined for real numbers.".to_string()
+        );
+
+        let err = exponentiation(0.0, -1.0).unwrap_err();
+        assert!(matches!(err.error_type, FunctionErrorType::DivisionByZero));
+        assert_eq!(
+            err.error,
+            "You cannot divide by zero. You tried to raise 0 to the power of -1, which divides 1 by 0 and has no result."
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_root() {
+        assert_eq!(square_root(16.0).unwrap().get_f64().unwrap(), 4.0);
+        assert_eq!(root(-27.0, 3.0).unwrap().get_f64().unwrap(), -3.0);
+        assert_eq!(cube_root(-8.0).get_f64().unwrap(), -2.0);
+
+        let err = square_root(-4.0).unwrap_err();
+        assert!(matches!(err.error_type, FunctionErrorType::DomainError));
+        assert_eq!(err.error, "Even roots of negative numbers are not defined for real numbers. You tried to take the root of degree 2 of -4 which has no real result.".to_string());
+    }
+
+    #[test]
+    fn test_trigonometric_functions() {
+        let value =
+            |result: Result<FunctionReturnType, FunctionError>| result.unwrap().get_f64().unwrap();
+        let degrees = &AngleMode::Degrees;
+        let gradians = &AngleMode::Gradians;
+
+        assert_eq!(value(sine(90.0, degrees)), 1.0);
+        assert_eq!(value(sine(180.0, degrees)), 0.0);
+        assert_eq!(value(cosine(200.0, gradians)), -1.0);
+        assert_eq!(value(sine(30.0, degrees)), 0.5);
+        assert_eq!(value(sine(-210.0, degrees)), 0.5);
+        assert_eq!(value(cosine(420.0, degrees)), 0.5);
+        assert_eq!(
+            value(cosine(50.0, gradians)),
+            std::f64::consts::FRAC_1_SQRT_2
+        );
+        assert_eq!(value(tangent(135.0, degrees)), -1.0);
+        assert_eq!(value(tangent(240.0, degrees)), 3.0_f64.sqrt());
+        assert_eq!(
+            arctangent2(1.0, 1.0, &AngleMode::Degrees)
+                .get_f64()
+                .unwrap(),
+            45.0
+        );
+
+        let err = tangent(90.0, &AngleMode::Degrees).unwrap_err();
+        assert_eq!(
+            err.error,
+            "The tangent of 90 is not defined for real numbers.".to_string()
+        );
+
+        let err = arcsine(2.0, &AngleMode::Radians).unwrap_err();
+        assert_eq!(
+            err.error,
+            "The arcsine of 2 is not defined for real numbers.".to_string()
+        );
+    }
+
+    #[test]
+    fn test_hyperbolic_functions() {
+        assert_eq!(hyperbolic_sine(0.0).unwrap().get_f64().unwrap(), 0.0);
+        assert_eq!(area_hyperbolic_cosine(1.0).unwrap().get_f64().unwrap(), 0.0);
+        assert!(matches!(
+            hyperbolic_cosine(1000.0).unwrap_err().error_type,
+            FunctionErrorType::OverflowInf
+        ));
+        assert!(matches!(
+            area_hyperbolic_tangent(1.0).unwrap_err().error_type,
+            FunctionErrorType::DomainError
+        ));
+    }
+
+    #[test]
+    fn test_logarithm() {
+        assert_eq!(natural_logarithm(1.0).unwrap().get_f64().unwrap(), 0.0);
+        assert_eq!(decimal_logarithm(1000.0).unwrap().get_f64().unwrap(), 3.0);
+        assert_eq!(binary_logarithm(8.0).unwrap().get_f64().unwrap(), 3.0);
+        assert_eq!(logarithm(81.0, 3.0).unwrap().get_f64().unwrap(), 4.0);
+
+        let err = natural_logarithm(0.0).unwrap_err();
+        assert!(matches!(err.error_type, FunctionErrorType::LogarithmOfZero));
+
+        let err = decimal_logarithm(-10.0).unwrap_err();
+        assert_eq!(err.error, "Logarithms of negative numbers are not defined for real numbers. You tried to take the decimal logarithm of -10.".to_string());
+
+        let err = logarithm(5.0, 1.0).unwrap_err();
+        assert!(matches!(
+            err.error_type,
+            FunctionErrorType::InvalidLogarithmBase
+        ));
+
+        let err = natural_logarithm_one_plus(-1.0).unwrap_err();
+        assert_eq!(err.error, "The logarithm of zero is not defined, it tends towards negative infinity. You tried to take the Ln1p of -1.".to_string());
+
+        let err = natural_logarithm_one_plus(-2.0).unwrap_err();
+        assert_eq!(err.error, "Logarithms of negative numbers are not defined for real numbers. You tried to take the Ln1p of -2.".to_string());
+    }
+
+    #[test]
+    fn test_exponential() {
+        assert_eq!(exponential(0.0).unwrap().get_f64().unwrap(), 1.0);
+        assert_eq!(exponential_minus_one(0.0).unwrap().get_f64().unwrap(), 0.0);
+        assert!(matches!(
+            exponential(1000.0).unwrap_err().error_type,
+            FunctionErrorType::OverflowInf
+        ));
+    }
+
+    #[test]
+    fn test_infinite_operands() {
+        assert_eq!(
+            addition(f64::INFINITY, 1.0).unwrap().get_f64().unwrap(),
+            f64::INFINITY
+        );
+        assert_eq!(
+            multiplication(-2.0, f64::INFINITY)
+                .unwrap()
+                .get_f64()
+                .unwrap(),
+            f64::NEG_INFINITY
+        );
+        assert!(multiplication(f64::MAX, 2.0).is_err());
+
+        let is_indeterminate = |result: Result<FunctionReturnType, FunctionError>| {
+            matches!(
+                result.unwrap_err().error_type,
+                FunctionErrorType::IndeterminateForm
+            )
+        };
+        assert!(is_indeterminate(subtraction(f64::INFINITY, f64::INFINITY)));
+        assert!(is_indeterminate(multiplication(f64::INFINITY, 0.0)));
+        assert!(is_indeterminate(division(f64::INFINITY, f64::INFINITY)));
+        assert!(is_indeterminate(modulo(f64::INFINITY, 2.0)));
+        assert!(matches!(
+            modulo(5.5, 0.0).unwrap_err().error_type,
+            FunctionErrorType::DivisionByZero
+        ));
+        assert_eq!(
+            division(0.0, 0.0).unwrap_err().error,
+            "You cannot divide by zero. You tried to divide 0 by 0 which has no result."
+                .to_string()
+        );
+        assert_eq!(
+            division(123456.5, 0.0).unwrap_err().error,
+            "You cannot divide by zero. You tried to divide 1.234565e5 by 0 which has no result."
+                .to_string()
+        );
+
+        let err = sine(f64::INFINITY, &AngleMode::Radians).unwrap_err();
+        assert_eq!(
+            err.error,
+            "The sine of inf is not defined for real numbers.".to_string()
+        );
+    }
 }
@@ -0,0 +1,35 @@
+//! A command line math interpreter that can also be used as a library.
+//!
+//! A [`Session`] evaluates inputs one after another like the REPL does, so variables and functions
+//! defined by an input can be used by all following inputs.
+//!
+//! ```
+//! let mut session = clmath::Session::new();
+//!
+//! session.evaluate("f(x) = x^2 + 1").unwrap();
+//! let value = session.evaluate("f(3)").unwrap().unwrap();
+//!
+//! assert_eq!(value.to_string(), "10");
+//! assert_eq!(value.to_f64(), Some(10.0));
+//! ```
+
+mod commands;
+mod decimal;
+mod environment;
+mod error;
+mod executor;
+mod functions;
+mod matrix;
+mod numeric;
+mod parser;
+mod session;
+mod symbolic;
+mod tokenizer;
+mod units;
+
+pub use error::{
+    CLMathError, CommandError, CommandErrorType, Error, FunctionError, FunctionErrorType, IoError,
+    ParserError, ParserErrorType, TokenKind, TokenizerError, TokenizerErrorType,
+};
+pub use session::{Session, Value};
+pub use tokenizer::Span;
@@ -1,54 +1,337 @@
-mod error;
-mod executor;
-mod functions;
-mod parser;
-mod tokenizer;
+use clmath::{CLMathError, Error, IoError, Session};
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::FileHistory;
+use rustyline::validate::Validator;
+use rustyline::{CompletionType, Config, Context, Editor, Helper};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
-use error::{CLMathError, IoError};
-use functions::FunctionReturnType;
-use std::io;
+const USAGE: &str = "Usage: clmath [-e EXPRESSION]... | clmath [FILE]
+
+Without arguments the inputs are read interactively, or line by line if the standard input is not a terminal.
+
+  -e EXPRESSION   Evaluates the expression, can be repeated
+  FILE            Evaluates the lines of the file, '-' reads the standard input
+  -h, --help      Prints this help";
+
+/// The exit status of invalid arguments, inputs with errors exit with 1
+const USAGE_ERROR: u8 = 2;
+
+const PROMPT: &str = "> ";
+
+/// The largest number of inputs kept in the history
+const MAX_HISTORY_SIZE: usize = 1000;
+
+/// Where the inputs are read from
+#[derive(Debug, PartialEq)]
+enum Mode {
+    Interactive,
+    Expressions(Vec<String>),
+    Script(String),
+    Help,
+}
+
+fn main() -> ExitCode {
+    let mode = match parse_args(std::env::args().skip(1)) {
+        Ok(mode) => mode,
+        Err(message) => {
+            eprintln!("{message}\n\n{USAGE}");
+            return ExitCode::from(USAGE_ERROR);
+        }
+    };
+
+    let mut session = Session::new();
+
+    match mode {
+        Mode::Help => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Mode::Interactive => run_interactive(&mut session),
+        Mode::Expressions(expressions) => {
+            run_script(&mut session, expressions.into_iter().map(Ok), "-e")
+        }
+        Mode::Script(path) if path == "-" => run_script(&mut session, stdin_lines(), "<stdin>"),
+        Mode::Script(path) => match File::open(&path) {
+            Ok(file) => run_script(&mut session, BufReader::new(file).lines(), &path),
+            Err(err) => {
+                eprintln!(
+                    "{}",
+                    Error::from(CLMathError::Io(IoError::CannotReadFile {
+                        path,
+                        error: err.to_string(),
+                    }))
+                );
+                ExitCode::FAILURE
+            }
+        },
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Mode, String> {
+    let mut expressions = Vec::new();
+    let mut path = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Mode::Help),
+            "-e" => match args.next() {
+                Some(expression) => expressions.push(expression),
+                None => return Err("The option '-e' needs an expression.".to_string()),
+            },
+            option if option.starts_with('-') && option != "-" => {
+                return Err(format!("Unknown option '{option}'."))
+            }
+            _ if path.is_some() => return Err("Only one file can be evaluated.".to_string()),
+            _ => path = Some(arg),
+        }
+    }
+
+    match (expressions.is_empty(), path) {
+        (true, None) if io::stdin().is_terminal() => Ok(Mode::Interactive),
+        (true, None) => Ok(Mode::Script("-".to_string())),
+        (true, Some(path)) => Ok(Mode::Script(path)),
+        (false, None) => Ok(Mode::Expressions(expressions)),
+        (false, Some(_)) => Err("Expressions and a file cannot be evaluated together.".to_string()),
+    }
+}
+
+/// Completes the names of built-in functions, variables and user-defined functions
+struct InputHelper {
+    names: Vec<String>,
+}
+
+impl Completer for InputHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let start = line[..pos]
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| c.is_alphanumeric())
+            .last()
+            .map_or(pos, |(idx, _)| idx);
+        let prefix = &line[start..pos];
+
+        if prefix.is_empty() {
+            return Ok((pos, Vec::new()));
+        }
+
+        let candidates = self
+            .names
+            .iter()
+            .filter(|name| name.starts_with(prefix))
+            .cloned()
+            .collect();
+
+        Ok((start, candidates))
+    }
+}
+
+impl Hinter for InputHelper {
+    type Hint = String;
+}
+
+impl Highlighter for InputHelper {}
+
+impl Validator for InputHelper {}
+
+impl Helper for InputHelper {}
+
+/// Returns the file the inputs of interactive sessions are kept in, if the user has a data directory
+fn history_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("clmath").join("history"))
+}
+
+/// Reads inputs with line editing, a history that is searched with Ctrl-R and tab completion
+fn run_interactive(session: &mut Session) -> ExitCode {
+    let config = Config::builder()
+        .max_history_size(MAX_HISTORY_SIZE)
+        .and_then(|builder| builder.history_ignore_dups(true))
+        .map(|builder| {
+            builder
+                .auto_add_history(true)
+                .completion_type(CompletionType::List)
+                .build()
+        });
+
+    let mut editor = match config.and_then(Editor::<InputHelper, FileHistory>::with_config) {
+        Ok(editor) => editor,
+        Err(err) => {
+            eprintln!("{}", line_editor_error(&err));
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut history_path = history_path();
+    if let Some(path) = &history_path {
+        // There is no history before the first session
+        let _ = editor.load_history(path);
+    }
+
+    editor.set_helper(Some(InputHelper {
+        names: session.completions(""),
+    }));
 
-fn main() {
     loop {
-        let input = get_input();
-    
-        // if invalid or no input get new input
-        if input.is_none() {
-            continue;
-        }
-        
-        if input.as_ref().unwrap() == "exit" {
-            break;
-        }
-    
-        match tokenizer::tokenize(input.unwrap()) {
-            Ok(tokens) => {
-                match parser::Parser::parse(tokens) {
-                    Ok(expression) => match executor::execute(expression) {
-                        Ok(result) => print_result(result),
-                        Err(err) => error::handle_error(err),
-                    },
-                    Err(err) => error::handle_error(err),
-                }
+        let input = match editor.readline(PROMPT) {
+            Ok(line) => line.trim().to_string(),
+            // Ctrl-C discards the current input
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => return ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("{}", line_editor_error(&err));
+                return ExitCode::FAILURE;
             }
-            Err(err) => error::handle_errors(err),
         };
+
+        // The history is saved after every input, so that it is kept however the session ends
+        if let Some(path) = &history_path {
+            if let Err(err) = save_history(&mut editor, path) {
+                eprintln!("{err}");
+                history_path = None;
+            }
+        }
+
+        if input == "exit" {
+            return ExitCode::SUCCESS;
+        }
+
+        match run(session, &input) {
+            Ok(Some(output)) => println!("{output}"),
+            Ok(None) => println!(),
+            Err(err) => println!("{err}"),
+        }
+
+        if let Some(helper) = editor.helper_mut() {
+            helper.names = session.completions("");
+        }
     }
 }
 
-fn get_input() -> Option<String> {
-    let mut input = String::new();
-    match io::stdin().read_line(&mut input) {
-        Ok(_) => return Some(input.trim().to_string()),
-        Err(err) => error::handle_error(CLMathError::Io(IoError::InvalidUTF8(err.to_string()))),
-    };
-    None
+fn save_history(editor: &mut Editor<InputHelper, FileHistory>, path: &Path) -> Result<(), Error> {
+    path.parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .map_err(ReadlineError::from)
+        .and_then(|_| editor.save_history(path))
+        .map_err(|err| {
+            Error::from(CLMathError::Io(IoError::CannotWriteFile {
+                path: path.display().to_string(),
+                error: err.to_string(),
+            }))
+        })
+}
+
+fn line_editor_error(err: &ReadlineError) -> Error {
+    Error::from(CLMathError::Io(IoError::LineEditor(err.to_string())))
+}
+
+/// Evaluates the inputs one after another and prints their results. Stops at the first error, which is printed
+/// with the name of the source and the line number of the input.
+fn run_script(
+    session: &mut Session,
+    lines: impl Iterator<Item = io::Result<String>>,
+    name: &str,
+) -> ExitCode {
+    for (idx, line) in lines.enumerate() {
+        let result = match line {
+            Ok(line) if line.trim() == "exit" => break,
+            Ok(line) => run(session, line.trim()),
+            Err(err) => Err(read_error(&err)),
+        };
+
+        match result {
+            Ok(Some(output)) => println!("{output}"),
+            Ok(None) => (),
+            Err(err) => {
+                eprintln!("{}:{}: {}", name, idx + 1, err);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
 }
 
-fn print_result(result: Option<FunctionReturnType>) {
-    if let Some(val) = result {
-        println!("{val}")
+/// Evaluates an expression or runs a command and returns the text to print
+fn run(session: &mut Session, input: &str) -> Result<Option<String>, Error> {
+    if Session::is_command(input) {
+        session.run_command(input).map(Some)
     } else {
-        println!();
+        session
+            .evaluate(input)
+            .map(|value| value.map(|value| value.to_string()))
+    }
+}
+
+/// Returns the lines of the standard input until it is closed
+fn stdin_lines() -> impl Iterator<Item = io::Result<String>> {
+    io::stdin().lock().lines()
+}
+
+/// Converts an error of reading an input, only invalid data is invalid UTF-8 since lines are read as strings
+fn read_error(err: &io::Error) -> Error {
+    let error = match err.kind() {
+        io::ErrorKind::InvalidData => IoError::InvalidUTF8(err.to_string()),
+        _ => IoError::CannotReadInput(err.to_string()),
+    };
+
+    Error::from(CLMathError::Io(error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Mode, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse(&["-h"]), Ok(Mode::Help));
+        assert_eq!(parse(&["-e", "1", "--help"]), Ok(Mode::Help));
+        assert_eq!(
+            parse(&["-e", "x = 2", "-e", "x^2"]),
+            Ok(Mode::Expressions(vec![
+                "x = 2".to_string(),
+                "x^2".to_string()
+            ]))
+        );
+        assert_eq!(
+            parse(&["input.txt"]),
+            Ok(Mode::Script("input.txt".to_string()))
+        );
+        assert_eq!(parse(&["-"]), Ok(Mode::Script("-".to_string())));
+
+        assert!(parse(&["-e"]).is_err());
+        assert!(parse(&["-x"]).is_err());
+        assert!(parse(&["a.txt", "b.txt"]).is_err());
+        assert!(parse(&["-e", "1", "a.txt"]).is_err());
+    }
+
+    #[test]
+    fn test_read_error() {
+        let invalid_data = io::Error::new(io::ErrorKind::InvalidData, "invalid UTF-8");
+        assert!(matches!(
+            read_error(&invalid_data).errors(),
+            [CLMathError::Io(IoError::InvalidUTF8(_))]
+        ));
+
+        let directory = io::Error::new(io::ErrorKind::IsADirectory, "Is a directory");
+        assert!(matches!(
+            read_error(&directory).errors(),
+            [CLMathError::Io(IoError::CannotReadInput(_))]
+        ));
     }
 }
@@ -0,0 +1,642 @@
+use num_bigint::BigInt;
+use num_traits::{One, Zero};
+
+use crate::{
+    error::{FunctionError, FunctionErrorType},
+    functions::{
+        rational_division, rational_to_f64, ElementaryFunc, FunctionReturnType, MAX_INTEGER_BITS,
+    },
+};
+
+/// The rows of a matrix, every row has the same number of elements
+pub type Matrix = Vec<Vec<FunctionReturnType>>;
+
+/// Runs an elementary function on two values the same way the executor does, so that
+/// matrices of integers, fractions, decimals, complex numbers or quantities all keep their type
+pub type Arithmetic<'a> = dyn FnMut(
+        &ElementaryFunc,
+        FunctionReturnType,
+        FunctionReturnType,
+    ) -> Result<FunctionReturnType, FunctionError>
+    + 'a;
+
+/// Floating point numbers with a smaller absolute value are treated as zero during elimination
+const ZERO_TOLERANCE: f64 = 1e-12;
+
+/// Matrices with symbolic entries are expanded along their cofactors, whose number of terms grows factorially with the size
+const MAX_SYMBOLIC_SIZE: usize = 6;
+
+/// Returns true if the values are the rows of a matrix, i.e. if every value is a list
+pub fn is_matrix(values: &[FunctionReturnType]) -> bool {
+    !values.is_empty()
+        && values
+            .iter()
+            .all(|value| matches!(value, FunctionReturnType::List(_)))
+}
+
+/// Returns the value as a matrix if it is a non-empty list of rows of the same non-zero length
+pub fn get_matrix(value: FunctionReturnType) -> Result<Matrix, FunctionError> {
+    let rows = value.get_list()?;
+
+    if !is_matrix(&rows) {
+        return Err(create_shape_error(
+            "Expected a matrix, i.e. a list of rows like [[1, 2], [3, 4]], as a parameter",
+        ));
+    }
+
+    let rows = rows
+        .into_iter()
+        .map(|row| row.get_list())
+        .collect::<Result<Matrix, _>>()?;
+
+    if rows[0].is_empty() || rows.iter().any(|row| row.len() != rows[0].len()) {
+        return Err(create_shape_error(
+            "All rows of a matrix have to contain the same number of elements",
+        ));
+    }
+
+    Ok(rows)
+}
+
+/// Returns the value as a square matrix
+pub fn get_square_matrix(value: FunctionReturnType) -> Result<Matrix, FunctionError> {
+    let matrix = get_matrix(value)?;
+
+    if matrix.len() != matrix[0].len() {
+        return Err(create_shape_error(&format!(
+            "Expected a square matrix but got a {}x{} matrix",
+            matrix.len(),
+            matrix[0].len()
+        )));
+    }
+
+    Ok(matrix)
+}
+
+/// Returns the value as a vector, i.e. a list of values that are not lists
+pub fn get_vector(value: FunctionReturnType) -> Result<Vec<FunctionReturnType>, FunctionError> {
+    let values = value.get_list()?;
+
+    if values
+        .iter()
+        .any(|value| matches!(value, FunctionReturnType::List(_)))
+    {
+        return Err(create_shape_error(
+            "Expected a vector, i.e. a list of numbers like [1, 2, 3], as a parameter",
+        ));
+    }
+
+    Ok(values)
+}
+
+fn matrix_value(matrix: Matrix) -> FunctionReturnType {
+    FunctionReturnType::List(matrix.into_iter().map(FunctionReturnType::List).collect())
+}
+
+fn create_shape_error(message: &str) -> FunctionError {
+    FunctionError::new(
+        format!("{}.", message),
+        FunctionErrorType::DimensionMismatch,
+    )
+}
+
+fn zero() -> FunctionReturnType {
+    FunctionReturnType::BigInt(BigInt::zero())
+}
+
+fn one() -> FunctionReturnType {
+    FunctionReturnType::BigInt(BigInt::one())
+}
+
+fn identity(size: usize) -> Matrix {
+    (0..size)
+        .map(|row| {
+            (0..size)
+                .map(|column| if row == column { one() } else { zero() })
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the absolute value of the number as a floating point number to choose pivots
+fn magnitude(value: &FunctionReturnType) -> f64 {
+    match value {
+        FunctionReturnType::Complex(num) => num.norm(),
+        FunctionReturnType::Quantity(quantity) => quantity.value.abs(),
+        value => value.clone().get_f64().map_or(0.0, f64::abs),
+    }
+}
+
+fn is_zero(value: &FunctionReturnType) -> bool {
+    match value {
+        // Symbolic expressions that are zero are simplified to numbers
+        FunctionReturnType::Symbolic(_) => false,
+        value => match value.get_exact() {
+            Some(num) => num.is_zero(),
+            None => magnitude(value) < ZERO_TOLERANCE,
+        },
+    }
+}
+
+/// Returns the first symbolic entry of the matrix, e.g. `x` in [[x, 1], [1, 1]]
+fn find_symbolic(matrix: &Matrix) -> Option<&FunctionReturnType> {
+    matrix
+        .iter()
+        .flatten()
+        .find(|value| matches!(value, FunctionReturnType::Symbolic(_)))
+}
+
+/// Returns an error if a matrix with symbolic entries is too large to be expanded along its cofactors
+fn check_symbolic_size(matrix: &Matrix, operation_name: &str) -> Result<(), FunctionError> {
+    if matrix.len() > MAX_SYMBOLIC_SIZE {
+        return Err(create_shape_error(&format!(
+            "The {} of a matrix with expressions is only calculated up to a size of {}x{}, but the matrix is {}x{}",
+            operation_name,
+            MAX_SYMBOLIC_SIZE,
+            MAX_SYMBOLIC_SIZE,
+            matrix.len(),
+            matrix.len()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Returns the matrix without the row and the column
+fn minor(matrix: &Matrix, row: usize, column: usize) -> Matrix {
+    matrix
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| *idx != row)
+        .map(|(_, values)| {
+            values
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| *idx != column)
+                .map(|(_, value)| value.clone())
+                .collect()
+        })
+        .collect()
+}
+
+/// Calculates the determinant by cofactor expansion along the first row. Unlike elimination it
+/// needs no pivots, so it also works for matrices with symbolic entries.
+fn cofactor_determinant(
+    matrix: &Matrix,
+    arithmetic: &mut Arithmetic,
+) -> Result<FunctionReturnType, FunctionError> {
+    if matrix.is_empty() {
+        return Ok(one());
+    }
+
+    let mut determinant = zero();
+    for (column, value) in matrix[0].iter().enumerate() {
+        let cofactor = cofactor_determinant(&minor(matrix, 0, column), arithmetic)?;
+        let term = arithmetic(&ElementaryFunc::Multiplication, value.clone(), cofactor)?;
+        let function = if column % 2 == 0 {
+            ElementaryFunc::Addition
+        } else {
+            ElementaryFunc::Subtraction
+        };
+        determinant = arithmetic(&function, determinant, term)?;
+    }
+
+    Ok(determinant)
+}
+
+/// Divides exact numbers as fractions, so that eliminating a matrix of integers stays exact
+fn divide(
+    a: FunctionReturnType,
+    b: FunctionReturnType,
+    arithmetic: &mut Arithmetic,
+) -> Result<FunctionReturnType, FunctionError> {
+    match (a.get_exact(), b.get_exact()) {
+        (Some(a), Some(b)) => rational_division(&a, &b),
+        _ => arithmetic(&ElementaryFunc::Division, a, b),
+    }
+}
+
+/// Calculates the sum of the products of the corresponding values
+fn sum_of_products(
+    a: impl Iterator<Item = FunctionReturnType>,
+    b: impl Iterator<Item = FunctionReturnType>,
+    arithmetic: &mut Arithmetic,
+) -> Result<FunctionReturnType, FunctionError> {
+    a.zip(b).try_fold(zero(), |sum, (a, b)| {
+        let product = arithmetic(&ElementaryFunc::Multiplication, a, b)?;
+        arithmetic(&ElementaryFunc::Addition, sum, product)
+    })
+}
+
+/// Reduces the matrix to reduced row echelon form with Gauss-Jordan elimination. Only the first
+/// `columns` columns are used for pivots, the remaining ones are transformed along, e.g. the
+/// right sides of a system of equations. Returns the number of pivots, i.e. the rank, and the
+/// determinant of the pivot columns, which is zero if they do not all contain a pivot.
+fn reduce(
+    matrix: &mut Matrix,
+    columns: usize,
+    arithmetic: &mut Arithmetic,
+) -> Result<(usize, FunctionReturnType), FunctionError> {
+    if let Some(value) = find_symbolic(matrix) {
+        return Err(FunctionError::new(
+            format!(
+                "Only matrices of numbers can be eliminated, but the matrix contains the expression {}.",
+                value
+            ),
+            FunctionErrorType::InvalidParameterType,
+        ));
+    }
+
+    let mut rank = 0;
+    let mut determinant = one();
+
+    for column in 0..columns {
+        // The pivot with the largest magnitude keeps the errors of floating point numbers small
+        let pivot_row = (rank..matrix.len())
+            .filter(|row| !is_zero(&matrix[*row][column]))
+            .max_by(|a, b| {
+                magnitude(&matrix[*a][column]).total_cmp(&magnitude(&matrix[*b][column]))
+            });
+
+        let Some(pivot_row) = pivot_row else {
+            determinant = zero();
+            continue;
+        };
+
+        if pivot_row != rank {
+            matrix.swap(pivot_row, rank);
+            determinant = arithmetic(
+                &ElementaryFunc::Multiplication,
+                determinant,
+                FunctionReturnType::BigInt(-BigInt::one()),
+            )?;
+        }
+
+        let pivot = matrix[rank][column].clone();
+        determinant = arithmetic(&ElementaryFunc::Multiplication, determinant, pivot.clone())?;
+
+        for value in matrix[rank].iter_mut() {
+            *value = divide(value.clone(), pivot.clone(), arithmetic)?;
+        }
+
+        for row in 0..matrix.len() {
+            let factor = matrix[row][column].clone();
+            if row == rank || is_zero(&factor) {
+                continue;
+            }
+
+            for idx in 0..matrix[row].len() {
+                let product = arithmetic(
+                    &ElementaryFunc::Multiplication,
+                    factor.clone(),
+                    matrix[rank][idx].clone(),
+                )?;
+                matrix[row][idx] = arithmetic(
+                    &ElementaryFunc::Subtraction,
+                    matrix[row][idx].clone(),
+                    product,
+                )?;
+            }
+        }
+
+        rank += 1;
+    }
+
+    Ok((rank, determinant))
+}
+
+fn create_singular_error(operation_name: &str) -> FunctionError {
+    FunctionError::new(
+        format!(
+            "The {} is not possible, because the matrix is singular, i.e. its determinant is zero.",
+            operation_name
+        ),
+        FunctionErrorType::SingularMatrix,
+    )
+}
+
+/// Calculates the dot product of two vectors of the same length
+pub fn dot(
+    a: Vec<FunctionReturnType>,
+    b: Vec<FunctionReturnType>,
+    arithmetic: &mut Arithmetic,
+) -> Result<FunctionReturnType, FunctionError> {
+    if a.len() != b.len() {
+        return Err(create_shape_error(&format!(
+            "The dot product needs two vectors of the same length but got vectors of length {} and {}",
+            a.len(),
+            b.len()
+        )));
+    }
+
+    sum_of_products(a.into_iter(), b.into_iter(), arithmetic)
+}
+
+/// Calculates the cross product of two vectors of length 3
+pub fn cross(
+    a: Vec<FunctionReturnType>,
+    b: Vec<FunctionReturnType>,
+    arithmetic: &mut Arithmetic,
+) -> Result<FunctionReturnType, FunctionError> {
+    if a.len() != 3 || b.len() != 3 {
+        return Err(create_shape_error(&format!(
+            "The cross product needs two vectors of length 3 but got vectors of length {} and {}",
+            a.len(),
+            b.len()
+        )));
+    }
+
+    let mut component = |i: usize, j: usize| {
+        let first = arithmetic(&ElementaryFunc::Multiplication, a[i].clone(), b[j].clone())?;
+        let second = arithmetic(&ElementaryFunc::Multiplication, a[j].clone(), b[i].clone())?;
+        arithmetic(&ElementaryFunc::Subtraction, first, second)
+    };
+
+    Ok(FunctionReturnType::List(vec![
+        component(1, 2)?,
+        component(2, 0)?,
+        component(0, 1)?,
+    ]))
+}
+
+/// Swaps the rows and columns of a matrix
+pub fn transpose(matrix: Matrix) -> FunctionReturnType {
+    let columns = matrix[0].len();
+    let mut transposed = vec![Vec::with_capacity(matrix.len()); columns];
+
+    for row in matrix {
+        for (column, value) in row.into_iter().enumerate() {
+            transposed[column].push(value);
+        }
+    }
+
+    matrix_value(transposed)
+}
+
+/// Calculates the sum of the diagonal of a square matrix
+pub fn trace(
+    matrix: Matrix,
+    arithmetic: &mut Arithmetic,
+) -> Result<FunctionReturnType, FunctionError> {
+    matrix
+        .into_iter()
+        .enumerate()
+        .try_fold(zero(), |sum, (idx, mut row)| {
+            arithmetic(&ElementaryFunc::Addition, sum, row.swap_remove(idx))
+        })
+}
+
+/// Multiplies two matrices. A vector on the left is a row vector and a vector on the right is a column vector,
+/// their products with a matrix are vectors again.
+pub fn multiply(
+    a: Vec<FunctionReturnType>,
+    b: Vec<FunctionReturnType>,
+    arithmetic: &mut Arithmetic,
+) -> Result<FunctionReturnType, FunctionError> {
+    let a_is_vector = !is_matrix(&a);
+    let b_is_vector = !is_matrix(&b);
+
+    let a = if a_is_vector {
+        vec![a]
+    } else {
+        get_matrix(FunctionReturnType::List(a))?
+    };
+    let b = if b_is_vector {
+        b.into_iter().map(|value| vec![value]).collect()
+    } else {
+        get_matrix(FunctionReturnType::List(b))?
+    };
+
+    if a[0].len() != b.len() {
+        return Err(create_shape_error(&format!(
+            "The product of a {}x{} and a {}x{} matrix is not defined, the number of columns of the first one has to match the number of rows of the second one",
+            a.len(),
+            a[0].len(),
+            b.len(),
+            b.first().map_or(0, Vec::len)
+        )));
+    }
+
+    let mut product = Matrix::with_capacity(a.len());
+    for row in &a {
+        let mut product_row = Vec::with_capacity(b[0].len());
+        for column in 0..b[0].len() {
+            product_row.push(sum_of_products(
+                row.iter().cloned(),
+                b.iter().map(|b_row| b_row[column].clone()),
+                arithmetic,
+            )?);
+        }
+        product.push(product_row);
+    }
+
+    Ok(match (a_is_vector, b_is_vector) {
+        (true, _) => FunctionReturnType::List(product.swap_remove(0)),
+        (false, true) => FunctionReturnType::List(
+            product
+                .into_iter()
+                .map(|mut row| row.swap_remove(0))
+                .collect(),
+        ),
+        (false, false) => matrix_value(product),
+    })
+}
+
+/// Raises a square matrix to an integer power by repeated squaring, negative powers are powers of the inverse
+pub fn power(
+    matrix: Matrix,
+    exponent: i64,
+    arithmetic: &mut Arithmetic,
+) -> Result<FunctionReturnType, FunctionError> {
+    let mut square = if exponent < 0 {
+        get_matrix(inverse(matrix, arithmetic)?)?
+    } else {
+        matrix
+    };
+    let mut result = identity(square.len());
+    let mut remaining = exponent.unsigned_abs();
+
+    // Every multiplication adds about the bits of the largest entry and of the size to the entries, e.g. of [[2]]^(10^30).
+    // Like powers of integers, powers with too large exact entries are calculated with floating point numbers.
+    let bits = square
+        .iter()
+        .flatten()
+        .filter_map(FunctionReturnType::get_exact)
+        .map(|num| num.numer().bits() + num.denom().bits())
+        .max()
+        .unwrap_or(0)
+        + u64::from(usize::BITS - square.len().leading_zeros());
+    if bits.saturating_mul(remaining) > MAX_INTEGER_BITS {
+        for value in square.iter_mut().flatten() {
+            if let Some(num) = value.get_exact() {
+                *value = FunctionReturnType::F64(rational_to_f64(&num)?);
+            }
+        }
+    }
+
+    while remaining > 0 {
+        if remaining % 2 == 1 {
+            result = get_matrix(multiply(
+                result.into_iter().map(FunctionReturnType::List).collect(),
+                square
+                    .iter()
+                    .cloned()
+                    .map(FunctionReturnType::List)
+                    .collect(),
+                arithmetic,
+            )?)?;
+        }
+
+        remaining /= 2;
+        if remaining > 0 {
+            square = get_matrix(multiply(
+                square
+                    .iter()
+                    .cloned()
+                    .map(FunctionReturnType::List)
+                    .collect(),
+                square.into_iter().map(FunctionReturnType::List).collect(),
+                arithmetic,
+            )?)?;
+        }
+    }
+
+    Ok(matrix_value(result))
+}
+
+/// Calculates the determinant of a square matrix
+pub fn determinant(
+    mut matrix: Matrix,
+    arithmetic: &mut Arithmetic,
+) -> Result<FunctionReturnType, FunctionError> {
+    if find_symbolic(&matrix).is_some() {
+        check_symbolic_size(&matrix, "determinant")?;
+        return cofactor_determinant(&matrix, arithmetic);
+    }
+
+    let columns = matrix.len();
+    Ok(reduce(&mut matrix, columns, arithmetic)?.1)
+}
+
+/// Calculates the inverse of a square matrix, which has to be non-singular
+pub fn inverse(
+    matrix: Matrix,
+    arithmetic: &mut Arithmetic,
+) -> Result<FunctionReturnType, FunctionError> {
+    let size = matrix.len();
+
+    if find_symbolic(&matrix).is_some() {
+        check_symbolic_size(&matrix, "inverse")?;
+        return symbolic_inverse(&matrix, arithmetic);
+    }
+
+    // Reducing the matrix next to the identity matrix turns the identity into the inverse
+    let mut augmented = matrix
+        .into_iter()
+        .zip(identity(size))
+        .map(|(mut row, identity_row)| {
+            row.extend(identity_row);
+            row
+        })
+        .collect::<Matrix>();
+
+    if reduce(&mut augmented, size, arithmetic)?.0 < size {
+        return Err(create_singular_error("inversion"));
+    }
+
+    Ok(matrix_value(
+        augmented
+            .into_iter()
+            .map(|row| row.into_iter().skip(size).collect())
+            .collect(),
+    ))
+}
+
+/// Calculates the inverse of a matrix with symbolic entries as its adjugate divided by its determinant
+fn symbolic_inverse(
+    matrix: &Matrix,
+    arithmetic: &mut Arithmetic,
+) -> Result<FunctionReturnType, FunctionError> {
+    let determinant = cofactor_determinant(matrix, arithmetic)?;
+    if is_zero(&determinant) {
+        return Err(create_singular_error("inversion"));
+    }
+
+    let mut inverse = Matrix::with_capacity(matrix.len());
+    for row in 0..matrix.len() {
+        let mut inverse_row = Vec::with_capacity(matrix.len());
+        for column in 0..matrix.len() {
+            // The adjugate is the transposed matrix of the cofactors
+            let mut cofactor = cofactor_determinant(&minor(matrix, column, row), arithmetic)?;
+            if (row + column) % 2 == 1 {
+                cofactor = arithmetic(&ElementaryFunc::Subtraction, zero(), cofactor)?;
+            }
+            inverse_row.push(arithmetic(
+                &ElementaryFunc::Division,
+                cofactor,
+                determinant.clone(),
+            )?);
+        }
+        inverse.push(inverse_row);
+    }
+
+    Ok(matrix_value(inverse))
+}
+
+/// Returns the rank of a matrix, i.e. the number of linearly independent rows
+pub fn rank(
+    mut matrix: Matrix,
+    arithmetic: &mut Arithmetic,
+) -> Result<FunctionReturnType, FunctionError> {
+    let columns = matrix[0].len();
+    let (rank, _) = reduce(&mut matrix, columns, arithmetic)?;
+
+    Ok(FunctionReturnType::BigInt(BigInt::from(rank)))
+}
+
+/// Solves the system of linear equations `A x = b` for a non-singular square matrix `A`.
+/// `b` is a vector or a matrix whose columns are solved for at once.
+pub fn solve(
+    a: Matrix,
+    b: Vec<FunctionReturnType>,
+    arithmetic: &mut Arithmetic,
+) -> Result<FunctionReturnType, FunctionError> {
+    let size = a.len();
+    let b_is_vector = !is_matrix(&b);
+    let b = if b_is_vector {
+        b.into_iter().map(|value| vec![value]).collect()
+    } else {
+        get_matrix(FunctionReturnType::List(b))?
+    };
+
+    if b.len() != size {
+        return Err(create_shape_error(&format!(
+            "The right side of the system of equations needs {} rows but has {}",
+            size,
+            b.len()
+        )));
+    }
+
+    let mut augmented = a
+        .into_iter()
+        .zip(b)
+        .map(|(mut row, b_row)| {
+            row.extend(b_row);
+            row
+        })
+        .collect::<Matrix>();
+
+    if reduce(&mut augmented, size, arithmetic)?.0 < size {
+        return Err(create_singular_error("solution of the system of equations"));
+    }
+
+    let solution = augmented
+        .into_iter()
+        .map(|row| row.into_iter().skip(size).collect::<Vec<_>>());
+
+    Ok(if b_is_vector {
+        FunctionReturnType::List(solution.map(|mut row| row.swap_remove(0)).collect())
+    } else {
+        matrix_value(solution.collect())
+    })
+}
@@ -0,0 +1,972 @@
+use crate::error::{FunctionError, FunctionErrorType};
+
+/// A real function of one variable, e.g. an expression that is evaluated for different values of its variable.
+/// An error means that the function is not defined at the value, e.g. `Ln(x)` for negative values.
+pub type RealFunction<'a> = dyn FnMut(f64) -> Result<f64, FunctionError> + 'a;
+
+/// The side from which a limit is approached
+#[derive(Debug, PartialEq)]
+pub enum Direction {
+    Below,
+    Above,
+    Both,
+}
+
+/// Roots are searched between the negative and the positive bound if no interval is provided
+pub const DEFAULT_SEARCH_BOUND: f64 = 100.0;
+
+/// The number of subintervals of an interval which are searched for sign changes of the function
+const SEARCH_STEPS: usize = 1000;
+
+/// The maximal number of iterations of Newton's and Brent's method
+const MAX_ITERATIONS: usize = 100;
+
+/// The maximal number of times the interval around a guess is widened to find a sign change
+const MAX_INTERVAL_EXPANSIONS: usize = 60;
+
+/// Newton's method stops when its step is smaller than this relative to the root
+const TOLERANCE: f64 = 1e-12;
+
+/// Absolute function values below this are treated as zero when checking a root that is not enclosed by a sign change
+const ZERO_TOLERANCE: f64 = 1e-10;
+
+/// The estimated error of an integral has to be below this relative to its value
+const INTEGRAL_TOLERANCE: f64 = 1e-10;
+
+/// The maximal number of subintervals into which an integral is divided
+const MAX_INTEGRAL_INTERVALS: usize = 1000;
+
+/// The extrapolated one-sided derivatives may differ by this relative to the derivative, they converge slower than
+/// the central derivative
+const ONE_SIDED_TOLERANCE: f64 = 1e-6;
+
+/// The number of step sizes that are extrapolated to zero with Richardson extrapolation
+const RICHARDSON_STEPS: usize = 10;
+
+/// The step size is divided by this factor for every value that is extrapolated
+const STEP_REDUCTION: f64 = 1.4;
+
+/// The number of times the distance to the point of a limit is halved if the extrapolation does not converge
+const LIMIT_STEPS: i32 = 60;
+
+/// The minimal number of values of a function close to the point of a limit, which are checked for convergence
+const MIN_LIMIT_VALUES: usize = 6;
+
+/// The estimated error of a derivative or a limit has to be below this relative to its value
+const EXTRAPOLATION_TOLERANCE: f64 = 1e-8;
+
+/// Limits are only shown with the digits that are accurate up to this relative difference, because
+/// the cancellation of digits close to the point, e.g. in (1 - Cos(x)) / x^2, makes the estimated error too small
+const LIMIT_ACCURACY: f64 = 1e-10;
+
+/// Nodes of the 15 point Kronrod rule on [-1, 1], every second one is a node of the 7 point Gauss rule.
+/// Only the non-negative nodes are listed, the rules are symmetric.
+const KRONROD_NODES: [f64; 8] = [
+    0.9914553711208126,
+    0.9491079123427585,
+    0.8648644233597691,
+    0.7415311855993945,
+    0.5860872354676911,
+    0.4058451513773972,
+    0.20778495500789848,
+    0.0,
+];
+
+const KRONROD_WEIGHTS: [f64; 8] = [
+    0.022935322010529224,
+    0.06309209262997856,
+    0.10479001032225019,
+    0.14065325971552592,
+    0.1690047266392679,
+    0.19035057806478542,
+    0.20443294007529889,
+    0.20948214108472782,
+];
+
+/// Weights of the 7 point Gauss rule for the Kronrod nodes with odd indices
+const GAUSS_WEIGHTS: [f64; 4] = [
+    0.1294849661688697,
+    0.27970539148927664,
+    0.3818300505051189,
+    0.4179591836734694,
+];
+
+/// Finds a root of the function near the guess with Newton's method. If it does not converge, e.g. because the
+/// derivative is zero, the interval around the guess is widened until the function changes its sign and
+/// the root in it is found with Brent's method.
+pub fn find_root(f: &mut RealFunction, guess: f64) -> Result<f64, FunctionError> {
+    if let Some(root) = newton(f, guess) {
+        return Ok(round_root(f, root));
+    }
+
+    let value = f(guess)?;
+    let mut width = 0.1 * guess.abs().max(1.0);
+
+    for _ in 0..MAX_INTERVAL_EXPANSIONS {
+        for bound in [guess - width, guess + width] {
+            match f(bound) {
+                Ok(bound_value) if has_sign_change(value, bound_value) => {
+                    let root = brent(f, guess, bound, value, bound_value)?;
+
+                    // Sign changes at poles, e.g. of 1/x, are not roots
+                    if f(root).is_ok_and(|root_value| {
+                        root_value.abs() <= value.abs().min(bound_value.abs())
+                    }) {
+                        return Ok(round_root(f, root));
+                    }
+
+                    return Err(FunctionError::new(
+                        format!(
+                            "No root was found near {}. Newton's method did not converge and the function changes its sign without being zero, e.g. at a pole.",
+                            guess
+                        ),
+                        FunctionErrorType::NoConvergence,
+                    ));
+                }
+                _ => (),
+            }
+        }
+        width *= 1.6;
+    }
+
+    Err(FunctionError::new(
+        format!(
+            "No root was found near {}. Newton's method did not converge and the function does not change its sign around the guess.",
+            guess
+        ),
+        FunctionErrorType::NoConvergence,
+    ))
+}
+
+/// Finds all real roots of the function in the interval. The interval is divided into subintervals and every sign change
+/// is narrowed down with Brent's method. Roots at which the function only touches zero, e.g. of `x^2`, are found with
+/// Newton's method starting at the local minima of the absolute value. Roots closer than a subinterval can be missed,
+/// e.g. most roots of `Sin(1/x)` close to 0, as the grid is not refined.
+pub fn find_roots(f: &mut RealFunction, from: f64, to: f64) -> Result<Vec<f64>, FunctionError> {
+    if !from.is_finite() || !to.is_finite() || from >= to {
+        return Err(FunctionError::new(
+            format!(
+                "The interval in which roots are searched has to be finite and its lower bound has to be less than its upper bound. You provided the interval from {} to {}.",
+                from, to
+            ),
+            FunctionErrorType::DomainError,
+        ));
+    }
+
+    let step = (to - from) / SEARCH_STEPS as f64;
+    let mut first_error = None;
+    let samples = (0..=SEARCH_STEPS)
+        .map(|idx| {
+            let x = if idx == SEARCH_STEPS {
+                to
+            } else {
+                from + idx as f64 * step
+            };
+            // Values at which the function is not defined are skipped
+            let value = f(x).unwrap_or_else(|err| {
+                first_error.get_or_insert(err);
+                f64::NAN
+            });
+            (x, value)
+        })
+        .collect::<Vec<_>>();
+
+    // The function is not defined anywhere in the interval, e.g. because it contains another variable
+    if let Some(err) = first_error.filter(|_| samples.iter().all(|(_, value)| value.is_nan())) {
+        return Err(err);
+    }
+
+    // Every number is a root of a function that is zero wherever it is defined, e.g. of x - x or Ln(x) - Ln(x)
+    let mut defined_values = samples
+        .iter()
+        .map(|(_, value)| *value)
+        .filter(|value| !value.is_nan())
+        .peekable();
+    if defined_values.peek().is_some() && defined_values.all(|value| value == 0.0) {
+        return Err(FunctionError::new(
+            format!(
+                "The function is zero wherever it is defined between {} and {}, so every number in the interval at which it is defined is a root.",
+                from, to
+            ),
+            FunctionErrorType::InfinitelyManySolutions,
+        ));
+    }
+
+    let mut roots = Vec::new();
+
+    for pair in samples.windows(2) {
+        let ((a, value_a), (b, value_b)) = (pair[0], pair[1]);
+
+        if value_a == 0.0 {
+            roots.push(a);
+        } else if value_b != 0.0 && has_sign_change(value_a, value_b) {
+            // A sign change around a point at which the function is not defined is skipped, e.g. of Ln(Abs(x)) at 0
+            let Ok(root) = brent(f, a, b, value_a, value_b) else {
+                continue;
+            };
+            // Sign changes at poles, e.g. of 1/x, are not roots
+            if f(root).is_ok_and(|value| value.abs() <= value_a.abs().min(value_b.abs())) {
+                roots.push(round_root(f, root));
+            }
+        }
+    }
+
+    if samples.last().is_some_and(|(_, value)| *value == 0.0) {
+        roots.push(to);
+    }
+
+    for triple in samples.windows(3) {
+        let ((a, value_a), (x, value), (b, value_b)) = (triple[0], triple[1], triple[2]);
+
+        if value.abs() < value_a.abs()
+            && value.abs() < value_b.abs()
+            && !has_sign_change(value_a, value)
+            && !has_sign_change(value, value_b)
+        {
+            if let Some(root) = newton(f, x).filter(|root| (a..=b).contains(root)) {
+                roots.push(round_root(f, root));
+            }
+        }
+    }
+
+    roots.sort_by(f64::total_cmp);
+    roots.dedup_by(|a, b| (*a - *b).abs() <= step * 1e-6);
+
+    Ok(roots)
+}
+
+/// Integrates the function from one bound to the other with adaptive Gauss-Kronrod quadrature. The difference of the
+/// 15 point Kronrod and the 7 point Gauss rule estimates the error of every subinterval, and the subinterval with the
+/// largest error is halved until the estimated error of the sum is small enough. Infinite bounds are mapped to finite ones.
+/// The integral is rounded to the digits within its estimated error.
+pub fn integrate(f: &mut RealFunction, from: f64, to: f64) -> Result<f64, FunctionError> {
+    if from.is_nan() || to.is_nan() {
+        return Err(FunctionError::new(
+            "The bounds of an integral have to be numbers.".to_string(),
+            FunctionErrorType::DomainError,
+        ));
+    }
+
+    if from == to {
+        return Ok(0.0);
+    }
+
+    if from > to {
+        return integrate(f, to, from).map(|integral| -integral);
+    }
+
+    // Samples next to an infinite bound can be mapped to infinity, where the function has no value, if the integral
+    // needs that many subdivisions there. The function does not decay fast enough then, e.g. `Sin(x)/x`.
+    let mut sample = |x: f64, scale: f64| {
+        let value = if x.is_finite() {
+            f(x)? * scale
+        } else {
+            f64::NAN
+        };
+
+        if !value.is_finite() {
+            return Err(FunctionError::new(
+                format!(
+                    "The integral from {} to {} did not converge, the function does not decay fast enough towards infinity.",
+                    from, to
+                ),
+                FunctionErrorType::NoConvergence,
+            ));
+        }
+
+        Ok(value)
+    };
+
+    // Substitutions that map the infinite interval to a finite one, x is the original variable and t the new one
+    match (from.is_finite(), to.is_finite()) {
+        (true, true) => integrate_finite(f, from, to),
+        // x = from + t / (1 - t) with t in [0, 1)
+        (true, false) => integrate_finite(
+            &mut |t| sample(from + t / (1.0 - t), 1.0 / ((1.0 - t) * (1.0 - t))),
+            0.0,
+            1.0,
+        ),
+        // x = to - (1 - t) / t with t in (0, 1]
+        (false, true) => {
+            integrate_finite(&mut |t| sample(to - (1.0 - t) / t, 1.0 / (t * t)), 0.0, 1.0)
+        }
+        // x = t / (1 - t^2) with t in (-1, 1)
+        (false, false) => integrate_finite(
+            &mut |t| {
+                let denominator = 1.0 - t * t;
+                sample(t / denominator, (1.0 + t * t) / (denominator * denominator))
+            },
+            -1.0,
+            1.0,
+        ),
+    }
+}
+
+fn integrate_finite(f: &mut RealFunction, from: f64, to: f64) -> Result<f64, FunctionError> {
+    // Every subinterval with its integral and the estimated error
+    let mut intervals = vec![gauss_kronrod(f, from, to)?];
+
+    loop {
+        let integral = intervals.iter().map(|(_, _, value, _)| value).sum::<f64>();
+        let error = intervals.iter().map(|(_, _, _, error)| error).sum::<f64>();
+
+        if error <= INTEGRAL_TOLERANCE * integral.abs() || error <= f64::MIN_POSITIVE {
+            // Integrals of smooth functions are accurate up to the rounding errors of the sum and are kept as they are
+            if error <= 100.0 * f64::EPSILON * integral.abs() {
+                return Ok(integral);
+            }
+
+            return Ok(round_to_error(integral, error));
+        }
+
+        if intervals.len() >= MAX_INTEGRAL_INTERVALS {
+            return Err(FunctionError::new(
+                format!(
+                    "The integral did not converge, it is about {} with an estimated error of {}. The function might have a singularity or oscillate too much.",
+                    integral, error
+                ),
+                FunctionErrorType::NoConvergence,
+            ));
+        }
+
+        let (idx, _) = intervals
+            .iter()
+            .enumerate()
+            .max_by(|(_, (_, _, _, a)), (_, (_, _, _, b))| a.total_cmp(b))
+            .expect("There is at least one interval.");
+        let (a, b, _, _) = intervals.swap_remove(idx);
+        let middle = 0.5 * (a + b);
+
+        intervals.push(gauss_kronrod(f, a, middle)?);
+        intervals.push(gauss_kronrod(f, middle, b)?);
+    }
+}
+
+/// Returns the bounds of the interval, the integral over it with the 15 point Kronrod rule and its estimated error
+fn gauss_kronrod(
+    f: &mut RealFunction,
+    from: f64,
+    to: f64,
+) -> Result<(f64, f64, f64, f64), FunctionError> {
+    let center = 0.5 * (from + to);
+    let half_width = 0.5 * (to - from);
+
+    let mut kronrod = 0.0;
+    let mut gauss = 0.0;
+
+    for (idx, node) in KRONROD_NODES.iter().enumerate() {
+        let values = if *node == 0.0 {
+            f(center)?
+        } else {
+            f(center - half_width * node)? + f(center + half_width * node)?
+        };
+
+        if !values.is_finite() {
+            return Err(FunctionError::new(
+                format!(
+                    "The function is not finite between {} and {}, so its integral cannot be calculated.",
+                    from, to
+                ),
+                FunctionErrorType::DomainError,
+            ));
+        }
+
+        kronrod += KRONROD_WEIGHTS[idx] * values;
+        if idx % 2 == 1 {
+            gauss += GAUSS_WEIGHTS[idx / 2] * values;
+        }
+    }
+
+    Ok((
+        from,
+        to,
+        kronrod * half_width,
+        ((kronrod - gauss) * half_width).abs(),
+    ))
+}
+
+/// Calculates the derivative at the value with central differences of decreasing step sizes,
+/// which are extrapolated to a step size of zero with Richardson extrapolation (Ridders' method).
+/// Central differences of functions with a corner are the mean of both sides, e.g. 0 for `Abs(x)` at 0,
+/// so the extrapolated one-sided differences have to agree as well.
+pub fn derivative(f: &mut RealFunction, at: f64) -> Result<f64, FunctionError> {
+    let start = 0.1 * at.abs().max(1.0);
+    let (derivative, error) =
+        richardson(&mut |h| Ok((f(at + h)? - f(at - h)?) / (2.0 * h)), start, 2)?;
+
+    if error > EXTRAPOLATION_TOLERANCE * derivative.abs().max(1.0) {
+        return Err(FunctionError::new(
+            format!(
+                "The derivative at {} did not converge, it is about {} with an estimated error of {}. The function might not be differentiable there.",
+                at, derivative, error
+            ),
+            FunctionErrorType::NoConvergence,
+        ));
+    }
+
+    let value = f(at)?;
+    let (left, left_error) = richardson(&mut |h| Ok((value - f(at - h)?) / h), start, 1)?;
+    let (right, right_error) = richardson(&mut |h| Ok((f(at + h)? - value) / h), start, 1)?;
+
+    if (right - left).abs()
+        > left_error + right_error + ONE_SIDED_TOLERANCE * derivative.abs().max(1.0)
+    {
+        Err(FunctionError::new(
+            format!(
+                "The derivative at {} does not exist, the derivative from the left is about {} but from the right about {}.",
+                at,
+                round_to_error(left, ONE_SIDED_TOLERANCE),
+                round_to_error(right, ONE_SIDED_TOLERANCE)
+            ),
+            FunctionErrorType::NoConvergence,
+        ))
+    } else {
+        Ok(round_to_error(derivative, error))
+    }
+}
+
+/// Estimates the limit of the function at the value, which can be infinite, from the given direction.
+/// The values at decreasing distances are extrapolated to a distance of zero with Richardson extrapolation.
+/// If that does not converge, the values at tiny distances are checked for convergence or divergence to infinity.
+pub fn limit(f: &mut RealFunction, at: f64, direction: Direction) -> Result<f64, FunctionError> {
+    if at.is_nan() {
+        return Err(FunctionError::new(
+            "The point of a limit has to be a number.".to_string(),
+            FunctionErrorType::DomainError,
+        ));
+    }
+
+    if direction == Direction::Both && at.is_finite() {
+        let (below, above) = match (
+            limit(f, at, Direction::Below),
+            limit(f, at, Direction::Above),
+        ) {
+            (Ok(below), Ok(above)) => (below, above),
+            // The function is only defined on one side, e.g. Sqrt(x) at 0
+            (Err(err), Ok(limit)) | (Ok(limit), Err(err))
+                if !matches!(err.error_type, FunctionErrorType::NoConvergence) =>
+            {
+                return Ok(limit)
+            }
+            (Err(err), _) | (_, Err(err)) => return Err(err),
+        };
+
+        let difference = (below - above).abs();
+        return if below == above {
+            Ok(below)
+        } else if below.is_finite() && difference <= EXTRAPOLATION_TOLERANCE * below.abs().max(1.0)
+        {
+            Ok(round_to_error(
+                0.5 * (below + above),
+                difference.max(LIMIT_ACCURACY * below.abs()),
+            ))
+        } else {
+            Err(FunctionError::new(
+                format!(
+                    "The limit at {} does not exist, it is {} from below but {} from above.",
+                    at, below, above
+                ),
+                FunctionErrorType::NoConvergence,
+            ))
+        };
+    }
+
+    // The distance h to the point is positive and tends towards zero, infinite points are approached with 1/h
+    let mut approach = |h: f64| match (at.is_finite(), &direction) {
+        (true, Direction::Below) => f(at - h),
+        (true, _) => f(at + h),
+        (false, _) => f(at.signum() / h),
+    };
+    let side = match (at.is_finite(), &direction) {
+        (true, Direction::Below) => "below",
+        (true, _) => "above",
+        (false, _) => "infinity",
+    };
+    let start = 0.1
+        * if at.is_finite() {
+            at.abs().max(1.0)
+        } else {
+            1.0
+        };
+
+    // The extrapolation fails for functions that overflow close to the point, e.g. Exp(1/x) at 0
+    if let Some((value, error)) = richardson(&mut approach, start, 1)
+        .ok()
+        .filter(|(value, error)| *error <= EXTRAPOLATION_TOLERANCE * value.abs().max(1.0))
+    {
+        return Ok(round_to_error(
+            value,
+            error.max(LIMIT_ACCURACY * value.abs()),
+        ));
+    }
+
+    // The distance is halved as long as the point and the approaching value are distinguishable,
+    // which they always are for infinite points. The values before the function fails, e.g. by overflowing, are enough to check them.
+    let resolution = if at.is_finite() {
+        64.0 * f64::EPSILON * at.abs()
+    } else {
+        0.0
+    };
+    let mut values = Vec::new();
+    for h in (0..LIMIT_STEPS)
+        .map(|idx| start * 0.5f64.powi(idx))
+        .take_while(|h| *h > resolution)
+    {
+        match approach(h) {
+            Ok(value) => values.push(value),
+            Err(_) if values.len() >= MIN_LIMIT_VALUES => break,
+            Err(err) => return Err(err),
+        }
+    }
+
+    if values.len() < MIN_LIMIT_VALUES {
+        return Err(FunctionError::new(
+            format!(
+                "The limit at {} from {} could not be estimated, the function could only be evaluated at {} points close to it.",
+                at,
+                side,
+                values.len()
+            ),
+            FunctionErrorType::NoConvergence,
+        ));
+    }
+
+    let differences = values
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .collect::<Vec<_>>();
+    let last = values[values.len() - 1];
+
+    // The values settle, e.g. of x*Sin(1/x) at 0, which converges too slowly for the extrapolation.
+    // The remaining distance to the limit can be a multiple of the last differences, e.g. of Sqrt(x) at 0.
+    let error = 4.0
+        * differences[differences.len() - MIN_LIMIT_VALUES / 2..]
+            .iter()
+            .map(|difference| difference.abs())
+            .fold(0.0, f64::max);
+    if error <= EXTRAPOLATION_TOLERANCE * last.abs().max(1.0) {
+        return Ok(round_to_error(last, error.max(LIMIT_ACCURACY * last.abs())));
+    }
+
+    // The values grow without bound in one direction, e.g. of 1/x or Ln(x) at 0, if their differences do not shrink
+    let is_diverging = differences
+        .iter()
+        .all(|difference| difference.signum() == last.signum() && *difference != 0.0)
+        && differences
+            .windows(2)
+            .all(|pair| pair[1].abs() >= 0.9 * pair[0].abs());
+    if is_diverging {
+        return Ok(f64::INFINITY.copysign(last));
+    }
+
+    Err(FunctionError::new(
+        format!(
+            "The limit at {} from {} does not appear to exist, the values close to it are {}, {} and {}.",
+            at,
+            side,
+            values[values.len() - 3],
+            values[values.len() - 2],
+            last
+        ),
+        FunctionErrorType::NoConvergence,
+    ))
+}
+
+/// Extrapolates the values of the function for decreasing step sizes to a step size of zero with Richardson extrapolation.
+/// The error of the values has to be a power series in the step size, e.g. only of even powers for central differences.
+/// Returns the extrapolated value with the smallest estimated error and that error.
+fn richardson(f: &mut RealFunction, start: f64, power: i32) -> Result<(f64, f64), FunctionError> {
+    let mut previous_row: Vec<f64> = Vec::new();
+    let mut best = (f64::NAN, f64::INFINITY);
+    let mut h = start;
+
+    for k in 0..RICHARDSON_STEPS {
+        let mut row = vec![f(h)?];
+        let mut factor = 1.0;
+
+        for j in 1..=k {
+            factor *= STEP_REDUCTION.powi(power);
+            let value = row[j - 1] + (row[j - 1] - previous_row[j - 1]) / (factor - 1.0);
+            let error = (value - row[j - 1])
+                .abs()
+                .max((value - previous_row[j - 1]).abs());
+
+            if error <= best.1 {
+                best = (value, error);
+            }
+            row.push(value);
+        }
+
+        // Rounding errors outweigh the smaller step size when the extrapolation gets worse
+        if k > 0 && (row[k] - previous_row[k - 1]).abs() >= 2.0 * best.1 {
+            break;
+        }
+
+        previous_row = row;
+        h /= STEP_REDUCTION;
+    }
+
+    Ok(best)
+}
+
+/// Rounds the value to the fewest significant digits that are within its estimated error,
+/// so that only accurate digits are shown and e.g. 1 is not shown as 0.9999999999999998
+fn round_to_error(value: f64, error: f64) -> f64 {
+    // Rounding errors of the function values limit the accuracy even if the extrapolation converges
+    let error = error.max(100.0 * f64::EPSILON * value.abs());
+
+    if value.abs() <= error {
+        return 0.0;
+    }
+
+    (0..17)
+        .map(|digits| {
+            format!("{:.*e}", digits, value)
+                .parse::<f64>()
+                .expect("A formatted float can be parsed.")
+        })
+        .find(|rounded| (rounded - value).abs() <= error)
+        .unwrap_or(value)
+}
+
+/// Newton's method with a central difference as derivative. Returns `None` if it does not converge
+/// or converges to a point which is not a root, e.g. for functions without roots.
+fn newton(f: &mut RealFunction, guess: f64) -> Option<f64> {
+    let mut x = guess;
+
+    for _ in 0..MAX_ITERATIONS {
+        let value = f(x).ok()?;
+        if value == 0.0 {
+            return Some(x);
+        }
+
+        let h = 1e-7 * x.abs().max(1.0);
+        let slope = (f(x + h).ok()? - f(x - h).ok()?) / (2.0 * h);
+        if slope == 0.0 || !slope.is_finite() {
+            return None;
+        }
+
+        let step = value / slope;
+        x -= step;
+        if !x.is_finite() {
+            return None;
+        }
+
+        if step.abs() <= TOLERANCE * x.abs().max(1.0) {
+            return is_root(f, x).then_some(x);
+        }
+    }
+
+    None
+}
+
+/// Returns true if the function is zero at the value or changes its sign directly around it
+fn is_root(f: &mut RealFunction, x: f64) -> bool {
+    let delta = 1e-9 * x.abs().max(1.0);
+
+    match (f(x), f(x - delta), f(x + delta)) {
+        (Ok(value), _, _) if value.abs() <= ZERO_TOLERANCE => true,
+        (_, Ok(lower), Ok(upper)) => has_sign_change(lower, upper),
+        _ => false,
+    }
+}
+
+/// Brent's method finds the root between two values at which the function has different signs.
+/// It combines bisection, which always converges, with the faster secant method and inverse quadratic interpolation.
+fn brent(
+    f: &mut RealFunction,
+    mut a: f64,
+    mut b: f64,
+    mut value_a: f64,
+    mut value_b: f64,
+) -> Result<f64, FunctionError> {
+    let (mut c, mut value_c) = (b, value_b);
+    let mut d = b - a;
+    let mut e = d;
+
+    for _ in 0..MAX_ITERATIONS {
+        // The root is always between b and c
+        if (value_b > 0.0 && value_c > 0.0) || (value_b < 0.0 && value_c < 0.0) {
+            (c, value_c) = (a, value_a);
+            d = b - a;
+            e = d;
+        }
+
+        // b is the best approximation
+        if value_c.abs() < value_b.abs() {
+            (a, value_a) = (b, value_b);
+            (b, value_b) = (c, value_c);
+            (c, value_c) = (a, value_a);
+        }
+
+        let tolerance = 2.0 * f64::EPSILON * b.abs() + f64::MIN_POSITIVE;
+        let middle = 0.5 * (c - b);
+
+        if middle.abs() <= tolerance || value_b == 0.0 {
+            return Ok(b);
+        }
+
+        if e.abs() >= tolerance && value_a.abs() > value_b.abs() {
+            let s = value_b / value_a;
+            let (mut p, mut q) = if a == c {
+                // Secant method
+                (2.0 * middle * s, 1.0 - s)
+            } else {
+                // Inverse quadratic interpolation
+                let q = value_a / value_c;
+                let r = value_b / value_c;
+                (
+                    s * (2.0 * middle * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+
+            if p > 0.0 {
+                q = -q;
+            } else {
+                p = -p;
+            }
+
+            // The interpolation is only used if it stays in the interval and converges fast enough
+            if 2.0 * p < (3.0 * middle * q - (tolerance * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = middle;
+                e = d;
+            }
+        } else {
+            // Bisection
+            d = middle;
+            e = d;
+        }
+
+        (a, value_a) = (b, value_b);
+        b += if d.abs() > tolerance {
+            d
+        } else {
+            tolerance.copysign(middle)
+        };
+        value_b = f(b)?;
+    }
+
+    Ok(b)
+}
+
+/// Rounds the root to 12 significant digits if the function is at least as close to zero there,
+/// so that roots like 2 are not shown as 1.9999999999999998
+fn round_root(f: &mut RealFunction, root: f64) -> f64 {
+    let rounded = format!("{:.11e}", root)
+        .parse::<f64>()
+        .expect("A formatted float can be parsed.");
+
+    match (f(rounded), f(root)) {
+        (Ok(rounded_value), Ok(value)) if rounded_value.abs() <= value.abs() => rounded,
+        _ => root,
+    }
+}
+
+fn has_sign_change(a: f64, b: f64) -> bool {
+    (a < 0.0 && b > 0.0) || (a > 0.0 && b < 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_root() {
+        assert_eq!(find_root(&mut |x| Ok(x * x - 4.0), 1.0).unwrap(), 2.0);
+        assert_eq!(find_root(&mut |x| Ok(x * x - 4.0), -1.0).unwrap(), -2.0);
+        assert!(
+            (find_root(&mut |x| Ok(x.cos() - x), 0.0).unwrap() - 0.7390851332151607).abs() < 1e-12
+        );
+        // Newton's method diverges for Atan far from its root, the sign change is found instead
+        assert_eq!(find_root(&mut |x| Ok(x.atan()), 2.0).unwrap(), 0.0);
+        assert!(find_root(&mut |x| Ok(x * x + 1.0), 0.5).is_err());
+        assert!(find_root(&mut |x| Ok(1.0 / x), 1.0).is_err());
+        // The sign change of the bracketing fallback is a pole, not a root
+        assert!(find_root(&mut |x| Ok(1.0 / (x - 3.0)), 2.5).is_err());
+    }
+
+    #[test]
+    fn test_integrate() {
+        let close = |a: f64, b: f64| (a - b).abs() <= 1e-9 * b.abs().max(1.0);
+
+        assert!(close(
+            integrate(&mut |x| Ok(x * x), 0.0, 1.0).unwrap(),
+            1.0 / 3.0
+        ));
+        assert!(close(
+            integrate(&mut |x| Ok(x * x), 1.0, 0.0).unwrap(),
+            -1.0 / 3.0
+        ));
+        assert!(close(
+            integrate(&mut |x| Ok(x.sin()), 0.0, std::f64::consts::PI).unwrap(),
+            2.0
+        ));
+        assert!(close(
+            integrate(&mut |x| Ok(1.0 / x.sqrt()), 0.0, 1.0).unwrap(),
+            2.0
+        ));
+        assert!(close(
+            integrate(
+                &mut |x| Ok((-x * x).exp()),
+                f64::NEG_INFINITY,
+                f64::INFINITY
+            )
+            .unwrap(),
+            std::f64::consts::PI.sqrt()
+        ));
+        assert!(close(
+            integrate(&mut |x| Ok(1.0 / (x * x)), 1.0, f64::INFINITY).unwrap(),
+            1.0
+        ));
+        assert_eq!(integrate(&mut |x| Ok(x), 2.0, 2.0).unwrap(), 0.0);
+        assert!(integrate(&mut |x| Ok(1.0 / x), -1.0, 1.0).is_err());
+
+        // Only the digits within the estimated error are kept
+        assert_eq!(
+            integrate(&mut |x| Ok(1.0 / x.sqrt()), 0.0, 1.0).unwrap(),
+            2.0
+        );
+        assert_eq!(integrate(&mut |x| Ok(x.ln()), 0.0, 1.0).unwrap(), -1.0);
+
+        // Functions that do not decay fast enough are sampled at infinity after many subdivisions
+        assert!(matches!(
+            integrate(&mut |x| Ok(x.sin() / x), 1.0, f64::INFINITY),
+            Err(FunctionError {
+                error_type: FunctionErrorType::NoConvergence,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_derivative() {
+        assert_eq!(derivative(&mut |x| Ok(x * x), 3.0).unwrap(), 6.0);
+        assert_eq!(derivative(&mut |x| Ok(x.sin()), 0.0).unwrap(), 1.0);
+        // Only the accurate digits are kept
+        assert_eq!(
+            derivative(&mut |x| Ok(x.exp()), 1.0).unwrap().to_string(),
+            "2.718281828459"
+        );
+        assert!(derivative(&mut |x| Ok((1.0 / x).sin()), 1e-3).is_err());
+
+        // The one-sided derivatives of a corner differ
+        assert!(derivative(&mut |x| Ok(x.abs()), 0.0).is_err());
+        assert!(derivative(&mut |x| Ok((x - 2.0).abs() + x), 2.0).is_err());
+        assert_eq!(derivative(&mut |x| Ok(x.abs()), 1.0).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_limit() {
+        assert_eq!(
+            limit(&mut |x| Ok(x.sin() / x), 0.0, Direction::Both).unwrap(),
+            1.0
+        );
+        assert_eq!(
+            limit(&mut |x| Ok((1.0 - x.cos()) / (x * x)), 0.0, Direction::Both).unwrap(),
+            0.5
+        );
+        assert_eq!(
+            limit(
+                &mut |x| Ok((1.0 + 1.0 / x).powf(x)),
+                f64::INFINITY,
+                Direction::Both
+            )
+            .unwrap()
+            .to_string(),
+            "2.7182818285"
+        );
+        assert_eq!(
+            limit(&mut |x| Ok(1.0 / x), 0.0, Direction::Above).unwrap(),
+            f64::INFINITY
+        );
+        assert_eq!(
+            limit(&mut |x| Ok(1.0 / x), 0.0, Direction::Below).unwrap(),
+            f64::NEG_INFINITY
+        );
+        assert_eq!(
+            limit(&mut |x| Ok(x.ln()), 0.0, Direction::Above).unwrap(),
+            f64::NEG_INFINITY
+        );
+        assert!(limit(&mut |x| Ok(1.0 / x), 0.0, Direction::Both).is_err());
+        assert!(limit(&mut |x| Ok((1.0 / x).sin()), 0.0, Direction::Above).is_err());
+        assert!(limit(&mut |x| Ok(x.abs() / x), 0.0, Direction::Both).is_err());
+        assert_eq!(
+            limit(&mut |x| Ok(x), f64::INFINITY, Direction::Both).unwrap(),
+            f64::INFINITY
+        );
+        assert_eq!(
+            limit(&mut |x| Ok(x), f64::NEG_INFINITY, Direction::Both).unwrap(),
+            f64::NEG_INFINITY
+        );
+        assert_eq!(
+            limit(&mut |x| Ok(x.exp()), f64::NEG_INFINITY, Direction::Both).unwrap(),
+            0.0
+        );
+        assert!(matches!(
+            limit(&mut |x| Ok(x.sin()), f64::INFINITY, Direction::Both)
+                .unwrap_err()
+                .error_type,
+            FunctionErrorType::NoConvergence
+        ));
+    }
+
+    #[test]
+    fn test_find_roots() {
+        assert_eq!(
+            find_roots(&mut |x| Ok(x * x - 4.0), -10.0, 10.0).unwrap(),
+            vec![-2.0, 2.0]
+        );
+        assert_eq!(
+            find_roots(&mut |x| Ok((x - 1.0) * (x - 1.0)), -10.0, 10.0).unwrap(),
+            vec![1.0]
+        );
+        assert_eq!(
+            find_roots(&mut |x| Ok(1.0 / x), -10.0, 10.0).unwrap(),
+            vec![]
+        );
+        assert_eq!(
+            find_roots(&mut |x| Ok(x.sin()), -1.0, 7.0).unwrap().len(),
+            3
+        );
+        assert!(find_roots(&mut |x| Ok(x * x + 1.0), -10.0, 10.0)
+            .unwrap()
+            .is_empty());
+        assert!(find_roots(&mut |x| Ok(x), 1.0, -1.0).is_err());
+        assert!(matches!(
+            find_roots(&mut |x| Ok(x - x), -10.0, 10.0),
+            Err(FunctionError {
+                error_type: FunctionErrorType::InfinitelyManySolutions,
+                ..
+            })
+        ));
+        // Values at which the function is not defined do not count as non-zero values
+        assert!(matches!(
+            find_roots(&mut |x| Ok(x.ln() - x.ln()), -10.0, 10.0),
+            Err(FunctionError {
+                error_type: FunctionErrorType::InfinitelyManySolutions,
+                ..
+            })
+        ));
+        // The sign change around 0, at which the function is not defined, is skipped
+        assert_eq!(
+            find_roots(
+                &mut |x| if x.abs() < 1e-4 {
+                    Err(FunctionError::new(
+                        "Not defined".to_string(),
+                        FunctionErrorType::DomainError,
+                    ))
+                } else {
+                    Ok(x * (x - 1.5))
+                },
+                -1.0,
+                2.0
+            )
+            .unwrap(),
+            vec![1.5]
+        );
+    }
+}
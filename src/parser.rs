@@ -1,25 +1,65 @@
 #![allow(dead_code)]
 
+use crate::environment::Environment;
 use crate::error::CLMathError;
 use crate::error::ParserError;
+use crate::error::ParserErrorType;
+use crate::error::TokenKind;
+use crate::executor::{STACK_RED_ZONE, STACK_SEGMENT_SIZE};
+use crate::functions::Associativity;
+use crate::functions::Constant;
 use crate::functions::ElementaryFunc;
 use crate::functions::Func;
 use crate::functions::HigherOrderFunc;
+use crate::functions::UnaryFunc;
+use crate::tokenizer::Number;
+use crate::tokenizer::Span;
 use crate::tokenizer::Symbol;
 use crate::tokenizer::Token;
 use crate::tokenizer::Variable;
+use std::collections::HashMap;
 use std::collections::VecDeque;
+use std::fmt::Display;
+use std::rc::Rc;
 
 #[derive(Debug)]
 pub enum Start {
+    Assignment(Assignment),
+    FunctionDefinition(FunctionDefinition),
     Expression(Expression),
     Empty,
 }
 
+#[derive(Debug)]
+pub struct Assignment {
+    pub variable: Variable,
+    equals: Equals,
+    pub expression: Expression,
+}
+
+#[derive(Debug)]
+pub struct FunctionDefinition {
+    pub name: String,
+    opening_bracket: OpeningBracket,
+    pub params: Vec<Variable>,
+    closing_bracket: ClosingBracket,
+    equals: Equals,
+    pub expression: Expression,
+}
+
+/// An expression with the part of the input it was parsed from
 #[derive(Debug)]
 pub enum Expression {
-    BracketedExpression(Box<BracketedExpression>),
-    MathExpression(Box<MathExpression>),
+    BracketedExpression(Box<BracketedExpression>, Span),
+    MathExpression(Box<MathExpression>, Span),
+}
+
+impl Expression {
+    pub fn span(&self) -> Span {
+        match self {
+            Expression::BracketedExpression(_, span) | Expression::MathExpression(_, span) => *span,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -31,15 +71,45 @@ pub struct BracketedExpression {
 
 #[derive(Debug)]
 pub enum MathExpression {
-    Number(f64),
+    Number(Number),
+    Constant(Constant),
     Variable(Variable),
     Function(Function),
+    Lambda(Lambda),
+    List(ListLiteral),
+    Equation(Equation),
+}
+
+/// An equation as a parameter of a function, e.g. `x^2 = 2` in `Solve(x^2 = 2, x)`
+#[derive(Debug)]
+pub struct Equation {
+    pub lhs: Expression,
+    equals: Equals,
+    pub rhs: Expression,
+}
+
+/// A list of values in square brackets, e.g. the vector `[1, 2, 3]` or the matrix `[[1, 2], [3, 4]]`
+#[derive(Debug)]
+pub struct ListLiteral {
+    opening_square_bracket: OpeningSquareBracket,
+    pub elements: Vec<Expression>,
+    closing_square_bracket: ClosingSquareBracket,
 }
 
 #[derive(Debug)]
+pub struct Lambda {
+    pub params: Vec<Variable>,
+    arrow: Arrow,
+    pub body: Rc<Expression>,
+}
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum Function {
     ElementaryFunction(ElementaryFunction),
     HigherOrderFunction(HigherOrderFunction),
+    UnaryFunction(UnaryFunction),
+    UserFunction(UserFunction),
 }
 
 #[derive(Debug)]
@@ -47,7 +117,15 @@ pub struct OpeningBracket;
 #[derive(Debug)]
 pub struct ClosingBracket;
 #[derive(Debug)]
+pub struct OpeningSquareBracket;
+#[derive(Debug)]
+pub struct ClosingSquareBracket;
+#[derive(Debug)]
 pub struct Comma;
+#[derive(Debug)]
+pub struct Equals;
+#[derive(Debug)]
+pub struct Arrow;
 
 #[derive(Debug)]
 pub struct ElementaryFunction {
@@ -56,6 +134,12 @@ pub struct ElementaryFunction {
     pub expression_rhs: Expression,
 }
 
+#[derive(Debug)]
+pub struct UnaryFunction {
+    pub function: UnaryFunc,
+    pub expression: Expression,
+}
+
 #[derive(Debug)]
 pub struct HigherOrderFunction {
     pub function: HigherOrderFunc,
@@ -64,6 +148,14 @@ pub struct HigherOrderFunction {
     closing_bracket: ClosingBracket,
 }
 
+#[derive(Debug)]
+pub struct UserFunction {
+    pub name: String,
+    opening_bracket: OpeningBracket,
+    pub params: Params,
+    closing_bracket: ClosingBracket,
+}
+
 #[derive(Debug)]
 pub struct Params {
     pub expression: Expression,
@@ -81,128 +173,511 @@ impl Params {
 
 pub struct Parser {
     tokens: VecDeque<Token>,
-    lhs_queue: VecDeque<Expression>,
-    expression_ends_queue: VecDeque<usize>,
-    returning: bool,
-    curr_idx: usize,
+    /// The span of every token in `tokens`
+    spans: VecDeque<Span>,
+    /// The span of the last popped token, or the end of the input if there was no token left
+    last_span: Span,
+    /// The empty span after the last token
+    end_span: Span,
+    /// The errors the parser recovered from
+    errors: Vec<ParserError>,
+    user_function_param_counts: HashMap<String, usize>,
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expression::BracketedExpression(expression, _) => {
+                write!(f, "({})", expression.expression)
+            }
+            Expression::MathExpression(expression, _) => write!(f, "{}", expression),
+        }
+    }
+}
+
+/// Displays the expression as input that is parsed to the same expression, e.g. to show the body of a function
+impl Display for MathExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MathExpression::Number(num) => write!(f, "{}", num),
+            MathExpression::Constant(constant) => write!(f, "{}", constant.get_keyword()),
+            MathExpression::Variable(var) => write!(f, "{}", var),
+            MathExpression::Function(Function::ElementaryFunction(function)) => {
+                match (&function.function, &function.expression_rhs) {
+                    // Units have to follow the number directly to be units, e.g. `5 m`
+                    (ElementaryFunc::Multiplication, Expression::MathExpression(rhs, _)) if matches!(rhs.as_ref(), MathExpression::Variable(var) if var.is_unit) =>
+                    {
+                        write!(f, "{} {}", function.expression_lhs, rhs)
+                    }
+                    (
+                        ElementaryFunc::Multiplication
+                        | ElementaryFunc::Division
+                        | ElementaryFunc::Exponentiation,
+                        rhs,
+                    ) => write!(
+                        f,
+                        "{}{}{}",
+                        function.expression_lhs,
+                        function.function.get_keyword(),
+                        rhs
+                    ),
+                    (_, rhs) => write!(
+                        f,
+                        "{} {} {}",
+                        function.expression_lhs,
+                        function.function.get_keyword(),
+                        rhs
+                    ),
+                }
+            }
+            MathExpression::Function(Function::UnaryFunction(function)) => {
+                write!(
+                    f,
+                    "{}{}",
+                    function.function.get_keyword(),
+                    function.expression
+                )
+            }
+            MathExpression::Function(Function::HigherOrderFunction(function)) => {
+                write!(
+                    f,
+                    "{}({})",
+                    function.function.get_keyword(),
+                    function.params
+                )
+            }
+            MathExpression::Function(Function::UserFunction(function)) => {
+                write!(f, "{}({})", function.name, function.params)
+            }
+            MathExpression::Lambda(lambda) => match lambda.params.as_slice() {
+                [param] => write!(f, "{} -> {}", param, lambda.body),
+                params => write!(
+                    f,
+                    "({}) -> {}",
+                    params
+                        .iter()
+                        .map(|param| param.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    lambda.body
+                ),
+            },
+            MathExpression::List(list) => write!(
+                f,
+                "[{}]",
+                list.elements
+                    .iter()
+                    .map(|element| element.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            MathExpression::Equation(equation) => write!(f, "{} = {}", equation.lhs, equation.rhs),
+        }
+    }
+}
+
+impl Display for Params {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.expression)?;
+        for (_, expression) in self.expression_comma.iter().flatten() {
+            write!(f, ", {}", expression)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Parser {
-    fn from(tokens: Vec<Token>) -> Self {
+    fn from(tokens: Vec<(Token, Span)>, env: &Environment) -> Self {
+        let (tokens, spans): (VecDeque<Token>, VecDeque<Span>) = tokens.into_iter().unzip();
+        let end = spans.back().map_or(0, |span| span.end);
+
         Parser {
-            tokens: tokens.into(),
-            lhs_queue: VecDeque::new(),
-            expression_ends_queue: VecDeque::new(),
-            returning: true,
-            curr_idx: 0,
+            tokens,
+            spans,
+            last_span: Span::new(0, 0),
+            end_span: Span::new(end, end),
+            errors: Vec::new(),
+            user_function_param_counts: env
+                .get_callables()
+                .map(|(name, param_count)| (name.clone(), param_count))
+                .collect(),
         }
     }
 
     fn pop(&mut self) -> Option<Token> {
-        self.curr_idx += 1;
+        self.last_span = self.spans.pop_front().unwrap_or(self.end_span);
         self.tokens.pop_front()
     }
 
-    pub fn parse(tokens: Vec<Token>) -> Result<Start, CLMathError> {
-        Parser::from(tokens).start().map_err(CLMathError::Parser)
+    /// Returns the span of the next token, or the end of the input if there is no token left
+    fn next_span(&self) -> Span {
+        self.spans.front().copied().unwrap_or(self.end_span)
     }
 
-    fn start(&mut self) -> Result<Start, ParserError> {
-        if self.tokens.is_empty() {
-            Ok(Start::Empty)
-        } else {
-            Ok(Start::Expression(self.expression()?))
+    /// Creates an error at the last popped token
+    fn error(&self, error_type: ParserErrorType, expected: &[TokenKind]) -> ParserError {
+        ParserError::new(error_type, self.last_span).expecting(expected)
+    }
+
+    /// Keeps an error to report it after parsing the rest of the input.
+    /// Only the first error at the end of the input is kept, the following ones are caused by it.
+    fn record(&mut self, error: ParserError) {
+        let is_repeated_end = error.span == self.end_span
+            && self
+                .errors
+                .last()
+                .is_some_and(|last| last.span == self.end_span);
+
+        if !is_repeated_end {
+            self.errors.push(error);
         }
     }
 
-    fn expression(&mut self) -> Result<Expression, ParserError> {
-        let expression = if let Some(first_token) = self.tokens.front() {
-            match first_token {
-                Token::Symbol(Symbol::OpeningBracket) => {
-                    Expression::BracketedExpression(Box::new(self.bracketed_expression()?))
+    /// Stands in for an operand that could not be parsed. It is never executed, because the error is reported instead.
+    fn placeholder(span: Span) -> Expression {
+        Expression::MathExpression(
+            Box::new(MathExpression::Number(Number::Integer(0.into()))),
+            span,
+        )
+    }
+
+    /// Returns true for the tokens that end an operand, parsing continues at them after an invalid operand
+    fn is_synchronizing(token: &Token) -> bool {
+        matches!(
+            token,
+            Token::Function(Func::Elementary(_))
+                | Token::Symbol(
+                    Symbol::Comma
+                        | Symbol::ClosingBracket
+                        | Symbol::ClosingSquareBracket
+                        | Symbol::Equals
+                )
+        )
+    }
+
+    /// Skips tokens until a token outside of brackets ends the skipping.
+    /// Brackets are skipped as a whole, unmatched closing brackets are skipped as well.
+    fn skip_until(&mut self, is_end: fn(&Token) -> bool) {
+        let mut depth = 0usize;
+
+        while let Some(token) = self.tokens.front() {
+            if depth == 0 && is_end(token) {
+                break;
+            }
+
+            match token {
+                Token::Symbol(Symbol::OpeningBracket | Symbol::OpeningSquareBracket) => depth += 1,
+                Token::Symbol(Symbol::ClosingBracket | Symbol::ClosingSquareBracket) => {
+                    depth = depth.saturating_sub(1)
                 }
-                _ => Expression::MathExpression(Box::new(self.math_expression()?)),
+                _ => (),
             }
+
+            self.pop();
+        }
+    }
+
+    /// Parses the tokens and reports all errors of the input. After an error parsing continues at the next comma,
+    /// closing bracket or operator.
+    pub fn parse(tokens: Vec<(Token, Span)>, env: &Environment) -> Result<Start, Vec<CLMathError>> {
+        let mut parser = Parser::from(tokens, env);
+
+        let start = parser.start();
+        let start = match start {
+            Ok(start) if parser.errors.is_empty() => return Ok(start),
+            Ok(_) => None,
+            Err(err) => Some(err),
+        };
+        if let Some(err) = start {
+            parser.record(err);
+        }
+
+        parser.errors.sort_by_key(|err| err.span.start);
+        Err(parser.errors.into_iter().map(CLMathError::Parser).collect())
+    }
+
+    fn start(&mut self) -> Result<Start, ParserError> {
+        if self.tokens.is_empty() {
+            return Ok(Start::Empty);
+        }
+
+        let start = if matches!(
+            (self.tokens.front(), self.tokens.get(1)),
+            (
+                Some(Token::Variable(_)),
+                Some(Token::Symbol(Symbol::Equals))
+            )
+        ) {
+            Start::Assignment(self.assignment()?)
+        } else if matches!(self.tokens.front(), Some(Token::Function(Func::User(_))))
+            && self.tokens.contains(&Token::Symbol(Symbol::Equals))
+        {
+            Start::FunctionDefinition(self.function_definition()?)
         } else {
-            panic!("Queue cannot be empty. It was checked before to be not empty.")
+            Start::Expression(self.expression(0)?)
         };
 
-        if !self.tokens.is_empty() && self.returning {
+        // Reports the tokens after a complete expression and parses the rest of the input for more errors
+        while !self.tokens.is_empty() {
+            self.record(
+                ParserError::new(
+                    ParserErrorType::ExpectedElementaryFunction,
+                    self.next_span(),
+                )
+                .expecting(&[TokenKind::Operator, TokenKind::End]),
+            );
+            self.pop();
+
             if matches!(
                 self.tokens.front(),
                 Some(Token::Function(Func::Elementary(_)))
             ) {
-                self.lhs_queue.push_front(expression);
-                self.expression()
-            } else if self.expression_ends_queue.is_empty() {
-                Err(ParserError::ExpectedElementaryFunction)
-            } else {
-                Ok(expression)
+                self.operations(Parser::placeholder(self.next_span()), 0)?;
+            } else if !self.tokens.is_empty() {
+                self.expression(0)?;
             }
-        } else {
-            Ok(expression)
         }
+
+        Ok(start)
     }
 
-    fn expression_until(&mut self) -> Result<Expression, ParserError> {
-        let expression = if let Some(first_token) = self.tokens.front() {
-            match first_token {
-                Token::Symbol(Symbol::OpeningBracket) => {
-                    Expression::BracketedExpression(Box::new(self.bracketed_expression()?))
-                }
-                _ => Expression::MathExpression(Box::new(self.math_expression()?)),
-            }
-        } else {
-            panic!("Queue cannot be empty. It was checked before to be not empty.")
+    fn function_definition(&mut self) -> Result<FunctionDefinition, ParserError> {
+        let name = match self.pop() {
+            Some(Token::Function(Func::User(name))) => name,
+            _ => return Err(self.error(ParserErrorType::ExpectedMathExpression, &[])),
         };
 
-        let end = *self.expression_ends_queue.front().expect(
-            "Should be a value there. This function is only called after value is pushed in queue.",
-        );
+        let opening_bracket = self.opening_bracket()?;
 
-        if self.curr_idx < end && !self.tokens.is_empty() {
-            self.lhs_queue.push_front(expression);
-            if matches!(
-                self.tokens.front(),
-                Some(Token::Function(Func::Elementary(_)))
-            ) {
-                self.expression_until()
-            } else {
-                Err(ParserError::ExpectedElementaryFunction)
+        let params = self.parameter_names()?;
+
+        let closing_bracket = self.closing_bracket(&[TokenKind::Comma, TokenKind::ClosingBracket]);
+
+        let equals = self.equals()?;
+
+        // Known before parsing the body so that the function can call itself
+        self.user_function_param_counts
+            .insert(name.clone(), params.len());
+
+        let expression = self.expression(0)?;
+
+        Ok(FunctionDefinition {
+            name,
+            opening_bracket,
+            params,
+            closing_bracket,
+            equals,
+            expression,
+        })
+    }
+
+    /// Parses the comma separated names of the parameters of a function, every name can only be used once
+    fn parameter_names(&mut self) -> Result<Vec<Variable>, ParserError> {
+        let mut params = vec![self.variable()?];
+        while matches!(self.tokens.front(), Some(Token::Symbol(Symbol::Comma))) {
+            self.comma()?;
+
+            let param = self.variable()?;
+            if params.iter().any(|other| other.name == param.name) {
+                return Err(ParserError::new(
+                    ParserErrorType::DuplicateParameter { name: param.name },
+                    self.last_span,
+                ));
             }
-        } else {
-            Ok(expression)
+            params.push(param);
         }
+
+        Ok(params)
     }
 
-    fn bracketed_expression(&mut self) -> Result<BracketedExpression, ParserError> {
-        let opening_bracket = self.opening_bracket()?;
+    fn variable(&mut self) -> Result<Variable, ParserError> {
+        match self.pop() {
+            Some(Token::Variable(variable)) => Ok(variable),
+            _ => Err(self.error(ParserErrorType::ExpectedVariable, &[TokenKind::Variable])),
+        }
+    }
 
-        let mut idx = 0;
-        let mut opening_brackets = 1;
+    fn assignment(&mut self) -> Result<Assignment, ParserError> {
+        let variable = match self.pop() {
+            Some(Token::Variable(variable)) => variable,
+            _ => {
+                return Err(self.error(
+                    ParserErrorType::ExpectedMathExpression,
+                    &[TokenKind::Variable],
+                ))
+            }
+        };
 
-        loop {
-            match self.tokens.get(idx) {
-                Some(Token::Symbol(Symbol::OpeningBracket)) => opening_brackets += 1,
-                Some(Token::Symbol(Symbol::ClosingBracket)) => opening_brackets -= 1,
-                Some(_) => (),
-                None => return Err(ParserError::ExpectedClosingBracket),
-            };
+        let equals = self.equals()?;
 
-            if opening_brackets == 0 {
+        let expression = self.expression(0)?;
+
+        Ok(Assignment {
+            variable,
+            equals,
+            expression,
+        })
+    }
+
+    /// Parses an expression using precedence climbing.
+    /// Only elementary functions with a precedence of at least `min_precedence` are consumed,
+    /// everything with a lower precedence is left for the caller.
+    fn expression(&mut self, min_precedence: u8) -> Result<Expression, ParserError> {
+        // Deeply nested brackets continue on a new stack segment like their evaluation
+        stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, || {
+            let expression_lhs = self.operand()?;
+
+            self.operations(expression_lhs, min_precedence)
+        })
+    }
+
+    /// Parses the elementary functions following the left hand side of an expression
+    fn operations(
+        &mut self,
+        mut expression_lhs: Expression,
+        min_precedence: u8,
+    ) -> Result<Expression, ParserError> {
+        let start = expression_lhs.span();
+
+        while let Some(Token::Function(Func::Elementary(function))) = self.tokens.front() {
+            let precedence = function.get_precedence();
+
+            if precedence < min_precedence {
                 break;
-            } else {
-                idx += 1;
             }
+
+            let function = function.clone();
+            self.pop();
+
+            let next_min_precedence = match function.get_associativity() {
+                Associativity::Left => precedence + 1,
+                Associativity::Right => precedence,
+            };
+
+            let expression_rhs = self.expression(next_min_precedence)?;
+
+            expression_lhs = Expression::MathExpression(
+                Box::new(MathExpression::Function(Function::ElementaryFunction(
+                    ElementaryFunction {
+                        expression_lhs,
+                        function,
+                        expression_rhs,
+                    },
+                ))),
+                start.to(self.last_span),
+            );
+        }
+
+        Ok(expression_lhs)
+    }
+
+    /// Parses an operand. A missing or invalid operand is recorded and replaced by a placeholder,
+    /// so that parsing can continue at the next comma, closing bracket or operator.
+    fn operand(&mut self) -> Result<Expression, ParserError> {
+        let start = self.next_span();
+
+        if self.tokens.front().is_none_or(Parser::is_synchronizing) {
+            let error_type = match self.tokens.front() {
+                None => ParserErrorType::ExpressionEmpty,
+                Some(Token::Function(Func::Elementary(_))) => {
+                    ParserErrorType::NoLhsExpressionProvided
+                }
+                Some(_) => ParserErrorType::ExpectedMathExpression,
+            };
+            self.record(ParserError::new(error_type, start).expecting(&[TokenKind::Operand]));
+
+            return Ok(Parser::placeholder(Span::new(start.start, start.start)));
+        }
+
+        if self.is_lambda_ahead() {
+            let lambda = self.lambda()?;
+            return Ok(Expression::MathExpression(
+                Box::new(MathExpression::Lambda(lambda)),
+                start.to(self.last_span),
+            ));
+        }
+
+        match self.tokens.front() {
+            Some(Token::Symbol(Symbol::OpeningBracket)) => {
+                let expression = self.bracketed_expression()?;
+                Ok(Expression::BracketedExpression(
+                    Box::new(expression),
+                    start.to(self.last_span),
+                ))
+            }
+            Some(_) => match self.math_expression() {
+                Ok(expression) => Ok(Expression::MathExpression(
+                    Box::new(expression),
+                    start.to(self.last_span),
+                )),
+                Err(err) => {
+                    self.record(err);
+                    self.skip_until(Parser::is_synchronizing);
+                    Ok(Parser::placeholder(start.to(self.last_span)))
+                }
+            },
+            None => unreachable!("An empty input is handled as a missing operand."),
+        }
+    }
+
+    /// Returns true if the next tokens are the parameters of an anonymous function followed by an arrow, e.g. `x ->` or `(x, y) ->`
+    fn is_lambda_ahead(&self) -> bool {
+        match (self.tokens.front(), self.tokens.get(1)) {
+            (Some(Token::Variable(_)), Some(Token::Symbol(Symbol::Arrow))) => true,
+            (Some(Token::Symbol(Symbol::OpeningBracket)), _) => {
+                let mut idx = 1;
+                loop {
+                    match (self.tokens.get(idx), self.tokens.get(idx + 1)) {
+                        (Some(Token::Variable(_)), Some(Token::Symbol(Symbol::Comma))) => idx += 2,
+                        (Some(Token::Variable(_)), Some(Token::Symbol(Symbol::ClosingBracket))) => {
+                            return matches!(
+                                self.tokens.get(idx + 2),
+                                Some(Token::Symbol(Symbol::Arrow))
+                            )
+                        }
+                        _ => return false,
+                    }
+                }
+            }
+            _ => false,
         }
+    }
+
+    fn lambda(&mut self) -> Result<Lambda, ParserError> {
+        let params = if matches!(
+            self.tokens.front(),
+            Some(Token::Symbol(Symbol::OpeningBracket))
+        ) {
+            self.opening_bracket()?;
+
+            let params = self.parameter_names()?;
+
+            self.closing_bracket(&[TokenKind::Comma, TokenKind::ClosingBracket]);
 
-        self.expression_ends_queue.push_front(idx + self.curr_idx); // Offset from start
+            params
+        } else {
+            vec![self.variable()?]
+        };
+
+        let arrow = self.arrow()?;
+
+        let body = self.expression(0)?;
+
+        Ok(Lambda {
+            params,
+            arrow,
+            body: Rc::new(body),
+        })
+    }
 
-        let expression = self.expression_until()?;
+    fn bracketed_expression(&mut self) -> Result<BracketedExpression, ParserError> {
+        let opening_bracket = self.opening_bracket()?;
 
-        self.expression_ends_queue.pop_front();
+        let expression = self.expression(0)?;
 
-        let closing_bracket = self.closing_bracket()?;
+        let closing_bracket =
+            self.closing_bracket(&[TokenKind::Operator, TokenKind::ClosingBracket]);
 
         Ok(BracketedExpression {
             opening_bracket,
@@ -215,42 +690,81 @@ impl Parser {
         if let Some(token) = self.pop() {
             let expr = match token {
                 Token::Number(num) => MathExpression::Number(num),
+                Token::Constant(constant) => MathExpression::Constant(constant),
                 Token::Variable(var) => MathExpression::Variable(var),
-                Token::Function(func) => MathExpression::Function(self.function(func)?),
-                _ => return Err(ParserError::ExpectedMathExpression),
+                Token::Function(Func::HigherOrder(func))
+                    if !func.has_variable_param()
+                        && !matches!(
+                            self.tokens.front(),
+                            Some(Token::Symbol(Symbol::OpeningBracket))
+                        ) =>
+                {
+                    MathExpression::Lambda(self.builtin_lambda(func))
+                }
+                Token::Function(Func::HigherOrder(func)) => MathExpression::Function(
+                    Function::HigherOrderFunction(self.higher_order_function(func)?),
+                ),
+                Token::Function(Func::User(name)) => {
+                    MathExpression::Function(Function::UserFunction(self.user_function(name)?))
+                }
+                Token::Function(Func::Unary(func)) => {
+                    MathExpression::Function(Function::UnaryFunction(self.unary_function(func)?))
+                }
+                Token::Symbol(Symbol::OpeningSquareBracket) => {
+                    MathExpression::List(self.list_literal()?)
+                }
+                Token::Function(Func::Elementary(_)) => {
+                    return Err(self.error(
+                        ParserErrorType::NoLhsExpressionProvided,
+                        &[TokenKind::Operand],
+                    ))
+                }
+                _ => {
+                    return Err(self.error(
+                        ParserErrorType::ExpectedMathExpression,
+                        &[TokenKind::Operand],
+                    ))
+                }
             };
 
             Ok(expr)
         } else {
-            Err(ParserError::ExpressionEmpty)
+            Err(self.error(ParserErrorType::ExpressionEmpty, &[TokenKind::Operand]))
         }
     }
 
-    fn function(&mut self, function: Func) -> Result<Function, ParserError> {
-        let f = match function {
-            Func::Elementary(func) => Function::ElementaryFunction(self.elementary_function(func)?),
-            Func::HigherOrder(func) => {
-                Function::HigherOrderFunction(self.higher_order_function(func)?)
+    /// Parses the comma separated elements of a list after its opening square bracket, lists can be empty
+    fn list_literal(&mut self) -> Result<ListLiteral, ParserError> {
+        let mut elements = Vec::new();
+
+        if !matches!(
+            self.tokens.front(),
+            Some(Token::Symbol(Symbol::ClosingSquareBracket))
+        ) {
+            elements.push(self.expression(0)?);
+
+            while matches!(self.tokens.front(), Some(Token::Symbol(Symbol::Comma))) {
+                self.comma()?;
+                elements.push(self.expression(0)?);
             }
-        };
+        }
 
-        Ok(f)
+        let closing_square_bracket = self.closing_square_bracket();
+
+        Ok(ListLiteral {
+            opening_square_bracket: OpeningSquareBracket,
+            elements,
+            closing_square_bracket,
+        })
     }
 
-    fn elementary_function(
-        &mut self,
-        function: ElementaryFunc,
-    ) -> Result<ElementaryFunction, ParserError> {
-        if let Some(expression_lhs) = self.lhs_queue.pop_front() {
-            let expression_rhs = self.non_returning_expression()?; // ensures left to right execution of expression
-            Ok(ElementaryFunction {
-                expression_lhs,
-                function,
-                expression_rhs,
-            })
-        } else {
-            Err(ParserError::NoLhsExpressionProvided)
-        }
+    fn unary_function(&mut self, function: UnaryFunc) -> Result<UnaryFunction, ParserError> {
+        let expression = self.expression(function.get_precedence())?;
+
+        Ok(UnaryFunction {
+            function,
+            expression,
+        })
     }
 
     fn higher_order_function(
@@ -259,9 +773,17 @@ impl Parser {
     ) -> Result<HigherOrderFunction, ParserError> {
         let opening_bracket = self.opening_bracket()?;
 
-        let params = self.params(&function)?;
+        let params = self.params(
+            function.get_keyword(),
+            *function.get_param_count() as usize,
+            function.is_variadic(),
+        )?;
 
-        let closing_bracket = self.closing_bracket()?;
+        let closing_bracket = self.closing_bracket(&[
+            TokenKind::Operator,
+            TokenKind::Comma,
+            TokenKind::ClosingBracket,
+        ]);
 
         Ok(HigherOrderFunction {
             function,
@@ -271,62 +793,319 @@ impl Parser {
         })
     }
 
+    /// Creates an anonymous function that calls the built-in function with its parameters, so that built-in functions
+    /// can be passed to other functions without brackets, e.g. `Sin` in `Map([1, 2], Sin)` is `x -> Sin(x)`
+    fn builtin_lambda(&mut self, function: HigherOrderFunc) -> Lambda {
+        let span = self.last_span;
+        let params = (0..*function.get_param_count() as usize)
+            .map(|idx| match ["x", "y", "z"].get(idx) {
+                Some(name) => name.to_string(),
+                None => format!("x{}", idx + 1),
+            })
+            .map(Variable::new)
+            .collect::<Vec<_>>();
+
+        let mut args = params.iter().map(|param| {
+            Expression::MathExpression(Box::new(MathExpression::Variable(param.clone())), span)
+        });
+        let first_arg = args
+            .next()
+            .expect("Every built-in function takes a parameter.");
+        let other_args = args.map(|arg| (Comma, arg)).collect::<Vec<_>>();
+
+        let body = Expression::MathExpression(
+            Box::new(MathExpression::Function(Function::HigherOrderFunction(
+                HigherOrderFunction {
+                    function,
+                    opening_bracket: OpeningBracket,
+                    params: Params::new(
+                        first_arg,
+                        Some(other_args).filter(|args| !args.is_empty()),
+                    ),
+                    closing_bracket: ClosingBracket,
+                },
+            ))),
+            span,
+        );
+
+        Lambda {
+            params,
+            arrow: Arrow,
+            body: Rc::new(body),
+        }
+    }
+
+    fn user_function(&mut self, name: String) -> Result<UserFunction, ParserError> {
+        let param_count = *self
+            .user_function_param_counts
+            .get(&name)
+            .expect("The tokenizer only creates calls of defined functions.");
+
+        let opening_bracket = self.opening_bracket()?;
+
+        let params = self.params(&name, param_count, false)?;
+
+        let closing_bracket = self.closing_bracket(&[
+            TokenKind::Operator,
+            TokenKind::Comma,
+            TokenKind::ClosingBracket,
+        ]);
+
+        Ok(UserFunction {
+            name,
+            opening_bracket,
+            params,
+            closing_bracket,
+        })
+    }
+
     fn opening_bracket(&mut self) -> Result<OpeningBracket, ParserError> {
         if let Some(token) = self.pop() {
             match token {
                 Token::Symbol(Symbol::OpeningBracket) => Ok(OpeningBracket),
-                _ => Err(ParserError::ExpectedOpeningBracket),
+                _ => Err(self.error(
+                    ParserErrorType::ExpectedOpeningBracket,
+                    &[TokenKind::OpeningBracket],
+                )),
             }
         } else {
-            Err(ParserError::ExpressionEmpty)
+            Err(self.error(
+                ParserErrorType::ExpressionEmpty,
+                &[TokenKind::OpeningBracket],
+            ))
         }
     }
 
-    fn closing_bracket(&mut self) -> Result<ClosingBracket, ParserError> {
-        if let Some(token) = self.pop() {
-            match token {
-                Token::Symbol(Symbol::ClosingBracket) => Ok(ClosingBracket),
-                _ => Err(ParserError::ExpectedClosingBracket),
-            }
-        } else {
-            Err(ParserError::ExpressionEmpty)
+    /// Parses a closing bracket. A missing bracket is recorded and the tokens up to the next closing bracket are skipped.
+    fn closing_bracket(&mut self, expected: &[TokenKind]) -> ClosingBracket {
+        if !matches!(
+            self.tokens.front(),
+            Some(Token::Symbol(Symbol::ClosingBracket))
+        ) {
+            self.record(
+                ParserError::new(ParserErrorType::ExpectedClosingBracket, self.next_span())
+                    .expecting(expected),
+            );
+            self.skip_until(|token| *token == Token::Symbol(Symbol::ClosingBracket));
         }
+
+        self.pop();
+        ClosingBracket
     }
 
-    fn non_returning_expression(&mut self) -> Result<Expression, ParserError> {
-        self.returning = false;
-        let expression = self.expression()?;
-        self.returning = true;
+    /// Parses a closing square bracket. A missing bracket is recorded and the tokens up to the next closing square bracket are skipped.
+    fn closing_square_bracket(&mut self) -> ClosingSquareBracket {
+        if !matches!(
+            self.tokens.front(),
+            Some(Token::Symbol(Symbol::ClosingSquareBracket))
+        ) {
+            self.record(
+                ParserError::new(
+                    ParserErrorType::ExpectedClosingSquareBracket,
+                    self.next_span(),
+                )
+                .expecting(&[
+                    TokenKind::Operator,
+                    TokenKind::Comma,
+                    TokenKind::ClosingSquareBracket,
+                ]),
+            );
+            self.skip_until(|token| *token == Token::Symbol(Symbol::ClosingSquareBracket));
+        }
 
-        Ok(expression)
+        self.pop();
+        ClosingSquareBracket
     }
 
-    fn params(&mut self, function_type: &HigherOrderFunc) -> Result<Params, ParserError> {
-        let param_count = function_type.get_param_count();
+    /// Parses the comma separated parameters of a function call.
+    /// Variadic functions take at least `param_count` parameters, all other functions exactly `param_count`.
+    fn params(
+        &mut self,
+        function_name: &str,
+        param_count: usize,
+        is_variadic: bool,
+    ) -> Result<Params, ParserError> {
+        let start = self.next_span();
+        let expression = self.param()?;
 
-        let expression = self.non_returning_expression()?;
+        let mut expression_comma = Vec::new();
 
-        if *param_count > 1 {
-            let mut expression_comma = Vec::new();
+        while matches!(self.tokens.front(), Some(Token::Symbol(Symbol::Comma))) {
+            let comma = self.comma()?;
 
-            for _ in 1..*param_count {
-                let comma = self.comma()?;
+            let expression = self.param()?;
+            expression_comma.push((comma, expression))
+        }
 
-                let expression = self.non_returning_expression()?;
-                expression_comma.push((comma, expression))
-            }
+        if is_variadic && expression_comma.len() + 1 < param_count {
+            self.record(ParserError::new(
+                ParserErrorType::TooFewParams {
+                    function: function_name.to_string(),
+                    minimum: param_count,
+                    found: expression_comma.len() + 1,
+                },
+                start.to(self.last_span),
+            ));
+        }
 
-            return Ok(Params::new(expression, Some(expression_comma)));
+        if !is_variadic && expression_comma.len() + 1 != param_count {
+            self.record(ParserError::new(
+                ParserErrorType::InvalidNumberOfParams {
+                    function: function_name.to_string(),
+                    expected: param_count,
+                    found: expression_comma.len() + 1,
+                },
+                start.to(self.last_span),
+            ));
         }
 
-        Ok(Params::new(expression, None))
+        if expression_comma.is_empty() {
+            Ok(Params::new(expression, None))
+        } else {
+            Ok(Params::new(expression, Some(expression_comma)))
+        }
+    }
+
+    /// Parses a parameter of a function call, which can also be an equation, e.g. `x^2 = 2` in `Solve(x^2 = 2, x)`
+    fn param(&mut self) -> Result<Expression, ParserError> {
+        let lhs = self.expression(0)?;
+
+        if !matches!(self.tokens.front(), Some(Token::Symbol(Symbol::Equals))) {
+            return Ok(lhs);
+        }
+
+        let equals = self.equals()?;
+        let rhs = self.expression(0)?;
+        let span = lhs.span().to(rhs.span());
+
+        Ok(Expression::MathExpression(
+            Box::new(MathExpression::Equation(Equation { lhs, equals, rhs })),
+            span,
+        ))
+    }
+
+    fn arrow(&mut self) -> Result<Arrow, ParserError> {
+        if matches!(self.pop(), Some(Token::Symbol(Symbol::Arrow))) {
+            Ok(Arrow)
+        } else {
+            Err(self.error(ParserErrorType::ExpectedArrow, &[TokenKind::Arrow]))
+        }
+    }
+
+    fn equals(&mut self) -> Result<Equals, ParserError> {
+        if matches!(self.pop(), Some(Token::Symbol(Symbol::Equals))) {
+            Ok(Equals)
+        } else {
+            Err(self.error(ParserErrorType::ExpectedEquals, &[TokenKind::Equals]))
+        }
     }
 
     fn comma(&mut self) -> Result<Comma, ParserError> {
         if matches!(self.pop(), Some(Token::Symbol(Symbol::Comma))) {
             Ok(Comma)
         } else {
-            Err(ParserError::ExpectedComma)
+            Err(self.error(ParserErrorType::ExpectedComma, &[TokenKind::Comma]))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::tokenize;
+
+    fn parse_errors(input: &str) -> Vec<ParserError> {
+        let env = Environment::new();
+        let tokens = tokenize(input.to_string(), &env).unwrap();
+
+        match Parser::parse(tokens, &env) {
+            Ok(_) => Vec::new(),
+            Err(errors) => errors
+                .into_iter()
+                .map(|err| match err {
+                    CLMathError::Parser(err) => err,
+                    _ => panic!("The parser only returns parser errors."),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_error_recovery() {
+        assert!(parse_errors("Max(1, 2) + (3 * 4)").is_empty());
+
+        let errors = parse_errors("Max(1, , 3) + Sin(2 ] * 3");
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(
+            errors[0].error_type,
+            ParserErrorType::InvalidNumberOfParams { found: 3, .. }
+        ));
+        assert!(matches!(
+            errors[1].error_type,
+            ParserErrorType::ExpectedMathExpression
+        ));
+        assert_eq!(errors[1].span, Span::new(7, 8));
+        assert_eq!(errors[1].expected, vec![TokenKind::Operand]);
+        assert!(matches!(
+            errors[2].error_type,
+            ParserErrorType::ExpectedClosingBracket
+        ));
+        assert_eq!(errors[2].span, Span::new(20, 21));
+        assert_eq!(
+            errors[2].expected,
+            vec![
+                TokenKind::Operator,
+                TokenKind::Comma,
+                TokenKind::ClosingBracket
+            ]
+        );
+
+        let errors = parse_errors("1 + * 2) * 3");
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            errors[0].error_type,
+            ParserErrorType::NoLhsExpressionProvided
+        ));
+        assert_eq!(
+            errors[1].expected,
+            vec![TokenKind::Operator, TokenKind::End]
+        );
+    }
+
+    #[test]
+    fn test_errors_at_end() {
+        let errors = parse_errors("Sin(1 +");
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0].error_type,
+            ParserErrorType::ExpressionEmpty
+        ));
+        assert_eq!(errors[0].span, Span::new(7, 7));
+
+        let errors = parse_errors("[1, 2");
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0].error_type,
+            ParserErrorType::ExpectedClosingSquareBracket
+        ));
+    }
+
+    #[test]
+    fn test_duplicate_parameters() {
+        let errors = parse_errors("f(x, y, x) = x + y");
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0].error_type,
+            ParserErrorType::DuplicateParameter { name } if name == "x"
+        ));
+        assert_eq!(errors[0].span, Span::new(8, 9));
+
+        let errors = parse_errors("Apply((a, a) -> a, 1, 2)");
+        assert!(matches!(
+            &errors[0].error_type,
+            ParserErrorType::DuplicateParameter { name } if name == "a"
+        ));
+
+        assert!(parse_errors("f(x, y) = x + y").is_empty());
+    }
+}
@@ -0,0 +1,193 @@
+use std::fmt::Display;
+
+use crate::{
+    commands,
+    environment::Environment,
+    error::Error,
+    executor,
+    functions::{FunctionReturnType, HIGHER_ORDER_FUNC_KEYWORDS},
+    parser::Parser,
+    tokenizer,
+};
+
+/// Evaluates inputs one after another. Variables, functions and the modes set by commands are kept for all following inputs.
+pub struct Session {
+    environment: Environment,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self {
+            environment: Environment::new(),
+        }
+    }
+
+    /// Evaluates an expression, an assignment or a function definition. Returns the value of expressions and assignments
+    /// and `None` for function definitions and empty inputs.
+    pub fn evaluate(&mut self, input: &str) -> Result<Option<Value>, Error> {
+        self.execute(input)
+            .map(|value| value.map(Value))
+            .map_err(|err| err.with_input(input))
+    }
+
+    fn execute(&mut self, input: &str) -> Result<Option<FunctionReturnType>, Error> {
+        let tokens = tokenizer::tokenize(input.to_string(), &self.environment)?;
+        let expression = Parser::parse(tokens, &self.environment)?;
+
+        Ok(executor::execute(expression, &mut self.environment)?)
+    }
+
+    /// Returns true if the input is a command changing the session, e.g. `:angle deg`, instead of a math expression
+    pub fn is_command(input: &str) -> bool {
+        commands::is_command(input)
+    }
+
+    /// Runs a command changing the session, e.g. `:angle deg`, and returns a message describing the new state
+    pub fn run_command(&mut self, input: &str) -> Result<String, Error> {
+        commands::execute_command(input, &mut self.environment)
+            .map_err(|err| Error::from(err).with_input(input))
+    }
+
+    /// Returns the names of the built-in functions, variables and user-defined functions starting with the prefix in sorted order
+    pub fn completions(&self, prefix: &str) -> Vec<String> {
+        let mut names = HIGHER_ORDER_FUNC_KEYWORDS
+            .keys()
+            .map(|keyword| keyword.to_string())
+            .chain(self.environment.get_variable_names().cloned())
+            .chain(self.environment.get_function_names().cloned())
+            .filter(|name| name.starts_with(prefix))
+            .collect::<Vec<_>>();
+
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The result of an input. It is displayed in the same syntax as the input, e.g. `3/4`, `[1, 2]` or `2x + 1`.
+#[derive(Debug, Clone)]
+pub struct Value(FunctionReturnType);
+
+impl Value {
+    /// Returns the name of the type of the value, e.g. `integer`, `fraction`, `list` or `expression`
+    pub fn type_name(&self) -> &'static str {
+        self.0.get_type_name()
+    }
+
+    /// Returns the value as a 64bit floating point number if it is a real number that fits into its range
+    pub fn to_f64(&self) -> Option<f64> {
+        self.0.clone().get_f64().ok()
+    }
+
+    /// Returns the value if it is a boolean, e.g. the result of a comparison
+    pub fn to_bool(&self) -> Option<bool> {
+        self.0.clone().get_bool().ok()
+    }
+
+    /// Returns the elements if the value is a list, e.g. a vector or the rows of a matrix
+    pub fn to_list(&self) -> Option<Vec<Value>> {
+        self.0
+            .clone()
+            .get_list()
+            .ok()
+            .map(|values| values.into_iter().map(Value).collect())
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session() {
+        let mut session = Session::new();
+
+        assert!(session.evaluate("x = 3").unwrap().is_some());
+        assert!(session.evaluate("f(t) = t^2").unwrap().is_none());
+        assert!(session.evaluate("").unwrap().is_none());
+
+        let value = session.evaluate("f(x) / 2").unwrap().unwrap();
+        assert_eq!(value.to_string(), "4.5");
+        assert_eq!(value.type_name(), "f64");
+        assert_eq!(value.to_f64(), Some(4.5));
+        assert_eq!(value.to_bool(), None);
+
+        let list = session.evaluate("[1, x < 2]").unwrap().unwrap().to_list();
+        assert_eq!(list.as_ref().map(Vec::len), Some(2));
+        assert_eq!(list.unwrap()[1].to_bool(), Some(false));
+
+        assert!(Session::is_command(":exact on"));
+        assert_eq!(session.run_command(":exact on").unwrap(), "Exact mode: on");
+        assert_eq!(
+            session.evaluate("f(x) / 2").unwrap().unwrap().to_string(),
+            "9/2"
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut session = Session::new();
+
+        let error = session.evaluate("1 / 0").unwrap_err();
+        assert!(matches!(error.errors(), [crate::CLMathError::Function(_)]));
+        assert!(error.to_string().contains("DivisionByZero"));
+
+        assert!(session.evaluate("Sin(1, 2)").is_err());
+        assert!(session.run_command(":unknown").is_err());
+    }
+
+    #[test]
+    fn test_completions() {
+        let mut session = Session::new();
+        session.evaluate("sum = 1").unwrap();
+        session.evaluate("square(x) = x^2").unwrap();
+
+        assert_eq!(session.completions("Sq"), vec!["Sqrt"]);
+        assert_eq!(session.completions("s"), vec!["square", "sum"]);
+        assert!(session.completions("").contains(&"Integrate".to_string()));
+        assert!(session.completions("zz").is_empty());
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let mut session = Session::new();
+        let underline = |error: Error| error.to_string().lines().last().unwrap().to_string();
+
+        assert_eq!(
+            underline(session.evaluate("1 + 2 $").unwrap_err()),
+            "          ^"
+        );
+        assert_eq!(
+            underline(session.evaluate("(1 + 2").unwrap_err()),
+            "          ^"
+        );
+        assert_eq!(
+            underline(session.evaluate("Sin(1, 2)").unwrap_err()),
+            "        ^~~~"
+        );
+        assert_eq!(
+            underline(session.evaluate("2 + 1/0").unwrap_err()),
+            "        ^~~"
+        );
+        assert_eq!(
+            underline(session.run_command(":angle turns").unwrap_err()),
+            "           ^~~~~"
+        );
+
+        session.evaluate("f(x) = 1/x").unwrap();
+        let error = session.evaluate("1 + f(0)").unwrap_err();
+        assert_eq!(error.errors()[0].span(), Some(crate::Span::new(4, 8)));
+    }
+}
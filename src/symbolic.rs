@@ -0,0 +1,1337 @@
+use std::{cmp::Ordering, collections::BTreeSet, fmt::Display};
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+use crate::{
+    environment::AngleMode,
+    error::{FunctionError, FunctionErrorType},
+    functions::{
+        rational_value, Constant, ElementaryFunc, FunctionReturnType, HigherOrderFunc, UnaryFunc,
+    },
+    units::UNIT_KEYWORDS,
+};
+
+/// Powers of exact numbers with larger exponents are kept as powers instead of being calculated
+const MAX_EXACT_EXPONENT: i32 = 1024;
+
+/// Integer powers of sums with larger exponents are not multiplied out
+const MAX_EXPANDED_EXPONENT: u32 = 64;
+
+/// Polynomials of a higher degree are not factored
+const MAX_FACTORED_DEGREE: usize = 64;
+
+/// Rational roots are only searched if the constant term and the leading coefficient are at most this large
+const MAX_DIVISOR_SEARCH: u64 = 1_000_000;
+
+/// Numbers, constants, variables and function calls bind tighter than every elementary function
+const OPERAND_PRECEDENCE: u8 = u8::MAX;
+
+/// A formula with free variables, e.g. `2x + 1`.
+/// Expressions are only created in a simplified form: sums and products are flat, numbers are combined,
+/// like terms and powers of the same base are collected and the terms and factors are sorted.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Symbolic {
+    /// An exact number, i.e. an integer or a fraction
+    Number(BigRational),
+    /// A 64bit floating point number, e.g. from a decimal literal
+    Float(f64),
+    Constant(Constant),
+    Variable(String),
+    Sum(Vec<Symbolic>),
+    /// A product of factors, a number is always the first factor
+    Product(Vec<Symbolic>),
+    Power(Box<Symbolic>, Box<Symbolic>),
+    /// A call of a predefined function, e.g. `Sin(x)`
+    Function(HigherOrderFunc, Vec<Symbolic>),
+}
+
+impl Symbolic {
+    pub fn integer(num: i64) -> Self {
+        Symbolic::Number(BigRational::from_integer(BigInt::from(num)))
+    }
+
+    pub fn fraction(numerator: i64, denominator: i64) -> Self {
+        Symbolic::Number(BigRational::new(
+            BigInt::from(numerator),
+            BigInt::from(denominator),
+        ))
+    }
+
+    /// Converts a calculated value to an expression, e.g. the 2 in `2x`
+    pub fn from_value(value: FunctionReturnType) -> Result<Self, FunctionError> {
+        match value {
+            FunctionReturnType::BigInt(num) => Ok(Symbolic::Number(BigRational::from(num))),
+            FunctionReturnType::Rational(num) => Ok(Symbolic::Number(num)),
+            FunctionReturnType::F64(num) => Ok(Symbolic::Float(num)),
+            value @ FunctionReturnType::Decimal(_) => Ok(Symbolic::Float(value.get_f64()?)),
+            FunctionReturnType::Symbolic(expression) => Ok(expression),
+            other => Err(FunctionError::new(
+                format!(
+                    "Only numbers can be part of a symbolic expression but got a {} {}.",
+                    other.get_type_name(),
+                    other
+                ),
+                FunctionErrorType::InvalidParameterType,
+            )),
+        }
+    }
+
+    pub fn is_number(&self) -> bool {
+        matches!(self, Symbolic::Number(_) | Symbolic::Float(_))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Symbolic::Number(num) => num.is_zero(),
+            Symbolic::Float(num) => *num == 0.0,
+            _ => false,
+        }
+    }
+
+    fn is_one(&self) -> bool {
+        match self {
+            Symbolic::Number(num) => num.is_one(),
+            Symbolic::Float(num) => *num == 1.0,
+            _ => false,
+        }
+    }
+
+    fn is_integer(&self) -> bool {
+        matches!(self, Symbolic::Number(num) if num.is_integer())
+    }
+
+    /// Returns true for negative numbers and products with a negative number as their coefficient, e.g. `-2x`
+    fn is_negative(&self) -> bool {
+        match self {
+            Symbolic::Number(num) => num.is_negative(),
+            Symbolic::Float(num) => *num < 0.0,
+            Symbolic::Product(factors) => factors[0].is_negative(),
+            _ => false,
+        }
+    }
+
+    /// Returns the name of the first variable in the expression
+    fn get_first_variable(&self) -> Option<&str> {
+        match self {
+            Symbolic::Variable(name) => Some(name),
+            Symbolic::Sum(params) | Symbolic::Product(params) | Symbolic::Function(_, params) => {
+                params.iter().find_map(Symbolic::get_first_variable)
+            }
+            Symbolic::Power(base, exponent) => base
+                .get_first_variable()
+                .or_else(|| exponent.get_first_variable()),
+            Symbolic::Number(_) | Symbolic::Float(_) | Symbolic::Constant(_) => None,
+        }
+    }
+
+    /// Returns true if the expression depends on the variable
+    pub fn contains_variable(&self, name: &str) -> bool {
+        match self {
+            Symbolic::Variable(variable) => variable == name,
+            Symbolic::Sum(params) | Symbolic::Product(params) | Symbolic::Function(_, params) => {
+                params.iter().any(|param| param.contains_variable(name))
+            }
+            Symbolic::Power(base, exponent) => {
+                base.contains_variable(name) || exponent.contains_variable(name)
+            }
+            Symbolic::Number(_) | Symbolic::Float(_) | Symbolic::Constant(_) => false,
+        }
+    }
+
+    /// Returns the simplified sum of the terms, e.g. `2x + 3x + 1` is `5x + 1`
+    pub fn sum(terms: Vec<Symbolic>) -> Self {
+        let mut constant = Symbolic::integer(0);
+        let mut like_terms: Vec<(Symbolic, Symbolic)> = Vec::new();
+
+        for term in terms.into_iter().flat_map(Symbolic::into_terms) {
+            if term.is_number() {
+                constant = add_numbers(&constant, &term);
+                continue;
+            }
+
+            let (coefficient, rest) = term.split_coefficient();
+            match like_terms.iter_mut().find(|(_, other)| *other == rest) {
+                Some((sum, _)) => *sum = add_numbers(sum, &coefficient),
+                None => like_terms.push((coefficient, rest)),
+            }
+        }
+
+        let mut terms = like_terms
+            .into_iter()
+            .filter(|(coefficient, _)| !coefficient.is_zero())
+            .map(|(coefficient, rest)| Symbolic::product(vec![coefficient, rest]))
+            .collect::<Vec<_>>();
+        terms.sort_by(compare_terms);
+
+        if !constant.is_zero() || terms.is_empty() {
+            terms.push(constant);
+        }
+
+        collect(terms, Symbolic::Sum)
+    }
+
+    /// Returns the simplified product of the factors, e.g. `2x * 3x` is `6x^2`
+    pub fn product(factors: Vec<Symbolic>) -> Self {
+        let mut coefficient = Symbolic::integer(1);
+        let mut powers: Vec<(Symbolic, Symbolic)> = Vec::new();
+
+        for factor in factors.into_iter().flat_map(Symbolic::into_factors) {
+            if factor.is_number() {
+                coefficient = multiply_numbers(&coefficient, &factor);
+                continue;
+            }
+
+            let (base, exponent) = factor.split_exponent();
+            match powers.iter_mut().find(|(other, _)| *other == base) {
+                Some((_, sum)) => *sum = Symbolic::sum(vec![sum.clone(), exponent]),
+                None => powers.push((base, exponent)),
+            }
+        }
+
+        if coefficient.is_zero() {
+            return coefficient;
+        }
+
+        let powers = powers
+            .into_iter()
+            .map(|(base, exponent)| Symbolic::power(base, exponent))
+            .collect::<Vec<_>>();
+
+        // Collected powers can be products again, e.g. `Sqrt(2x) * Sqrt(2x)` is `2x`, whose factors have to be combined as well
+        if powers
+            .iter()
+            .any(|power| matches!(power, Symbolic::Product(_)))
+        {
+            return Symbolic::product(std::iter::once(coefficient).chain(powers).collect());
+        }
+
+        let mut factors = Vec::new();
+        for power in powers {
+            if power.is_number() {
+                coefficient = multiply_numbers(&coefficient, &power);
+            } else {
+                factors.push(power);
+            }
+        }
+        factors.sort_by(compare_factors);
+
+        if coefficient.is_zero() {
+            return coefficient;
+        }
+
+        if !coefficient.is_one() || factors.is_empty() {
+            factors.insert(0, coefficient);
+        }
+
+        collect(factors, Symbolic::Product)
+    }
+
+    /// Returns the simplified power, e.g. `(x^2)^3` is `x^6`
+    pub fn power(base: Symbolic, exponent: Symbolic) -> Self {
+        if exponent.is_zero() {
+            return Symbolic::integer(1);
+        }
+
+        if exponent.is_one() || base.is_one() {
+            return base;
+        }
+
+        match (base, exponent) {
+            (Symbolic::Number(base), Symbolic::Number(exponent)) if exponent.is_integer() => {
+                match exponent.to_integer().to_i32() {
+                    Some(exponent)
+                        if exponent.abs() <= MAX_EXACT_EXPONENT
+                            && !(base.is_zero() && exponent < 0) =>
+                    {
+                        Symbolic::Number(base.pow(exponent))
+                    }
+                    _ => Symbolic::Power(
+                        Box::new(Symbolic::Number(base)),
+                        Box::new(Symbolic::Number(exponent)),
+                    ),
+                }
+            }
+            (base @ Symbolic::Float(_), exponent) | (base, exponent @ Symbolic::Float(_))
+                if base.is_number() && exponent.is_number() =>
+            {
+                let result = number_to_f64(&base).powf(number_to_f64(&exponent));
+
+                if result.is_finite() {
+                    Symbolic::Float(result)
+                } else {
+                    Symbolic::Power(Box::new(base), Box::new(exponent))
+                }
+            }
+            (Symbolic::Power(base, inner), exponent) if exponent.is_integer() => {
+                Symbolic::power(*base, Symbolic::product(vec![*inner, exponent]))
+            }
+            (Symbolic::Product(factors), exponent) if exponent.is_integer() => Symbolic::product(
+                factors
+                    .into_iter()
+                    .map(|factor| Symbolic::power(factor, exponent.clone()))
+                    .collect(),
+            ),
+            (base, exponent) => Symbolic::Power(Box::new(base), Box::new(exponent)),
+        }
+    }
+
+    /// Returns the call of the predefined function, known values are calculated, e.g. `Ln(1)` is `0`
+    pub fn function(function: HigherOrderFunc, params: Vec<Symbolic>) -> Self {
+        match (&function, params.as_slice()) {
+            (HigherOrderFunc::NaturalLogarithm(_), [Symbolic::Constant(Constant::E)]) => {
+                Symbolic::integer(1)
+            }
+            (HigherOrderFunc::NaturalLogarithm(_), [param]) if param.is_one() => {
+                Symbolic::integer(0)
+            }
+            // Numbers are calculated, e.g. `2.5` in `Ln(2.5)`
+            (HigherOrderFunc::NaturalLogarithm(_), [Symbolic::Float(num)]) if *num > 0.0 => {
+                Symbolic::Float(num.ln())
+            }
+            (
+                HigherOrderFunc::Exponential(_)
+                | HigherOrderFunc::Cosine(_)
+                | HigherOrderFunc::HyperbolicCosine(_),
+                [param],
+            ) if param.is_zero() => Symbolic::integer(1),
+            (
+                HigherOrderFunc::Sine(_)
+                | HigherOrderFunc::Tangent(_)
+                | HigherOrderFunc::Arcsine(_)
+                | HigherOrderFunc::Arctangent(_)
+                | HigherOrderFunc::HyperbolicSine(_)
+                | HigherOrderFunc::HyperbolicTangent(_)
+                | HigherOrderFunc::AreaHyperbolicSine(_)
+                | HigherOrderFunc::AreaHyperbolicTangent(_)
+                | HigherOrderFunc::ExponentialMinusOne(_)
+                | HigherOrderFunc::NaturalLogarithmOnePlus(_)
+                | HigherOrderFunc::CubeRoot(_)
+                | HigherOrderFunc::Absolute(_),
+                [param],
+            ) if param.is_zero() => Symbolic::integer(0),
+            _ => Symbolic::Function(function, params),
+        }
+    }
+
+    pub fn negation(expression: Symbolic) -> Self {
+        Symbolic::product(vec![Symbolic::integer(-1), expression])
+    }
+
+    /// Splits a sum into its terms, a numeric coefficient is distributed over a sum, e.g. `2(x + y)` into `2x` and `2y`
+    fn into_terms(self) -> Vec<Symbolic> {
+        match self {
+            Symbolic::Sum(terms) => terms,
+            Symbolic::Product(factors) => match factors.as_slice() {
+                [coefficient, Symbolic::Sum(terms)] if coefficient.is_number() => terms
+                    .iter()
+                    .map(|term| Symbolic::product(vec![coefficient.clone(), term.clone()]))
+                    .collect(),
+                _ => vec![Symbolic::Product(factors)],
+            },
+            term => vec![term],
+        }
+    }
+
+    fn into_factors(self) -> Vec<Symbolic> {
+        match self {
+            Symbolic::Product(factors) => factors,
+            factor => vec![factor],
+        }
+    }
+
+    /// Splits a term into its numeric coefficient and the rest, e.g. `3x^2` into `3` and `x^2`
+    fn split_coefficient(self) -> (Symbolic, Symbolic) {
+        match self {
+            Symbolic::Product(mut factors) if factors[0].is_number() => {
+                let coefficient = factors.remove(0);
+                (coefficient, collect(factors, Symbolic::Product))
+            }
+            term => (Symbolic::integer(1), term),
+        }
+    }
+
+    /// Splits a factor into its base and exponent, e.g. `x^2` into `x` and `2`
+    fn split_exponent(self) -> (Symbolic, Symbolic) {
+        match self {
+            Symbolic::Power(base, exponent) => (*base, *exponent),
+            factor => (factor, Symbolic::integer(1)),
+        }
+    }
+
+    fn collect_variables<'a>(&'a self, variables: &mut BTreeSet<&'a str>) {
+        match self {
+            Symbolic::Variable(name) => {
+                variables.insert(name);
+            }
+            Symbolic::Sum(params) | Symbolic::Product(params) | Symbolic::Function(_, params) => {
+                params
+                    .iter()
+                    .for_each(|param| param.collect_variables(variables));
+            }
+            Symbolic::Power(base, exponent) => {
+                base.collect_variables(variables);
+                exponent.collect_variables(variables);
+            }
+            Symbolic::Number(_) | Symbolic::Float(_) | Symbolic::Constant(_) => (),
+        }
+    }
+
+    /// Returns the degree of a term in the variable or in all variables for sorting, e.g. 2 for `3x^2` or `x*y`
+    fn get_degree(&self, variable: Option<&str>) -> f64 {
+        match self {
+            Symbolic::Variable(name) if variable.is_none_or(|variable| variable == name) => 1.0,
+            Symbolic::Variable(_) => 0.0,
+            Symbolic::Power(base, exponent) if exponent.is_number() => {
+                base.get_degree(variable) * number_to_f64(exponent)
+            }
+            Symbolic::Power(base, _) => base.get_degree(variable),
+            Symbolic::Product(factors) => factors
+                .iter()
+                .map(|factor| factor.get_degree(variable))
+                .sum(),
+            Symbolic::Sum(terms) => terms
+                .iter()
+                .map(|term| term.get_degree(variable))
+                .fold(0.0, f64::max),
+            Symbolic::Number(_)
+            | Symbolic::Float(_)
+            | Symbolic::Constant(_)
+            | Symbolic::Function(_, _) => 0.0,
+        }
+    }
+
+    /// Returns the rank of a factor for sorting, constants come before variables and variables before function calls
+    fn get_rank(&self) -> u8 {
+        match self {
+            Symbolic::Number(_) | Symbolic::Float(_) => 0,
+            Symbolic::Constant(_) => 1,
+            Symbolic::Variable(_) => 2,
+            Symbolic::Power(base, _) => base.get_rank(),
+            Symbolic::Function(_, _) => 3,
+            Symbolic::Sum(_) => 4,
+            Symbolic::Product(_) => 5,
+        }
+    }
+
+    /// Returns the precedence of the outermost function of the expression in the input syntax
+    fn get_precedence(&self) -> u8 {
+        match self {
+            Symbolic::Sum(_) => ElementaryFunc::Addition.get_precedence(),
+            // Negative powers are written as divisions, e.g. `1/x`
+            Symbolic::Product(_) | Symbolic::Power(_, _) if self.is_quotient() => {
+                ElementaryFunc::Division.get_precedence()
+            }
+            Symbolic::Product(_) => ElementaryFunc::Multiplication.get_precedence(),
+            Symbolic::Power(_, exponent) if is_one_half(exponent) => OPERAND_PRECEDENCE,
+            Symbolic::Power(_, _) => ElementaryFunc::Exponentiation.get_precedence(),
+            Symbolic::Number(num) if !num.is_integer() => ElementaryFunc::Division.get_precedence(),
+            Symbolic::Number(_) | Symbolic::Float(_) if self.is_negative() => {
+                UnaryFunc::Negation.get_precedence()
+            }
+            _ => OPERAND_PRECEDENCE,
+        }
+    }
+
+    fn is_quotient(&self) -> bool {
+        match self {
+            Symbolic::Power(_, exponent) => exponent.is_negative(),
+            Symbolic::Product(factors) => factors.iter().any(|factor| match factor {
+                Symbolic::Number(num) => !num.is_integer(),
+                factor => factor.is_quotient(),
+            }),
+            _ => false,
+        }
+    }
+
+    /// Formats the expression and brackets it if it binds weaker than `min_precedence`
+    fn format(&self, min_precedence: u8) -> String {
+        if self.get_precedence() < min_precedence {
+            format!("({})", self)
+        } else {
+            self.to_string()
+        }
+    }
+}
+
+/// Returns the single item or combines the items, e.g. into a sum
+fn collect(mut items: Vec<Symbolic>, combine: fn(Vec<Symbolic>) -> Symbolic) -> Symbolic {
+    if items.len() == 1 {
+        items.pop().expect("There is exactly one item.")
+    } else {
+        combine(items)
+    }
+}
+
+fn number_to_f64(num: &Symbolic) -> f64 {
+    match num {
+        Symbolic::Number(num) => num.to_f64().unwrap_or(f64::NAN),
+        Symbolic::Float(num) => *num,
+        _ => unreachable!("Only numbers are converted to floating point numbers."),
+    }
+}
+
+/// Adds two numbers, the sum is only exact if both numbers are
+fn add_numbers(a: &Symbolic, b: &Symbolic) -> Symbolic {
+    match (a, b) {
+        (Symbolic::Number(a), Symbolic::Number(b)) => Symbolic::Number(a + b),
+        _ => Symbolic::Float(number_to_f64(a) + number_to_f64(b)),
+    }
+}
+
+/// Multiplies two numbers, the product is only exact if both numbers are
+fn multiply_numbers(a: &Symbolic, b: &Symbolic) -> Symbolic {
+    match (a, b) {
+        (Symbolic::Number(a), Symbolic::Number(b)) => Symbolic::Number(a * b),
+        _ => Symbolic::Float(number_to_f64(a) * number_to_f64(b)),
+    }
+}
+
+fn is_one_half(num: &Symbolic) -> bool {
+    matches!(num, Symbolic::Number(num) if *num == BigRational::new(BigInt::one(), BigInt::from(2)))
+}
+
+/// Sorts terms by descending degree, e.g. `x^2 + 2x + 1`
+fn compare_terms(a: &Symbolic, b: &Symbolic) -> Ordering {
+    let mut variables = BTreeSet::new();
+    a.collect_variables(&mut variables);
+    b.collect_variables(&mut variables);
+
+    // Terms of the same degree are sorted by the powers of their variables in alphabetical order, e.g. `x^2 + x*y + y^2`
+    std::iter::once(None)
+        .chain(variables.into_iter().map(Some))
+        .map(|variable| b.get_degree(variable).total_cmp(&a.get_degree(variable)))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or_else(|| {
+            let (_, a) = a.clone().split_coefficient();
+            let (_, b) = b.clone().split_coefficient();
+            a.to_string().cmp(&b.to_string())
+        })
+}
+
+/// Sorts factors by their rank and name, e.g. `Pi*x*y*Sin(x)`
+fn compare_factors(a: &Symbolic, b: &Symbolic) -> Ordering {
+    a.get_rank()
+        .cmp(&b.get_rank())
+        .then_with(|| a.to_string().cmp(&b.to_string()))
+}
+
+/// Returns true if a number can be written directly in front of the factor without a multiplication sign, e.g. `2x` or `3Sin(x)`.
+/// Units are excluded, because a number followed by a unit is a quantity.
+fn is_juxtaposable(factor: &Symbolic) -> bool {
+    match factor {
+        Symbolic::Variable(name) => !UNIT_KEYWORDS.contains_key(name.as_str()),
+        Symbolic::Power(base, exponent) => is_one_half(exponent) || is_juxtaposable(base),
+        Symbolic::Constant(_) | Symbolic::Function(_, _) => true,
+        _ => false,
+    }
+}
+
+fn format_sum(terms: &[Symbolic]) -> String {
+    let min_precedence = ElementaryFunc::Multiplication.get_precedence();
+    let mut s = terms[0].to_string();
+
+    for term in &terms[1..] {
+        if term.is_negative() {
+            s.push_str(" - ");
+            s.push_str(&Symbolic::negation(term.clone()).format(min_precedence));
+        } else {
+            s.push_str(" + ");
+            s.push_str(&term.format(min_precedence));
+        }
+    }
+
+    s
+}
+
+/// Formats a product as a fraction with the factors with negative exponents in the denominator, e.g. `3x/(2y)`
+fn format_product(factors: &[Symbolic]) -> String {
+    let mut sign = "";
+    let mut numerator = Vec::new();
+    let mut denominator = Vec::new();
+
+    for factor in factors {
+        match factor {
+            Symbolic::Number(num) => {
+                if num.is_negative() {
+                    sign = "-";
+                }
+                numerator.push(Symbolic::Number(BigRational::from(num.numer().abs())));
+                denominator.push(Symbolic::Number(BigRational::from(num.denom().clone())));
+            }
+            Symbolic::Float(num) => {
+                if *num < 0.0 {
+                    sign = "-";
+                }
+                numerator.push(Symbolic::Float(num.abs()));
+            }
+            Symbolic::Power(base, exponent) if exponent.is_negative() => {
+                denominator.push(Symbolic::power(
+                    *base.clone(),
+                    Symbolic::negation(*exponent.clone()),
+                ));
+            }
+            factor => numerator.push(factor.clone()),
+        }
+    }
+
+    numerator.retain(|factor| !factor.is_one());
+    denominator.retain(|factor| !factor.is_one());
+
+    let min_precedence = UnaryFunc::Negation.get_precedence();
+    let mut s = sign.to_string();
+
+    if numerator.is_empty() {
+        s.push('1');
+    }
+
+    for (idx, factor) in numerator.iter().enumerate() {
+        if idx > 0 && !(numerator[idx - 1].is_number() && is_juxtaposable(factor)) {
+            s.push('*');
+        }
+        s.push_str(&factor.format(min_precedence));
+    }
+
+    if !denominator.is_empty() {
+        s.push('/');
+        s.push_str(&collect(denominator, Symbolic::Product).format(min_precedence));
+    }
+
+    s
+}
+
+impl Display for Symbolic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Symbolic::Number(num) => num.to_string(),
+            Symbolic::Float(num) => num.to_string(),
+            Symbolic::Constant(constant) => constant.get_keyword().to_string(),
+            Symbolic::Variable(name) => name.clone(),
+            Symbolic::Sum(terms) => format_sum(terms),
+            Symbolic::Product(factors) => format_product(factors),
+            Symbolic::Power(_, exponent) if exponent.is_negative() => {
+                format_product(std::slice::from_ref(self))
+            }
+            Symbolic::Power(base, exponent) if is_one_half(exponent) => format!("Sqrt({})", base),
+            Symbolic::Power(base, exponent) => format!(
+                "{}^{}",
+                base.format(OPERAND_PRECEDENCE),
+                exponent.format(ElementaryFunc::Exponentiation.get_precedence())
+            ),
+            Symbolic::Function(function, params) => format!(
+                "{}({})",
+                function.get_keyword(),
+                params
+                    .iter()
+                    .map(|param| param.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+
+        write!(f, "{s}")
+    }
+}
+
+/// Returns the expression as a number if it does not contain variables or constants
+pub fn symbolic_value(expression: Symbolic) -> FunctionReturnType {
+    match expression {
+        Symbolic::Number(num) => rational_value(num),
+        Symbolic::Float(num) => FunctionReturnType::F64(num),
+        expression => FunctionReturnType::Symbolic(expression),
+    }
+}
+
+/// Returns the expression with products and integer powers of sums multiplied out, e.g. `(x + 1)^2` is `x^2 + 2x + 1`.
+/// Sums in denominators are expanded, but not distributed over.
+pub fn expand(expression: &Symbolic) -> Symbolic {
+    match expression {
+        Symbolic::Sum(terms) => Symbolic::sum(terms.iter().map(expand).collect()),
+        Symbolic::Product(factors) => factors
+            .iter()
+            .map(expand)
+            .fold(Symbolic::integer(1), multiply_out),
+        Symbolic::Power(base, exponent) => {
+            let base = expand(base);
+
+            match exponent.as_ref() {
+                Symbolic::Number(num) if num.is_integer() && matches!(base, Symbolic::Sum(_)) => {
+                    match num.to_integer().abs().to_u32() {
+                        Some(count) if count <= MAX_EXPANDED_EXPONENT => {
+                            let expanded = (0..count).fold(Symbolic::integer(1), |product, _| {
+                                multiply_out(product, base.clone())
+                            });
+
+                            if num.is_negative() {
+                                Symbolic::power(expanded, Symbolic::integer(-1))
+                            } else {
+                                expanded
+                            }
+                        }
+                        _ => Symbolic::power(base, *exponent.clone()),
+                    }
+                }
+                exponent => Symbolic::power(base, expand(exponent)),
+            }
+        }
+        Symbolic::Function(function, params) => {
+            Symbolic::function(function.clone(), params.iter().map(expand).collect())
+        }
+        Symbolic::Number(_)
+        | Symbolic::Float(_)
+        | Symbolic::Constant(_)
+        | Symbolic::Variable(_) => expression.clone(),
+    }
+}
+
+/// Multiplies every term of the first expression with every term of the second one, e.g. `(x + 1) * (x - 1)` is `x^2 - 1`
+fn multiply_out(a: Symbolic, b: Symbolic) -> Symbolic {
+    let b_terms = b.into_terms();
+
+    Symbolic::sum(
+        a.into_terms()
+            .into_iter()
+            .flat_map(|a| {
+                b_terms
+                    .iter()
+                    .map(move |b| Symbolic::product(vec![a.clone(), b.clone()]))
+            })
+            .collect(),
+    )
+}
+
+/// Returns the expression with sums written as products where possible, e.g. `2x^2 + 2x` is `2x*(x + 1)`.
+/// Common factors of all terms are factored out and polynomials in one variable are split at their rational roots.
+pub fn factor(expression: &Symbolic) -> Symbolic {
+    match expression {
+        Symbolic::Sum(_) => match expand(expression) {
+            Symbolic::Sum(terms) => factor_sum(terms),
+            expanded => factor(&expanded),
+        },
+        Symbolic::Product(factors) => Symbolic::product(factors.iter().map(factor).collect()),
+        Symbolic::Power(base, exponent) => Symbolic::power(factor(base), *exponent.clone()),
+        Symbolic::Function(function, params) => {
+            Symbolic::function(function.clone(), params.iter().map(factor).collect())
+        }
+        Symbolic::Number(_)
+        | Symbolic::Float(_)
+        | Symbolic::Constant(_)
+        | Symbolic::Variable(_) => expression.clone(),
+    }
+}
+
+fn factor_sum(terms: Vec<Symbolic>) -> Symbolic {
+    let common_factor = get_common_factor(&terms);
+    let remaining_terms = terms
+        .into_iter()
+        .map(|term| {
+            Symbolic::product(vec![
+                term,
+                Symbolic::power(common_factor.clone(), Symbolic::integer(-1)),
+            ])
+        })
+        .collect();
+    let remaining = Symbolic::sum(remaining_terms);
+
+    let remaining = match remaining.get_first_variable() {
+        Some(variable) => match get_polynomial(&remaining, variable) {
+            Some(coefficients) => factor_polynomial(coefficients, variable),
+            None => remaining,
+        },
+        None => remaining,
+    };
+
+    Symbolic::product(vec![common_factor, remaining])
+}
+
+/// Returns the largest factor that all terms share, i.e. the greatest common divisor of their coefficients and
+/// the smallest powers of their common bases. The sign is chosen so that the first term stays positive.
+fn get_common_factor(terms: &[Symbolic]) -> Symbolic {
+    let split_terms = terms
+        .iter()
+        .map(|term| term.clone().split_coefficient())
+        .collect::<Vec<_>>();
+
+    // The coefficient of a constant term is the constant itself, e.g. 4 in `2x + 4`
+    let coefficient = split_terms
+        .iter()
+        .map(|(coefficient, rest)| match (coefficient, rest) {
+            (Symbolic::Number(_), Symbolic::Number(num)) => Some(num.clone()),
+            (Symbolic::Number(num), _) => Some(num.clone()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()
+        .map_or(Symbolic::integer(1), |coefficients| {
+            let numerator = coefficients
+                .iter()
+                .fold(BigInt::zero(), |gcd, num| gcd.gcd(num.numer()));
+            let denominator = coefficients
+                .iter()
+                .fold(BigInt::one(), |lcm, num| lcm.lcm(num.denom()));
+            let sign = if coefficients[0].is_negative() { -1 } else { 1 };
+
+            Symbolic::Number(BigRational::new(numerator * sign, denominator))
+        });
+
+    let factors_of = |rest: &Symbolic| -> Vec<(Symbolic, Symbolic)> {
+        rest.clone()
+            .into_factors()
+            .into_iter()
+            .filter(|factor| !factor.is_number())
+            .map(Symbolic::split_exponent)
+            .filter(|(_, exponent)| matches!(exponent, Symbolic::Number(_)))
+            .collect()
+    };
+
+    let mut common_powers = factors_of(&split_terms[0].1);
+    for (_, rest) in &split_terms[1..] {
+        let powers = factors_of(rest);
+        common_powers = common_powers
+            .into_iter()
+            .filter_map(|(base, exponent)| {
+                powers
+                    .iter()
+                    .find(|(other, _)| *other == base)
+                    .map(|(_, other)| match (&exponent, other) {
+                        (Symbolic::Number(a), Symbolic::Number(b)) => {
+                            (base.clone(), Symbolic::Number(a.clone().min(b.clone())))
+                        }
+                        _ => unreachable!("Only powers with numbers as exponents are compared."),
+                    })
+            })
+            .collect();
+    }
+
+    Symbolic::product(
+        std::iter::once(coefficient)
+            .chain(
+                common_powers
+                    .into_iter()
+                    .filter(|(_, exponent)| !exponent.is_negative())
+                    .map(|(base, exponent)| Symbolic::power(base, exponent)),
+            )
+            .collect(),
+    )
+}
+
+/// Returns the exact coefficients of the polynomial in the variable, starting with the constant term,
+/// or nothing if the expression is not a polynomial in the variable
+fn get_polynomial(expression: &Symbolic, variable: &str) -> Option<Vec<BigRational>> {
+    let mut coefficients: Vec<BigRational> = Vec::new();
+
+    for term in expression.clone().into_terms() {
+        let (coefficient, degree) = match term.split_coefficient() {
+            (_, Symbolic::Number(num)) => (num, 0),
+            (Symbolic::Number(coefficient), rest) => {
+                let degree = match rest {
+                    Symbolic::Variable(name) if name == variable => 1,
+                    Symbolic::Power(base, exponent)
+                        if *base == Symbolic::Variable(variable.to_string()) =>
+                    {
+                        match *exponent {
+                            Symbolic::Number(num) if num.is_integer() => num
+                                .to_integer()
+                                .to_usize()
+                                .filter(|degree| *degree <= MAX_FACTORED_DEGREE)?,
+                            _ => return None,
+                        }
+                    }
+                    _ => return None,
+                };
+                (coefficient, degree)
+            }
+            _ => return None,
+        };
+
+        if coefficients.len() <= degree {
+            coefficients.resize(degree + 1, BigRational::zero());
+        }
+        coefficients[degree] += coefficient;
+    }
+
+    Some(coefficients)
+}
+
+/// Splits a polynomial into linear factors at its rational roots, e.g. `x^2 - 1` into `(x - 1)*(x + 1)`.
+/// The part without rational roots is kept as a polynomial.
+fn factor_polynomial(mut coefficients: Vec<BigRational>, variable: &str) -> Symbolic {
+    let mut factors = Vec::new();
+
+    while coefficients.len() > 2 {
+        let Some(root) = find_rational_root(&coefficients) else {
+            break;
+        };
+
+        // Dividing by x - p/q and multiplying the linear factor with q keeps integer coefficients, e.g. 2x - 1
+        let mut quotient = vec![BigRational::zero(); coefficients.len() - 1];
+        let mut remainder = BigRational::zero();
+        for idx in (0..coefficients.len()).rev() {
+            remainder = &coefficients[idx] + &remainder * &root;
+            if idx > 0 {
+                quotient[idx - 1] = remainder.clone();
+            }
+        }
+
+        let denominator = BigRational::from(root.denom().clone());
+        coefficients = quotient
+            .into_iter()
+            .map(|coefficient| coefficient / &denominator)
+            .collect();
+
+        factors.push(Symbolic::sum(vec![
+            Symbolic::product(vec![
+                Symbolic::Number(denominator),
+                Symbolic::Variable(variable.to_string()),
+            ]),
+            Symbolic::Number(-BigRational::from(root.numer().clone())),
+        ]));
+    }
+
+    factors.push(Symbolic::sum(
+        coefficients
+            .into_iter()
+            .enumerate()
+            .map(|(degree, coefficient)| {
+                Symbolic::product(vec![
+                    Symbolic::Number(coefficient),
+                    Symbolic::power(
+                        Symbolic::Variable(variable.to_string()),
+                        Symbolic::integer(degree as i64),
+                    ),
+                ])
+            })
+            .collect(),
+    ));
+
+    Symbolic::product(factors)
+}
+
+/// Returns a rational root of the polynomial if it has one. By the rational root theorem the numerator of
+/// every root divides the constant term and the denominator divides the leading coefficient.
+fn find_rational_root(coefficients: &[BigRational]) -> Option<BigRational> {
+    if coefficients[0].is_zero() {
+        return Some(BigRational::zero());
+    }
+
+    let scale = coefficients.iter().fold(BigInt::one(), |lcm, coefficient| {
+        lcm.lcm(coefficient.denom())
+    });
+    let integers = coefficients
+        .iter()
+        .map(|coefficient| (coefficient * &scale).to_integer())
+        .collect::<Vec<_>>();
+
+    let numerators = get_divisors(integers.first()?)?;
+    let denominators = get_divisors(integers.last()?)?;
+
+    for denominator in &denominators {
+        for numerator in &numerators {
+            for root in [
+                BigRational::new(numerator.clone(), denominator.clone()),
+                BigRational::new(-numerator.clone(), denominator.clone()),
+            ] {
+                let value = coefficients
+                    .iter()
+                    .rev()
+                    .fold(BigRational::zero(), |value, coefficient| {
+                        value * &root + coefficient
+                    });
+
+                if value.is_zero() {
+                    return Some(root);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns the positive divisors of the integer in ascending order, or nothing if it is too large to search them
+fn get_divisors(num: &BigInt) -> Option<Vec<BigInt>> {
+    let num = num
+        .abs()
+        .to_u64()
+        .filter(|num| *num <= MAX_DIVISOR_SEARCH)?;
+
+    Some(
+        (1..=num)
+            .take_while(|divisor| divisor * divisor <= num)
+            .filter(|divisor| num % divisor == 0)
+            .flat_map(|divisor| [divisor, num / divisor])
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(BigInt::from)
+            .collect(),
+    )
+}
+
+/// Returns the shortest of the expression, its expanded and its factored form, e.g. `x + 1` for `(x^2 - 1)/(x - 1)`
+pub fn simplify(expression: &Symbolic) -> Symbolic {
+    [expression.clone(), expand(expression), factor(expression)]
+        .into_iter()
+        .min_by_key(|expression| expression.to_string().len())
+        .expect("There are three forms to choose from.")
+}
+
+/// Returns the derivative of the expression with respect to the variable, e.g. `2x` for `x^2`.
+/// Trigonometric functions are differentiated for angles in the provided unit.
+pub fn derivative(
+    expression: &Symbolic,
+    variable: &str,
+    angle_mode: &AngleMode,
+) -> Result<Symbolic, FunctionError> {
+    if !expression.contains_variable(variable) {
+        return Ok(Symbolic::integer(0));
+    }
+
+    let derivative = match expression {
+        Symbolic::Sum(terms) => Symbolic::sum(
+            terms
+                .iter()
+                .map(|term| derivative(term, variable, angle_mode))
+                .collect::<Result<_, _>>()?,
+        ),
+        // Product rule: every factor is differentiated once while the others stay the same
+        Symbolic::Product(factors) => {
+            let mut terms = Vec::new();
+
+            for idx in 0..factors.len() {
+                let mut product = factors.clone();
+                product[idx] = derivative(&factors[idx], variable, angle_mode)?;
+                terms.push(Symbolic::product(product));
+            }
+
+            Symbolic::sum(terms)
+        }
+        Symbolic::Power(base, exponent) if !exponent.contains_variable(variable) => {
+            Symbolic::product(vec![
+                *exponent.clone(),
+                Symbolic::power(
+                    *base.clone(),
+                    Symbolic::sum(vec![*exponent.clone(), Symbolic::integer(-1)]),
+                ),
+                derivative(base, variable, angle_mode)?,
+            ])
+        }
+        Symbolic::Power(base, exponent) if **base == Symbolic::Constant(Constant::E) => {
+            Symbolic::product(vec![
+                expression.clone(),
+                derivative(exponent, variable, angle_mode)?,
+            ])
+        }
+        // The derivative of b^e is b^e * (e' * Ln(b) + e * b' / b)
+        Symbolic::Power(base, exponent) => Symbolic::product(vec![
+            expression.clone(),
+            Symbolic::sum(vec![
+                Symbolic::product(vec![
+                    derivative(exponent, variable, angle_mode)?,
+                    Symbolic::function(HigherOrderFunc::NaturalLogarithm(1), vec![*base.clone()]),
+                ]),
+                Symbolic::product(vec![
+                    *exponent.clone(),
+                    derivative(base, variable, angle_mode)?,
+                    Symbolic::power(*base.clone(), Symbolic::integer(-1)),
+                ]),
+            ]),
+        ]),
+        Symbolic::Function(function, params) => {
+            function_derivative(function, params, variable, angle_mode)?
+        }
+        Symbolic::Variable(_) => Symbolic::integer(1),
+        Symbolic::Number(_) | Symbolic::Float(_) | Symbolic::Constant(_) => {
+            unreachable!("Expressions without variables have been handled before.")
+        }
+    };
+
+    Ok(derivative)
+}
+
+/// Applies the chain rule to the call of a predefined function
+fn function_derivative(
+    function: &HigherOrderFunc,
+    params: &[Symbolic],
+    variable: &str,
+    angle_mode: &AngleMode,
+) -> Result<Symbolic, FunctionError> {
+    let param = params[0].clone();
+    let inner_derivative = derivative(&param, variable, angle_mode)?;
+
+    // Derivatives of trigonometric functions are scaled for angles that are not in radians
+    let radians_per_unit = match angle_mode {
+        AngleMode::Radians => Symbolic::integer(1),
+        angle_mode => Symbolic::product(vec![
+            Symbolic::fraction(2, angle_mode.get_full_turn() as i64),
+            Symbolic::Constant(Constant::Pi),
+        ]),
+    };
+    let units_per_radian = Symbolic::power(radians_per_unit.clone(), Symbolic::integer(-1));
+
+    let square = || Symbolic::power(param.clone(), Symbolic::integer(2));
+    let one_minus_square =
+        || Symbolic::sum(vec![Symbolic::integer(1), Symbolic::negation(square())]);
+    let call = |function: HigherOrderFunc| Symbolic::function(function, vec![param.clone()]);
+
+    let outer_derivative = match function {
+        HigherOrderFunc::Sine(_) => {
+            Symbolic::product(vec![radians_per_unit, call(HigherOrderFunc::Cosine(1))])
+        }
+        HigherOrderFunc::Cosine(_) => Symbolic::product(vec![
+            Symbolic::integer(-1),
+            radians_per_unit,
+            call(HigherOrderFunc::Sine(1)),
+        ]),
+        HigherOrderFunc::Tangent(_) => Symbolic::product(vec![
+            radians_per_unit,
+            Symbolic::power(call(HigherOrderFunc::Cosine(1)), Symbolic::integer(-2)),
+        ]),
+        HigherOrderFunc::Arcsine(_) => Symbolic::product(vec![
+            units_per_radian,
+            Symbolic::power(one_minus_square(), Symbolic::fraction(-1, 2)),
+        ]),
+        HigherOrderFunc::Arccosine(_) => Symbolic::product(vec![
+            Symbolic::integer(-1),
+            units_per_radian,
+            Symbolic::power(one_minus_square(), Symbolic::fraction(-1, 2)),
+        ]),
+        HigherOrderFunc::Arctangent(_) => Symbolic::product(vec![
+            units_per_radian,
+            Symbolic::power(
+                Symbolic::sum(vec![Symbolic::integer(1), square()]),
+                Symbolic::integer(-1),
+            ),
+        ]),
+        // The derivative of Atan2(y, x) is (x * y' - y * x') / (x^2 + y^2)
+        HigherOrderFunc::Arctangent2(_) => {
+            let (y, x) = (param, params[1].clone());
+            let x_derivative = derivative(&x, variable, angle_mode)?;
+
+            return Ok(Symbolic::product(vec![
+                units_per_radian,
+                Symbolic::sum(vec![
+                    Symbolic::product(vec![x.clone(), inner_derivative]),
+                    Symbolic::negation(Symbolic::product(vec![y.clone(), x_derivative])),
+                ]),
+                Symbolic::power(
+                    Symbolic::sum(vec![
+                        Symbolic::power(x, Symbolic::integer(2)),
+                        Symbolic::power(y, Symbolic::integer(2)),
+                    ]),
+                    Symbolic::integer(-1),
+                ),
+            ]));
+        }
+        HigherOrderFunc::HyperbolicSine(_) => call(HigherOrderFunc::HyperbolicCosine(1)),
+        HigherOrderFunc::HyperbolicCosine(_) => call(HigherOrderFunc::HyperbolicSine(1)),
+        HigherOrderFunc::HyperbolicTangent(_) => Symbolic::power(
+            call(HigherOrderFunc::HyperbolicCosine(1)),
+            Symbolic::integer(-2),
+        ),
+        HigherOrderFunc::AreaHyperbolicSine(_) => Symbolic::power(
+            Symbolic::sum(vec![square(), Symbolic::integer(1)]),
+            Symbolic::fraction(-1, 2),
+        ),
+        HigherOrderFunc::AreaHyperbolicCosine(_) => Symbolic::power(
+            Symbolic::sum(vec![square(), Symbolic::integer(-1)]),
+            Symbolic::fraction(-1, 2),
+        ),
+        HigherOrderFunc::AreaHyperbolicTangent(_) => {
+            Symbolic::power(one_minus_square(), Symbolic::integer(-1))
+        }
+        HigherOrderFunc::NaturalLogarithm(_) => Symbolic::power(param, Symbolic::integer(-1)),
+        HigherOrderFunc::DecimalLogarithm(_) | HigherOrderFunc::BinaryLogarithm(_) => {
+            let base = match function {
+                HigherOrderFunc::DecimalLogarithm(_) => 10,
+                _ => 2,
+            };
+
+            Symbolic::power(
+                Symbolic::product(vec![
+                    param,
+                    Symbolic::function(
+                        HigherOrderFunc::NaturalLogarithm(1),
+                        vec![Symbolic::integer(base)],
+                    ),
+                ]),
+                Symbolic::integer(-1),
+            )
+        }
+        // Logarithms to other bases are quotients of natural logarithms, the base can depend on the variable as well
+        HigherOrderFunc::Logarithm(_) => {
+            let quotient = Symbolic::product(vec![
+                call(HigherOrderFunc::NaturalLogarithm(1)),
+                Symbolic::power(
+                    Symbolic::function(
+                        HigherOrderFunc::NaturalLogarithm(1),
+                        vec![params[1].clone()],
+                    ),
+                    Symbolic::integer(-1),
+                ),
+            ]);
+
+            return derivative(&quotient, variable, angle_mode);
+        }
+        HigherOrderFunc::Exponential(_) | HigherOrderFunc::ExponentialMinusOne(_) => {
+            call(HigherOrderFunc::Exponential(1))
+        }
+        HigherOrderFunc::NaturalLogarithmOnePlus(_) => Symbolic::power(
+            Symbolic::sum(vec![Symbolic::integer(1), param]),
+            Symbolic::integer(-1),
+        ),
+        HigherOrderFunc::CubeRoot(_) => Symbolic::product(vec![
+            Symbolic::fraction(1, 3),
+            Symbolic::power(call(HigherOrderFunc::CubeRoot(1)), Symbolic::integer(-2)),
+        ]),
+        HigherOrderFunc::Absolute(_) => Symbolic::product(vec![
+            param.clone(),
+            Symbolic::power(call(HigherOrderFunc::Absolute(1)), Symbolic::integer(-1)),
+        ]),
+        function => {
+            return Err(FunctionError::new(
+                format!(
+                    "The function '{}' cannot be differentiated.",
+                    function.get_keyword()
+                ),
+                FunctionErrorType::InvalidParameterType,
+            ))
+        }
+    };
+
+    Ok(Symbolic::product(vec![outer_derivative, inner_derivative]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn x() -> Symbolic {
+        Symbolic::Variable("x".to_string())
+    }
+
+    #[test]
+    fn test_simplification() {
+        let two_x = Symbolic::product(vec![Symbolic::integer(2), x()]);
+        let three_x = Symbolic::product(vec![Symbolic::integer(3), x()]);
+        assert_eq!(
+            Symbolic::sum(vec![two_x.clone(), three_x.clone()]).to_string(),
+            "5x"
+        );
+        assert_eq!(
+            Symbolic::product(vec![two_x.clone(), three_x]).to_string(),
+            "6x^2"
+        );
+        assert_eq!(
+            Symbolic::sum(vec![two_x.clone(), Symbolic::negation(two_x)]),
+            Symbolic::integer(0)
+        );
+        assert_eq!(
+            Symbolic::power(
+                Symbolic::power(x(), Symbolic::integer(2)),
+                Symbolic::integer(3)
+            )
+            .to_string(),
+            "x^6"
+        );
+        assert_eq!(
+            Symbolic::product(vec![x(), Symbolic::power(x(), Symbolic::integer(-1))]),
+            Symbolic::integer(1)
+        );
+    }
+
+    #[test]
+    fn test_formatting() {
+        let sum = Symbolic::sum(vec![
+            Symbolic::integer(1),
+            Symbolic::negation(x()),
+            Symbolic::power(x(), Symbolic::integer(2)),
+        ]);
+        assert_eq!(sum.to_string(), "x^2 - x + 1");
+        assert_eq!(
+            Symbolic::product(vec![Symbolic::fraction(3, 2), x()]).to_string(),
+            "3x/2"
+        );
+        assert_eq!(
+            Symbolic::power(sum.clone(), Symbolic::integer(-1)).to_string(),
+            "1/(x^2 - x + 1)"
+        );
+        assert_eq!(
+            Symbolic::power(x(), Symbolic::fraction(1, 2)).to_string(),
+            "Sqrt(x)"
+        );
+        assert_eq!(
+            Symbolic::product(vec![sum, Symbolic::Variable("s".to_string())]).to_string(),
+            "s*(x^2 - x + 1)"
+        );
+    }
+
+    #[test]
+    fn test_expand_and_factor() {
+        let y = Symbolic::Variable("y".to_string());
+        let sum = Symbolic::sum(vec![x(), y.clone()]);
+        assert_eq!(
+            expand(&Symbolic::power(sum.clone(), Symbolic::integer(2))).to_string(),
+            "x^2 + 2x*y + y^2"
+        );
+
+        let cubic = Symbolic::sum(vec![
+            Symbolic::power(x(), Symbolic::integer(3)),
+            Symbolic::product(vec![
+                Symbolic::integer(-6),
+                Symbolic::power(x(), Symbolic::integer(2)),
+            ]),
+            Symbolic::product(vec![Symbolic::integer(11), x()]),
+            Symbolic::integer(-6),
+        ]);
+        assert_eq!(factor(&cubic).to_string(), "(x - 1)*(x - 2)*(x - 3)");
+        assert_eq!(
+            factor(&Symbolic::sum(vec![Symbolic::product(vec![x(), y]), x()])).to_string(),
+            "x*(y + 1)"
+        );
+        assert_eq!(factor(&sum), sum);
+        assert_eq!(
+            factor(&Symbolic::sum(vec![
+                Symbolic::product(vec![Symbolic::integer(6), x()]),
+                Symbolic::integer(9),
+            ]))
+            .to_string(),
+            "3*(2x + 3)"
+        );
+    }
+
+    #[test]
+    fn test_derivative() {
+        let derive = |expression: Symbolic| {
+            derivative(&expression, "x", &AngleMode::Radians)
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(derive(Symbolic::power(x(), Symbolic::integer(3))), "3x^2");
+        assert_eq!(
+            derive(Symbolic::product(vec![
+                x(),
+                Symbolic::function(HigherOrderFunc::Sine(1), vec![x()])
+            ])),
+            "x*Cos(x) + Sin(x)"
+        );
+        assert_eq!(
+            derive(Symbolic::power(x(), Symbolic::fraction(1, 2))),
+            "1/(2Sqrt(x))"
+        );
+        assert_eq!(
+            derive(Symbolic::function(
+                HigherOrderFunc::NaturalLogarithm(1),
+                vec![x()]
+            )),
+            "1/x"
+        );
+        assert!(derivative(
+            &Symbolic::function(HigherOrderFunc::Factorial(1), vec![x()]),
+            "x",
+            &AngleMode::Radians
+        )
+        .is_err());
+    }
+}
@@ -1,30 +1,92 @@
 use core::fmt::Display;
 use core::panic;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::Num;
 use phf_macros::phf_map;
 
+use crate::decimal;
+use crate::environment::Environment;
 use crate::error::{CLMathError, TokenizerError, TokenizerErrorType};
 
 use crate::functions::{
-    ElementaryFunc, Func, ELEMENTARY_FUNC_KEYWORDS, HIGHER_ORDER_FUNC_KEYWORDS,
+    Constant, ElementaryFunc, Func, CONSTANT_KEYWORDS, CONVERSION_KEYWORD,
+    ELEMENTARY_FUNC_KEYWORDS, HIGHER_ORDER_FUNC_KEYWORDS, UNARY_FUNC_KEYWORDS,
 };
+use crate::units::UNIT_KEYWORDS;
+
+/// The position of a part of the input, from the index of its first char to the index after its last char.
+/// Tokens that are not written in the input, e.g. implicit multiplications, have an empty span where they are inserted.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns the span from the start of this span to the end of the other span
+    pub fn to(self, other: Span) -> Span {
+        Span::new(self.start, other.end.max(self.start))
+    }
+}
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Variable {
     pub name: String,
-    pub value: Option<f64>,
+    /// True if the name is used as a unit, e.g. `m` in `5 m`. Other variables with the name of a unit are free variables.
+    pub is_unit: bool,
 }
 
 impl Variable {
-    pub fn new(name: String, value: Option<f64>) -> Variable {
-        Variable { name, value }
+    pub fn new(name: String) -> Variable {
+        Variable {
+            name,
+            is_unit: false,
+        }
+    }
+
+    pub fn unit(name: String) -> Variable {
+        Variable {
+            name,
+            is_unit: true,
+        }
     }
 }
 
 impl Display for Variable {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self.value {
-            Some(value) => write!(f, "{} = {}", self.name, value),
-            None => write!(f, "{} = undefined", self.name),
+        write!(f, "{}", self.name)
+    }
+}
+
+/// A number literal. Literals without a decimal point are integers and stay exact.
+/// Literals with a decimal point keep their exact value, so they can be used as fractions in exact mode.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Number {
+    Integer(BigInt),
+    Decimal(BigRational),
+    /// The imaginary unit `i`, which is reserved and cannot be used as a variable name
+    Imaginary,
+}
+
+impl Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Number::Integer(num) => write!(f, "{}", num),
+            // The literal had finitely many digits, which are all kept with enough significant digits
+            Number::Decimal(num) => {
+                let digits = num.numer().to_string().len() + 4 * num.denom().to_string().len();
+                write!(
+                    f,
+                    "{}",
+                    decimal::format(&decimal::from_rational(num, digits as u64))
+                )
+            }
+            Number::Imaginary => write!(f, "i"),
         }
     }
 }
@@ -32,7 +94,8 @@ impl Display for Variable {
 #[derive(Debug, PartialEq, Clone)]
 pub enum Token {
     Function(Func),
-    Number(f64),
+    Number(Number),
+    Constant(Constant),
     Variable(Variable),
     Symbol(Symbol),
     Empty,
@@ -52,21 +115,34 @@ enum TokenType {
 pub enum Symbol {
     OpeningBracket,
     ClosingBracket,
+    OpeningSquareBracket,
+    ClosingSquareBracket,
     Comma,
+    Equals,
+    Arrow,
 }
 
 const SYMBOLS: phf::Map<char, Symbol> = phf_map! {
     '(' => Symbol::OpeningBracket,
     ')' => Symbol::ClosingBracket,
-    ',' => Symbol::Comma
+    '[' => Symbol::OpeningSquareBracket,
+    ']' => Symbol::ClosingSquareBracket,
+    ',' => Symbol::Comma,
+    '=' => Symbol::Equals,
 };
 
+/// The only symbol consisting of two chars, separating the parameters of an anonymous function from its body
+const ARROW: &str = "->";
+const IMAGINARY_UNIT: &str = "i";
+
 struct Tokenizer {
     curr_idx: usize,
     token_start_idx: usize,
     input: String,
     input_len: usize,
     tokens: Vec<Token>,
+    /// The span of every token in `tokens`
+    spans: Vec<Span>,
     curr_token_type: TokenType,
     is_done: bool,
     errors: Vec<TokenizerError>,
@@ -81,6 +157,7 @@ impl Tokenizer {
             is_done: input.is_empty(),
             input,
             tokens: Vec::new(),
+            spans: Vec::new(),
             curr_token_type: TokenType::Empty,
             errors: Vec::new(),
         }
@@ -115,6 +192,7 @@ impl Tokenizer {
                 TokenType::ElementaryFunc | TokenType::HigherOrderFunc => {
                     self.tokenize_function(&token_value)
                 }
+                TokenType::Symbol if token_value == ARROW => Token::Symbol(Symbol::Arrow),
                 TokenType::Symbol => {
                     if token_value.len() > 1 {
                         panic!("Symbols can only be one char. This function should not be called with a string of more than one char.")
@@ -124,10 +202,13 @@ impl Tokenizer {
             }
         };
 
+        let span = Span::new(self.token_start_idx, self.curr_idx + 1);
+
         self.step();
         self.token_start_idx = self.curr_idx;
 
         self.tokens.push(token);
+        self.spans.push(span);
     }
 
     fn tokenize_empty(&mut self) -> Token {
@@ -136,18 +217,40 @@ impl Tokenizer {
     }
 
     fn tokenize_number(&mut self, token_value: &str) -> Token {
-        Token::Number(token_value.parse::<f64>().unwrap_or_else(|err| {
+        if let Ok(integer) = token_value.parse::<BigInt>() {
+            return Token::Number(Number::Integer(integer));
+        }
+
+        if let Err(err) = token_value.parse::<f64>() {
             self.add_error(
                 TokenizerErrorType::InvalidNumber,
                 Some(token_value),
                 Some(err.to_string()),
             );
-            f64::NAN
-        }))
+            return Token::Empty;
+        }
+
+        // The decimal is the integer of all its digits divided by the power of ten of the number of fractional digits
+        let (whole, fraction) = token_value.split_once('.').unwrap_or((token_value, ""));
+        let digits = BigInt::from_str_radix(&format!("0{whole}{fraction}"), 10)
+            .expect("The number should only contain digits after parsing it.");
+
+        Token::Number(Number::Decimal(BigRational::new(
+            digits,
+            BigInt::from(10).pow(fraction.len() as u32),
+        )))
     }
 
     fn tokenize_variable(&self, token_value: &str) -> Token {
-        Token::Variable(Variable::new(token_value.to_string(), None))
+        if token_value == IMAGINARY_UNIT {
+            return Token::Number(Number::Imaginary);
+        }
+
+        if token_value == CONVERSION_KEYWORD {
+            return Token::Function(Func::Elementary(ElementaryFunc::Conversion));
+        }
+
+        Token::Variable(Variable::new(token_value.to_string()))
     }
 
     fn tokenize_function(&mut self, token_value: &str) -> Token {
@@ -157,7 +260,15 @@ impl Tokenizer {
                     panic!("Elementary functions can only be one char. This function should not be called with a string of more than one char if the current token type is 'ElementaryFunc'.")
                 }
 
-                ELEMENTARY_FUNC_KEYWORDS.get(&token_value.chars().nth(0).unwrap()).map_or_else(
+                let c = token_value.chars().nth(0).unwrap();
+
+                if self.is_operand_expected() {
+                    if let Some(func) = UNARY_FUNC_KEYWORDS.get(&c) {
+                        return Token::Function(Func::Unary(func.clone()));
+                    }
+                }
+
+                ELEMENTARY_FUNC_KEYWORDS.get(&c).map_or_else(
                 || {
                     self.add_error(TokenizerErrorType::InvalidFunctionName, Some(token_value), None);
                     Token::Empty
@@ -165,6 +276,15 @@ impl Tokenizer {
                 |func| Token::Function(Func::Elementary(func.clone())),
             )},
 
+            TokenType::HigherOrderFunc if CONSTANT_KEYWORDS.contains_key(token_value) => {
+                Token::Constant(CONSTANT_KEYWORDS.get(token_value).unwrap().clone())
+            }
+
+            // Units starting with an uppercase letter, e.g. `N` or `Hz`, cannot be variables and are always units
+            TokenType::HigherOrderFunc if !HIGHER_ORDER_FUNC_KEYWORDS.contains_key(token_value) && UNIT_KEYWORDS.contains_key(token_value) => {
+                Token::Variable(Variable::unit(token_value.to_string()))
+            }
+
             TokenType::HigherOrderFunc => HIGHER_ORDER_FUNC_KEYWORDS.get(token_value).map_or_else(
                 || {
                     self.add_error(TokenizerErrorType::InvalidFunctionName, Some(token_value), None);
@@ -181,7 +301,11 @@ impl Tokenizer {
             match symbol {
                 Symbol::OpeningBracket => Token::Symbol(Symbol::OpeningBracket),
                 Symbol::ClosingBracket => Token::Symbol(Symbol::ClosingBracket),
+                Symbol::OpeningSquareBracket => Token::Symbol(Symbol::OpeningSquareBracket),
+                Symbol::ClosingSquareBracket => Token::Symbol(Symbol::ClosingSquareBracket),
                 Symbol::Comma => Token::Symbol(Symbol::Comma),
+                Symbol::Equals => Token::Symbol(Symbol::Equals),
+                Symbol::Arrow => Token::Symbol(Symbol::Arrow),
             }
         } else {
             self.add_error(
@@ -227,11 +351,50 @@ impl Tokenizer {
         self.errors.push(TokenizerError::new(
             error,
             error_type,
-            self.token_start_idx,
-            self.curr_idx,
+            Span::new(self.token_start_idx, self.curr_idx + 1),
         ))
     }
 
+    /// Returns true if the next token has to start a new operand, e.g. at the start of the input or after an elementary function
+    fn is_operand_expected(&self) -> bool {
+        matches!(
+            self.tokens
+                .iter()
+                .rev()
+                .find(|token| **token != Token::Empty),
+            None | Some(Token::Function(Func::Elementary(_)))
+                | Some(Token::Function(Func::Unary(_)))
+                | Some(Token::Symbol(Symbol::OpeningBracket))
+                | Some(Token::Symbol(Symbol::OpeningSquareBracket))
+                | Some(Token::Symbol(Symbol::Comma))
+                | Some(Token::Symbol(Symbol::Equals))
+                | Some(Token::Symbol(Symbol::Arrow))
+        )
+    }
+
+    /// Includes directly following digits and letters in the current function name if the longer name is a keyword, e.g. `Atan2` or `Ln1p`
+    fn step_over_keyword_alphanumerics(&mut self) {
+        let alphanumerics = self
+            .input
+            .chars()
+            .skip(self.curr_idx + 1)
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .count();
+
+        if let Some(length) = (1..=alphanumerics).rev().find(|length| {
+            self.input
+                .get(self.token_start_idx..=self.curr_idx + length)
+                .is_some_and(|name| {
+                    HIGHER_ORDER_FUNC_KEYWORDS.contains_key(name)
+                        || CONSTANT_KEYWORDS.contains_key(name)
+                })
+        }) {
+            for _ in 0..length {
+                self.step();
+            }
+        }
+    }
+
     fn get_char(&self) -> char {
         match self.input.chars().nth(self.curr_idx) {
             Some(c) => c,
@@ -240,7 +403,21 @@ impl Tokenizer {
     }
 
     fn remove_empty_tokens(&mut self) {
-        self.tokens.retain(|token| *token != Token::Empty);
+        let (tokens, spans) = self
+            .tokens
+            .drain(..)
+            .zip(self.spans.drain(..))
+            .filter(|(token, _)| *token != Token::Empty)
+            .unzip();
+
+        self.tokens = tokens;
+        self.spans = spans;
+    }
+
+    /// Inserts a token that is not written in the input with an empty span at the provided position
+    fn insert_implicit(&mut self, idx: usize, token: Token, position: usize) {
+        self.tokens.insert(idx, token);
+        self.spans.insert(idx, Span::new(position, position));
     }
 
     fn is_symbol(c: &char) -> bool {
@@ -252,7 +429,7 @@ impl Tokenizer {
     }
 
     fn is_number(c: &char) -> bool {
-        c.is_numeric() || *c == '.' 
+        c.is_numeric() || *c == '.'
     }
 
     fn add_multiplications(&mut self) {
@@ -262,11 +439,14 @@ impl Tokenizer {
             if idx + 1 != self.tokens.len() {
                 match token {
                     Token::Number(_)
+                    | Token::Constant(_)
                     | Token::Symbol(Symbol::ClosingBracket)
                     | Token::Variable(_) => {
                         match self.tokens.get(idx + 1).unwrap() {
                             Token::Function(Func::HigherOrder(_))
+                            | Token::Function(Func::User(_))
                             | Token::Number(_)
+                            | Token::Constant(_)
                             | Token::Symbol(Symbol::OpeningBracket)
                             | Token::Variable(_) => multiplication_idx.push(idx + 1),
                             _ => (),
@@ -278,17 +458,177 @@ impl Tokenizer {
         }
 
         for idx in multiplication_idx.iter().rev() {
-            self.tokens.insert(
+            self.insert_implicit(
                 *idx,
                 Token::Function(Func::Elementary(ElementaryFunc::Multiplication)),
+                self.spans[*idx].start,
+            );
+        }
+    }
+
+    /// Returns true if the tokens up to the index end with a unit or the power of a unit, e.g. `m` or `s^2`
+    fn ends_with_unit(&self, idx: usize) -> bool {
+        let unit_idx = match (
+            idx.checked_sub(2).map(|idx| &self.tokens[idx]),
+            idx.checked_sub(1).map(|idx| &self.tokens[idx]),
+            &self.tokens[idx],
+        ) {
+            (_, _, Token::Variable(var)) if var.is_unit => return true,
+            (
+                _,
+                Some(Token::Function(Func::Elementary(ElementaryFunc::Exponentiation))),
+                Token::Number(_),
+            ) => idx.checked_sub(2),
+            (
+                Some(Token::Function(Func::Elementary(ElementaryFunc::Exponentiation))),
+                Some(Token::Function(Func::Unary(_))),
+                Token::Number(_),
+            ) => idx.checked_sub(3),
+            _ => None,
+        };
+
+        matches!(unit_idx.map(|idx| &self.tokens[idx]), Some(Token::Variable(var)) if var.is_unit)
+    }
+
+    /// Returns the index after the unit at the index and its exponent if it has one, e.g. after `s^-1`
+    fn end_of_unit_power(&self, unit_idx: usize) -> usize {
+        let end = unit_idx + 1;
+        if let Some(Token::Function(Func::Elementary(ElementaryFunc::Exponentiation))) =
+            self.tokens.get(end)
+        {
+            let exponent_idx = match self.tokens.get(end + 1) {
+                Some(Token::Function(Func::Unary(_))) => end + 2,
+                _ => end + 1,
+            };
+
+            if let Some(Token::Number(_)) = self.tokens.get(exponent_idx) {
+                return exponent_idx + 1;
+            }
+        }
+
+        end
+    }
+
+    /// Marks variables with the name of a unit as units if they directly follow a number, e.g. `5 m`, a conversion,
+    /// e.g. `to m`, or another unit they are multiplied or divided by, e.g. `m/s`, or written next to, e.g. `N m`.
+    /// All other names of units are free variables.
+    fn mark_units(&mut self) {
+        for idx in 0..self.tokens.len() {
+            let Token::Variable(var) = &self.tokens[idx] else {
+                continue;
+            };
+
+            if var.is_unit || !UNIT_KEYWORDS.contains_key(var.name.as_str()) || idx == 0 {
+                continue;
+            }
+
+            let is_unit = match &self.tokens[idx - 1] {
+                Token::Number(_) => true,
+                Token::Function(Func::Elementary(ElementaryFunc::Conversion)) => true,
+                Token::Function(Func::Elementary(
+                    ElementaryFunc::Multiplication | ElementaryFunc::Division,
+                )) => idx >= 2 && self.ends_with_unit(idx - 2),
+                _ => self.ends_with_unit(idx - 1),
+            };
+
+            if is_unit {
+                self.tokens[idx] = Token::Variable(Variable::unit(var.name.clone()));
+            }
+        }
+    }
+
+    /// Groups numbers that are directly followed by a unit into one operand, so that `1 km / 2 h` is `(1 km) / (2 h)`.
+    /// A power of the unit belongs to the group as well, e.g. `2 m^2` or `3 s^-1`, and so do units written next to it,
+    /// e.g. `5 N m`.
+    fn group_quantities(&mut self) {
+        let mut idx = 0;
+
+        while idx + 1 < self.tokens.len() {
+            let is_quantity = matches!(
+                (&self.tokens[idx], &self.tokens[idx + 1]),
+                (Token::Number(_), Token::Variable(var)) if var.is_unit
             );
+
+            if !is_quantity {
+                idx += 1;
+                continue;
+            }
+
+            let mut end = self.end_of_unit_power(idx + 1);
+            while matches!(self.tokens.get(end), Some(Token::Variable(var)) if var.is_unit) {
+                end = self.end_of_unit_power(end);
+            }
+
+            self.insert_implicit(
+                end,
+                Token::Symbol(Symbol::ClosingBracket),
+                self.spans[end - 1].end,
+            );
+            self.insert_implicit(
+                idx,
+                Token::Symbol(Symbol::OpeningBracket),
+                self.spans[idx].start,
+            );
+            idx = end + 2;
+        }
+    }
+
+    /// Returns the name of the function if the tokens define a function, e.g. `f(x, y) = x + y`
+    fn get_defined_function_name(&self) -> Option<String> {
+        let name = match (self.tokens.first(), self.tokens.get(1)) {
+            (Some(Token::Variable(var)), Some(Token::Symbol(Symbol::OpeningBracket))) => {
+                var.name.clone()
+            }
+            _ => return None,
+        };
+
+        let mut idx = 2;
+        loop {
+            match (self.tokens.get(idx), self.tokens.get(idx + 1)) {
+                (Some(Token::Variable(_)), Some(Token::Symbol(Symbol::Comma))) => idx += 2,
+                (Some(Token::Variable(_)), Some(Token::Symbol(Symbol::ClosingBracket))) => break,
+                _ => return None,
+            }
+        }
+
+        matches!(
+            self.tokens.get(idx + 2),
+            Some(Token::Symbol(Symbol::Equals))
+        )
+        .then_some(name)
+    }
+
+    /// Turns variables that are directly followed by an opening bracket into calls,
+    /// if a function with their name is defined in the environment or by the input itself
+    fn mark_user_functions(&mut self, env: &Environment) {
+        let defined_function_name = self.get_defined_function_name();
+
+        for idx in 0..self.tokens.len() {
+            let name = match (self.tokens.get(idx), self.tokens.get(idx + 1)) {
+                (Some(Token::Variable(var)), Some(Token::Symbol(Symbol::OpeningBracket))) => {
+                    &var.name
+                }
+                _ => continue,
+            };
+
+            if env.is_callable(name) || defined_function_name.as_ref() == Some(name) {
+                self.tokens[idx] = Token::Function(Func::User(name.clone()));
+            }
         }
     }
 
-    fn run(&mut self) {
+    fn run(&mut self, env: &Environment) {
         while !self.is_done {
             match self.get_char() {
-                c if c.is_whitespace() => self.step(),
+                c if c.is_whitespace() => {
+                    self.step();
+                    self.token_start_idx = self.curr_idx;
+                }
+                '-' if self.peek() == Some('>') => {
+                    self.curr_token_type = TokenType::Symbol;
+                    self.step();
+                    self.consume();
+                }
                 c if Tokenizer::is_symbol(&c) => {
                     self.curr_token_type = TokenType::Symbol;
                     self.consume();
@@ -311,6 +651,10 @@ impl Tokenizer {
 
                         self.step();
                     }
+
+                    if self.curr_token_type == TokenType::HigherOrderFunc {
+                        self.step_over_keyword_alphanumerics();
+                    }
                     self.consume();
                 }
                 c if c.is_numeric() => {
@@ -337,14 +681,18 @@ impl Tokenizer {
         }
 
         self.remove_empty_tokens();
+        self.mark_user_functions(env);
+        self.mark_units();
+        self.group_quantities();
         self.add_multiplications();
     }
 }
 
-pub fn tokenize(input: String) -> Result<Vec<Token>, Vec<CLMathError>> {
+/// Splits the input into tokens with their spans
+pub fn tokenize(input: String, env: &Environment) -> Result<Vec<(Token, Span)>, Vec<CLMathError>> {
     let mut tokenizer = Tokenizer::from(input);
 
-    tokenizer.run();
+    tokenizer.run(env);
 
     if !tokenizer.errors.is_empty() {
         return Err(tokenizer
@@ -354,5 +702,79 @@ pub fn tokenize(input: String) -> Result<Vec<Token>, Vec<CLMathError>> {
             .collect());
     }
 
-    Ok(tokenizer.tokens)
+    Ok(tokenizer.tokens.into_iter().zip(tokenizer.spans).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(input: &str) -> Vec<Token> {
+        tokenize(input.to_string(), &Environment::new())
+            .unwrap()
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect()
+    }
+
+    /// Returns the names of the variables of the input and whether they are units
+    fn variables(input: &str) -> Vec<(String, bool)> {
+        tokens(input)
+            .into_iter()
+            .filter_map(|token| match token {
+                Token::Variable(var) => Some((var.name, var.is_unit)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn var(name: &str, is_unit: bool) -> (String, bool) {
+        (name.to_string(), is_unit)
+    }
+
+    #[test]
+    fn test_units_and_variables() {
+        assert_eq!(variables("5 m"), vec![var("m", true)]);
+        assert_eq!(variables("m + 1"), vec![var("m", false)]);
+        assert_eq!(variables("t + 1"), vec![var("t", false)]);
+        assert_eq!(variables("2 x"), vec![var("x", false)]);
+        assert_eq!(variables("2 * s"), vec![var("s", false)]);
+        assert_eq!(variables("2 m * s"), vec![var("m", true), var("s", true)]);
+        assert_eq!(
+            variables("x to km/h"),
+            vec![var("x", false), var("km", true), var("h", true)]
+        );
+        assert_eq!(variables("5 N m"), vec![var("N", true), var("m", true)]);
+        assert_eq!(
+            variables("3 m s^-1 + d"),
+            vec![var("m", true), var("s", true), var("d", false)]
+        );
+    }
+
+    #[test]
+    fn test_quantity_groups() {
+        let multiplication = Token::Function(Func::Elementary(ElementaryFunc::Multiplication));
+        let opening = Token::Symbol(Symbol::OpeningBracket);
+        let closing = Token::Symbol(Symbol::ClosingBracket);
+
+        // `5 N m` is `(5*N*m)`
+        assert_eq!(
+            tokens("5 N m"),
+            vec![
+                opening.clone(),
+                Token::Number(Number::Integer(BigInt::from(5))),
+                multiplication.clone(),
+                Token::Variable(Variable::unit("N".to_string())),
+                multiplication.clone(),
+                Token::Variable(Variable::unit("m".to_string())),
+                closing.clone(),
+            ]
+        );
+
+        // The power of a unit belongs to the group, `2 x` is not a quantity
+        let grouped = tokens("2 m^2 / 2 x");
+        assert_eq!(grouped.first(), Some(&opening));
+        assert_eq!(grouped.iter().filter(|token| **token == closing).count(), 1);
+        assert_eq!(grouped[grouped.len() - 5], closing);
+    }
 }
@@ -0,0 +1,496 @@
+use std::fmt::Display;
+
+use phf_macros::phf_map;
+
+use crate::{
+    error::{FunctionError, FunctionErrorType},
+    functions::FunctionReturnType,
+};
+
+/// The exponents of the SI base dimensions length, mass, time, electric current, temperature, amount of substance and luminous intensity
+pub type Dimension = [i32; 7];
+
+const BASE_UNITS: [&str; 7] = ["m", "kg", "s", "A", "K", "mol", "cd"];
+
+/// A unit that can follow a number, e.g. `5 km`
+#[derive(Debug)]
+pub struct UnitDefinition {
+    /// The value of one of this unit in SI base units
+    pub scale: f64,
+    pub dimension: Dimension,
+}
+
+const fn unit(scale: f64, dimension: Dimension) -> UnitDefinition {
+    UnitDefinition { scale, dimension }
+}
+
+const LENGTH: Dimension = [1, 0, 0, 0, 0, 0, 0];
+const MASS: Dimension = [0, 1, 0, 0, 0, 0, 0];
+const TIME: Dimension = [0, 0, 1, 0, 0, 0, 0];
+const CURRENT: Dimension = [0, 0, 0, 1, 0, 0, 0];
+const TEMPERATURE: Dimension = [0, 0, 0, 0, 1, 0, 0];
+const AMOUNT: Dimension = [0, 0, 0, 0, 0, 1, 0];
+const LUMINOSITY: Dimension = [0, 0, 0, 0, 0, 0, 1];
+const VOLUME: Dimension = [3, 0, 0, 0, 0, 0, 0];
+const FREQUENCY: Dimension = [0, 0, -1, 0, 0, 0, 0];
+const FORCE: Dimension = [1, 1, -2, 0, 0, 0, 0];
+const PRESSURE: Dimension = [-1, 1, -2, 0, 0, 0, 0];
+const ENERGY: Dimension = [2, 1, -2, 0, 0, 0, 0];
+const POWER: Dimension = [2, 1, -3, 0, 0, 0, 0];
+const CHARGE: Dimension = [0, 0, 1, 1, 0, 0, 0];
+const VOLTAGE: Dimension = [2, 1, -3, -1, 0, 0, 0];
+
+/// Units are only used for names that are not bound to a variable, so variables can still be called e.g. `m` or `h`
+pub const UNIT_KEYWORDS: phf::Map<&'static str, UnitDefinition> = phf_map! {
+    "m" => unit(1.0, LENGTH),
+    "km" => unit(1e3, LENGTH),
+    "cm" => unit(1e-2, LENGTH),
+    "mm" => unit(1e-3, LENGTH),
+    "nm" => unit(1e-9, LENGTH),
+    "in" => unit(0.0254, LENGTH),
+    "ft" => unit(0.3048, LENGTH),
+    "yd" => unit(0.9144, LENGTH),
+    "mi" => unit(1609.344, LENGTH),
+    "kg" => unit(1.0, MASS),
+    "g" => unit(1e-3, MASS),
+    "mg" => unit(1e-6, MASS),
+    "t" => unit(1e3, MASS),
+    "lb" => unit(0.45359237, MASS),
+    "oz" => unit(0.028349523125, MASS),
+    "s" => unit(1.0, TIME),
+    "ms" => unit(1e-3, TIME),
+    "min" => unit(60.0, TIME),
+    "h" => unit(3600.0, TIME),
+    "d" => unit(86400.0, TIME),
+    "A" => unit(1.0, CURRENT),
+    "K" => unit(1.0, TEMPERATURE),
+    "mol" => unit(1.0, AMOUNT),
+    "cd" => unit(1.0, LUMINOSITY),
+    "l" => unit(1e-3, VOLUME),
+    "ml" => unit(1e-6, VOLUME),
+    "Hz" => unit(1.0, FREQUENCY),
+    "N" => unit(1.0, FORCE),
+    "Pa" => unit(1.0, PRESSURE),
+    "bar" => unit(1e5, PRESSURE),
+    "J" => unit(1.0, ENERGY),
+    "kJ" => unit(1e3, ENERGY),
+    "cal" => unit(4.184, ENERGY),
+    "kcal" => unit(4184.0, ENERGY),
+    "eV" => unit(1.602176634e-19, ENERGY),
+    "W" => unit(1.0, POWER),
+    "kW" => unit(1e3, POWER),
+    "C" => unit(1.0, CHARGE),
+    "V" => unit(1.0, VOLTAGE),
+};
+
+/// A product of powers of units, e.g. `km/h` or `m/s^2`. Units keep the order in which they were first used.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Unit {
+    factors: Vec<(&'static str, i32)>,
+}
+
+impl Unit {
+    /// Returns the unit with the provided name if it is known
+    pub fn from_name(name: &str) -> Option<Unit> {
+        UNIT_KEYWORDS.get_key(name).map(|name| Unit {
+            factors: vec![(*name, 1)],
+        })
+    }
+
+    /// Returns the product of the powers of known units, e.g. `m/s` for `[("m", 1), ("s", -1)]`
+    pub fn from_factors(factors: &[(&'static str, i32)]) -> Unit {
+        Unit {
+            factors: factors.to_vec(),
+        }
+    }
+
+    /// Returns the exponents of the base dimensions of the unit
+    pub fn get_dimension(&self) -> Dimension {
+        let mut dimension = [0; 7];
+
+        for (name, exponent) in &self.factors {
+            for (base, unit_base) in dimension.iter_mut().zip(UNIT_KEYWORDS[name].dimension) {
+                *base += unit_base * exponent;
+            }
+        }
+
+        dimension
+    }
+
+    /// Returns the value of one of this unit in SI base units
+    pub fn get_scale(&self) -> f64 {
+        self.factors
+            .iter()
+            .map(|(name, exponent)| UNIT_KEYWORDS[name].scale.powi(*exponent))
+            .product()
+    }
+
+    pub fn is_dimensionless(&self) -> bool {
+        self.get_dimension() == [0; 7]
+    }
+
+    /// Raises every factor of the unit to the power
+    fn pow(&self, exponent: i32) -> Unit {
+        Unit {
+            factors: self
+                .factors
+                .iter()
+                .map(|(name, factor_exponent)| (*name, factor_exponent * exponent))
+                .collect(),
+        }
+    }
+
+    /// Multiplies the units. Factors of the second unit that measure the same dimension as a factor
+    /// of the first one are converted to it, e.g. `km * m` results in `km^2`, and the returned scale
+    /// is the factor by which the value of the product has to be multiplied for the conversion.
+    fn multiply(&self, other: &Unit) -> (Unit, f64) {
+        let mut factors = self.factors.clone();
+        let mut scale = 1.0;
+
+        for (name, exponent) in &other.factors {
+            let definition = &UNIT_KEYWORDS[name];
+            let same_dimension = factors.iter().position(|(factor_name, _)| {
+                UNIT_KEYWORDS[factor_name].dimension == definition.dimension
+            });
+
+            match same_dimension {
+                Some(idx) => {
+                    let factor = &mut factors[idx];
+                    scale *= (definition.scale / UNIT_KEYWORDS[factor.0].scale).powi(*exponent);
+                    factor.1 += exponent;
+                }
+                None => factors.push((*name, *exponent)),
+            }
+        }
+
+        factors.retain(|(_, exponent)| *exponent != 0);
+
+        (Unit { factors }, scale)
+    }
+}
+
+fn format_factor(name: &str, exponent: i32) -> String {
+    if exponent == 1 {
+        name.to_string()
+    } else {
+        format!("{}^{}", name, exponent)
+    }
+}
+
+impl Display for Unit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let numerator = self
+            .factors
+            .iter()
+            .filter(|(_, exponent)| *exponent > 0)
+            .map(|(name, exponent)| format_factor(name, *exponent))
+            .collect::<Vec<_>>();
+        let denominator = self
+            .factors
+            .iter()
+            .filter(|(_, exponent)| *exponent < 0)
+            .map(|(name, exponent)| format_factor(name, -exponent))
+            .collect::<Vec<_>>();
+
+        // Units without a numerator are written with negative exponents, e.g. `s^-1`
+        if numerator.is_empty() {
+            let factors = self
+                .factors
+                .iter()
+                .map(|(name, exponent)| format_factor(name, *exponent))
+                .collect::<Vec<_>>();
+            return write!(f, "{}", factors.join("*"));
+        }
+
+        let numerator = numerator.join("*");
+
+        match denominator.len() {
+            0 => write!(f, "{numerator}"),
+            1 => write!(f, "{}/{}", numerator, denominator[0]),
+            _ => write!(f, "{}/({})", numerator, denominator.join("*")),
+        }
+    }
+}
+
+/// Formats the dimension in SI base units for error messages, e.g. `m/s` for a velocity
+fn format_dimension(dimension: &Dimension) -> String {
+    let unit = Unit {
+        factors: BASE_UNITS
+            .iter()
+            .zip(dimension)
+            .filter(|(_, exponent)| **exponent != 0)
+            .map(|(name, exponent)| (*name, *exponent))
+            .collect(),
+    };
+
+    if unit.factors.is_empty() {
+        "no unit".to_string()
+    } else {
+        unit.to_string()
+    }
+}
+
+/// A number with a unit, e.g. `9.81 m/s^2`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quantity {
+    pub value: f64,
+    pub unit: Unit,
+}
+
+impl Quantity {
+    pub fn new(value: f64, unit: Unit) -> Self {
+        Self { value, unit }
+    }
+
+    /// Returns the value of the quantity in the provided unit of the same dimension
+    fn value_in(&self, unit: &Unit) -> f64 {
+        self.value * self.unit.get_scale() / unit.get_scale()
+    }
+}
+
+impl Display for Quantity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.unit.factors.is_empty() {
+            write!(f, "{}", self.value)
+        } else {
+            write!(f, "{} {}", self.value, self.unit)
+        }
+    }
+}
+
+/// Returns a quantity, or a plain number if the units cancel each other out, e.g. for `km/m`
+fn quantity_value(quantity: Quantity) -> FunctionReturnType {
+    if quantity.unit.is_dimensionless() {
+        FunctionReturnType::F64(quantity.value * quantity.unit.get_scale())
+    } else {
+        FunctionReturnType::Quantity(quantity)
+    }
+}
+
+/// Returns an error if the quantities measure different dimensions, e.g. a length and a time
+fn check_dimensions(a: &Quantity, b: &Quantity, operation_name: &str) -> Result<(), FunctionError> {
+    let (a_dimension, b_dimension) = (a.unit.get_dimension(), b.unit.get_dimension());
+
+    if a_dimension != b_dimension {
+        return Err(FunctionError::new(
+            format!(
+                "The {} of {} and {} is not possible, because their dimensions ({} and {}) are different.",
+                operation_name,
+                a,
+                b,
+                format_dimension(&a_dimension),
+                format_dimension(&b_dimension)
+            ),
+            FunctionErrorType::DimensionMismatch,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Calculates the sum of two quantities of the same dimension in the unit of the first one
+pub fn quantity_addition(a: &Quantity, b: &Quantity) -> Result<FunctionReturnType, FunctionError> {
+    check_dimensions(a, b, "addition")?;
+    Ok(quantity_value(Quantity::new(
+        a.value + b.value_in(&a.unit),
+        a.unit.clone(),
+    )))
+}
+
+/// Calculates the difference of two quantities of the same dimension in the unit of the first one
+pub fn quantity_subtraction(
+    a: &Quantity,
+    b: &Quantity,
+) -> Result<FunctionReturnType, FunctionError> {
+    check_dimensions(a, b, "subtraction")?;
+    Ok(quantity_value(Quantity::new(
+        a.value - b.value_in(&a.unit),
+        a.unit.clone(),
+    )))
+}
+
+/// Calculates the product of two quantities, the units are multiplied as well
+pub fn quantity_multiplication(a: &Quantity, b: &Quantity) -> FunctionReturnType {
+    let (unit, scale) = a.unit.multiply(&b.unit);
+    quantity_value(Quantity::new(a.value * b.value * scale, unit))
+}
+
+/// Calculates the quotient of two quantities, the units are divided as well
+pub fn quantity_division(a: &Quantity, b: &Quantity) -> Result<FunctionReturnType, FunctionError> {
+    if b.value == 0.0 {
+        return Err(FunctionError::new(
+            format!(
+                "You cannot divide by zero. You tried to divide {} by {} which has no result.",
+                a, b
+            ),
+            FunctionErrorType::DivisionByZero,
+        ));
+    }
+
+    let (unit, scale) = a.unit.multiply(&b.unit.pow(-1));
+    Ok(quantity_value(Quantity::new(
+        a.value / b.value * scale,
+        unit,
+    )))
+}
+
+/// Calculates the modulus of the division of two quantities of the same dimension in the unit of the first one
+pub fn quantity_modulo(a: &Quantity, b: &Quantity) -> Result<FunctionReturnType, FunctionError> {
+    check_dimensions(a, b, "modulo")?;
+    Ok(quantity_value(Quantity::new(
+        a.value % b.value_in(&a.unit),
+        a.unit.clone(),
+    )))
+}
+
+/// Raises a quantity to the power of a number, e.g. `(2 m)^2` results in `4 m^2`.
+/// The exponents of the resulting unit have to be integers.
+pub fn quantity_exponentiation(
+    base: &Quantity,
+    exponent: f64,
+) -> Result<FunctionReturnType, FunctionError> {
+    let is_integer = |num: f64| num.fract() == 0.0 && num.abs() <= i32::MAX.into();
+
+    if !base
+        .unit
+        .factors
+        .iter()
+        .all(|(_, factor_exponent)| is_integer(*factor_exponent as f64 * exponent))
+    {
+        return Err(FunctionError::new(
+            format!(
+                "The exponentiation of {} and {} is not possible, because the exponents of the resulting unit would not be integers.",
+                base, exponent
+            ),
+            FunctionErrorType::DimensionMismatch,
+        ));
+    }
+
+    let unit = Unit {
+        factors: base
+            .unit
+            .factors
+            .iter()
+            .map(|(name, factor_exponent)| (*name, (*factor_exponent as f64 * exponent) as i32))
+            .collect(),
+    };
+
+    Ok(quantity_value(Quantity::new(
+        base.value.powf(exponent),
+        unit,
+    )))
+}
+
+/// Returns true if the first of two quantities of the same dimension is smaller than the second else false
+pub fn quantity_less_than(a: &Quantity, b: &Quantity) -> Result<FunctionReturnType, FunctionError> {
+    check_dimensions(a, b, "comparison")?;
+    Ok(FunctionReturnType::Bool(a.value < b.value_in(&a.unit)))
+}
+
+/// Returns true if the first of two quantities of the same dimension is greater than the second else false
+pub fn quantity_greater_than(
+    a: &Quantity,
+    b: &Quantity,
+) -> Result<FunctionReturnType, FunctionError> {
+    check_dimensions(a, b, "comparison")?;
+    Ok(FunctionReturnType::Bool(a.value > b.value_in(&a.unit)))
+}
+
+/// Returns the absolute value of a quantity
+pub fn quantity_absolute(num: &Quantity) -> FunctionReturnType {
+    FunctionReturnType::Quantity(Quantity::new(num.value.abs(), num.unit.clone()))
+}
+
+/// Returns the negated value of a quantity
+pub fn quantity_negation(num: &Quantity) -> FunctionReturnType {
+    FunctionReturnType::Quantity(Quantity::new(-num.value, num.unit.clone()))
+}
+
+/// Returns the minimum of two quantities of the same dimension
+pub fn quantity_minimum(a: &Quantity, b: &Quantity) -> Result<FunctionReturnType, FunctionError> {
+    check_dimensions(a, b, "minimum")?;
+    Ok(FunctionReturnType::Quantity(
+        if b.value_in(&a.unit) < a.value {
+            b.clone()
+        } else {
+            a.clone()
+        },
+    ))
+}
+
+/// Returns the maximum of two quantities of the same dimension
+pub fn quantity_maximum(a: &Quantity, b: &Quantity) -> Result<FunctionReturnType, FunctionError> {
+    check_dimensions(a, b, "maximum")?;
+    Ok(FunctionReturnType::Quantity(
+        if b.value_in(&a.unit) > a.value {
+            b.clone()
+        } else {
+            a.clone()
+        },
+    ))
+}
+
+/// Converts a quantity to the unit of the second quantity, which has to measure the same dimension, e.g. `100 km/h to m/s`
+pub fn unit_conversion(
+    quantity: &Quantity,
+    target: &Quantity,
+) -> Result<FunctionReturnType, FunctionError> {
+    check_dimensions(quantity, target, "conversion")?;
+    Ok(FunctionReturnType::Quantity(Quantity::new(
+        quantity.value_in(&target.unit),
+        target.unit.clone(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quantity(value: f64, name: &str) -> Quantity {
+        Quantity::new(value, Unit::from_name(name).unwrap())
+    }
+
+    #[test]
+    fn test_unit_arithmetic() {
+        assert_eq!(
+            quantity_addition(&quantity(1.0, "km"), &quantity(500.0, "m"))
+                .unwrap()
+                .to_string(),
+            "1.5 km"
+        );
+        assert_eq!(
+            quantity_multiplication(&quantity(2.0, "km"), &quantity(500.0, "m")).to_string(),
+            "1 km^2"
+        );
+        assert_eq!(
+            quantity_division(&quantity(3.0, "km"), &quantity(1.0, "m"))
+                .unwrap()
+                .to_string(),
+            "3000"
+        );
+        assert_eq!(
+            quantity_division(&quantity(10.0, "m"), &quantity(2.0, "s"))
+                .unwrap()
+                .to_string(),
+            "5 m/s"
+        );
+        assert!(quantity_addition(&quantity(1.0, "m"), &quantity(1.0, "s")).is_err());
+    }
+
+    #[test]
+    fn test_unit_conversion() {
+        assert_eq!(
+            unit_conversion(&quantity(1.0, "mi"), &quantity(1.0, "km"))
+                .unwrap()
+                .to_string(),
+            "1.609344 km"
+        );
+        assert_eq!(
+            unit_conversion(&quantity(2.0, "h"), &quantity(1.0, "min"))
+                .unwrap()
+                .to_string(),
+            "120 min"
+        );
+        assert!(unit_conversion(&quantity(1.0, "kg"), &quantity(1.0, "N")).is_err());
+    }
+}
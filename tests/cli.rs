@@ -0,0 +1,50 @@
+use std::fs;
+use std::process::{Command, Output};
+
+fn clmath(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_clmath"))
+        .args(args)
+        .output()
+        .expect("The binary should be built for integration tests.")
+}
+
+#[test]
+fn test_exit_codes() {
+    let output = clmath(&["-e", "x = 2", "-e", "x^2"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "2\n4\n");
+
+    // Errors in the inputs exit with 1
+    let output = clmath(&["-e", "1/0"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("-e:1: "));
+
+    // Invalid arguments exit with 2 and print the usage
+    let output = clmath(&["--unknown"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Usage: clmath"));
+
+    assert_eq!(clmath(&["--help"]).status.code(), Some(0));
+    assert_eq!(clmath(&["does/not/exist.txt"]).status.code(), Some(1));
+}
+
+#[test]
+fn test_read_errors() {
+    let output = clmath(&[env!("CARGO_MANIFEST_DIR")]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("CannotReadInput"), "{stderr}");
+
+    let path = std::env::temp_dir().join(format!("clmath-invalid-{}.txt", std::process::id()));
+    fs::write(&path, b"1 + 1\n\xff\xfe\n").unwrap();
+    let output = clmath(&[path.to_str().unwrap()]);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "2\n");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(":2: ") && stderr.contains("InvalidUTF8"),
+        "{stderr}"
+    );
+}